@@ -509,6 +509,33 @@ fn bench_gpu_physics(c: &mut Criterion) {
     }
 }
 
+// ---------------------------------------------------------------------------
+// SPH fluid
+// ---------------------------------------------------------------------------
+
+fn bench_sph_fluid(c: &mut Criterion) {
+    use rein_bench::{create_headless_context, setup_fluid, step_fluid};
+
+    let ctx = match create_headless_context() {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            eprintln!("SPH benchmarks skipped: {e}");
+            return;
+        }
+    };
+
+    // One 60 Hz frame (default 4 substeps), waiting for the GPU
+    let mut group = c.benchmark_group("gpu/sph_step");
+    group.sample_size(20);
+    for &n in &[10_000, 100_000] {
+        let fluid = setup_fluid(&ctx, n).expect("SPH fluid setup");
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, _| {
+            b.iter(|| step_fluid(&ctx, &fluid));
+        });
+    }
+    group.finish();
+}
+
 // ---------------------------------------------------------------------------
 // Main
 // ---------------------------------------------------------------------------
@@ -522,5 +549,6 @@ criterion_group!(
     bench_mass_physics,
     bench_sleep_effect,
     bench_gpu_physics,
+    bench_sph_fluid,
 );
 criterion_main!(benches);
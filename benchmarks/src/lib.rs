@@ -15,12 +15,156 @@
 //! Filter by group:
 //!   cargo bench --manifest-path benchmarks/Cargo.toml --bench physics -- broadphase
 //!   cargo bench --manifest-path benchmarks/Cargo.toml --bench physics -- gpu
+//!
+//! ## Scenes
+//!
+//! Scene contents live in RON files under `benchmarks/scenes`. Each holds an
+//! optional `scene` spawned as is (e.g. a ground), body templates, and the
+//! layout that places `n` bodies, cycling through the templates by body
+//! index.
 
-use glam::{Mat4, Vec3};
-use rein::ecs::components::physics::{Collider, ColliderShape, RigidBody, SleepInfo};
+use std::sync::OnceLock;
+
+use glam::Vec3;
 use rein::ecs::components::transform::{GlobalTransform, Transform};
-use rein::physics::contact::{ContactManifold, ContactPoint};
+use rein::ecs::scene::{Scene, SceneEntity};
+use rein::physics::contact::{ContactManifold, ContactMaterial, ContactPoint};
 use rein::physics::{PhysicsConfig, PhysicsWorld};
+use ron::extensions::Extensions;
+use serde::Deserialize;
+
+/// Placement of body `i` of `n`.
+#[derive(Debug, Clone, Copy, Deserialize)]
+enum Layout {
+    /// Square grid in the XZ plane with `ceil(sqrt(n))` columns, optionally
+    /// centered on the origin. Bodies cycle through `layers` heights
+    /// `layer_height` apart, starting at `height`.
+    Grid {
+        spacing: f32,
+        #[serde(default)]
+        centered: bool,
+        #[serde(default)]
+        height: f32,
+        #[serde(default = "one_layer")]
+        layers: usize,
+        #[serde(default)]
+        layer_height: f32,
+    },
+    /// Vertical column from `base` upwards.
+    Column { base: f32, spacing: f32 },
+    /// Deterministic scatter over a disc of `radius` at `height`, with
+    /// `layers` heights `layer_height` apart.
+    Scatter {
+        radius: f32,
+        height: f32,
+        layers: usize,
+        layer_height: f32,
+    },
+}
+
+fn one_layer() -> usize {
+    1
+}
+
+impl Layout {
+    fn position(&self, i: usize, n: usize) -> Vec3 {
+        match *self {
+            Layout::Grid {
+                spacing,
+                centered,
+                height,
+                layers,
+                layer_height,
+            } => {
+                let cols = (n as f32).sqrt().ceil() as usize;
+                let offset = if centered {
+                    cols as f32 * spacing * 0.5
+                } else {
+                    0.0
+                };
+                Vec3::new(
+                    (i % cols) as f32 * spacing - offset,
+                    height + (i % layers) as f32 * layer_height,
+                    (i / cols) as f32 * spacing - offset,
+                )
+            }
+            Layout::Column { base, spacing } => Vec3::new(0.0, base + i as f32 * spacing, 0.0),
+            Layout::Scatter {
+                radius,
+                height,
+                layers,
+                layer_height,
+            } => {
+                let angle = (i * 137) as f32 * 0.01;
+                let r = radius * (((i * 73 + 17) % 100) as f32 / 100.0).sqrt();
+                Vec3::new(
+                    r * angle.cos(),
+                    height + (i % layers) as f32 * layer_height,
+                    r * angle.sin(),
+                )
+            }
+        }
+    }
+}
+
+/// Contents of a scene file.
+#[derive(Deserialize)]
+struct SceneFile {
+    #[serde(default)]
+    scene: Scene,
+    layout: Layout,
+    bodies: Vec<SceneEntity>,
+}
+
+/// A benchmark scene with its body templates built into component bundles
+/// once, so spawning a body only copies a bundle and sets its position.
+struct BenchScene {
+    scene: Scene,
+    layout: Layout,
+    bodies: Vec<hecs::BuiltEntityClone>,
+}
+
+impl BenchScene {
+    /// Parse a scene file embedded from `benchmarks/scenes`.
+    fn load(text: &str) -> Self {
+        let file: SceneFile = ron::Options::default()
+            .with_default_extension(Extensions::IMPLICIT_SOME)
+            .from_str(text)
+            .expect("invalid benchmark scene");
+        Self {
+            scene: file.scene,
+            layout: file.layout,
+            bodies: file.bodies.iter().map(SceneEntity::bundle).collect(),
+        }
+    }
+
+    /// Spawn the entities placed by the file itself, e.g. a ground.
+    fn spawn_scene(&self, world: &mut hecs::World) -> Vec<hecs::Entity> {
+        self.scene.spawn(world).expect("invalid benchmark scene")
+    }
+
+    /// Spawn body `i` of `n` on the layout.
+    fn spawn_body(&self, world: &mut hecs::World, i: usize, n: usize) -> hecs::Entity {
+        let pos = self.layout.position(i, n);
+        let entity = world.spawn(&self.bodies[i % self.bodies.len()]);
+        let (transform, global) = world
+            .query_one_mut::<(&mut Transform, &mut GlobalTransform)>(entity)
+            .expect("bundles have transforms");
+        transform.position = pos;
+        global.0 = transform.to_matrix();
+        entity
+    }
+
+    /// Spawn the scene and `n` bodies into a new world.
+    fn spawn(&self, n: usize) -> hecs::World {
+        let mut world = hecs::World::new();
+        self.spawn_scene(&mut world);
+        for i in 0..n {
+            self.spawn_body(&mut world, i, n);
+        }
+        world
+    }
+}
 
 // ---------------------------------------------------------------------------
 // Basic scenes
@@ -28,147 +172,23 @@ use rein::physics::{PhysicsConfig, PhysicsWorld};
 
 /// Spawn `n` dynamic sphere bodies in a grid layout so roughly half overlap.
 pub fn setup_sphere_world(n: usize) -> hecs::World {
-    let mut world = hecs::World::new();
-    let cols = (n as f32).sqrt().ceil() as usize;
-
-    for i in 0..n {
-        let x = (i % cols) as f32 * 1.5;
-        let z = (i / cols) as f32 * 1.5;
-        let pos = Vec3::new(x, 0.0, z);
-
-        world.spawn((
-            Transform::from_position(pos),
-            GlobalTransform(Mat4::from_translation(pos)),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 1.0 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-    }
-    world
+    BenchScene::load(include_str!("../scenes/spheres.ron")).spawn(n)
 }
 
 /// Mixed scene: half dynamic spheres, half static boxes.
 pub fn setup_mixed_world(n: usize) -> hecs::World {
-    let mut world = hecs::World::new();
-    let cols = (n as f32).sqrt().ceil() as usize;
-
-    for i in 0..n {
-        let x = (i % cols) as f32 * 1.5;
-        let z = (i / cols) as f32 * 1.5;
-        let pos = Vec3::new(x, 0.0, z);
-
-        if i % 2 == 0 {
-            world.spawn((
-                Transform::from_position(pos),
-                GlobalTransform(Mat4::from_translation(pos)),
-                RigidBody::new_dynamic(1.0),
-                Collider {
-                    shape: ColliderShape::Sphere { radius: 1.0 },
-                    offset: Vec3::ZERO,
-                    is_sensor: false,
-                },
-            ));
-        } else {
-            world.spawn((
-                Transform::from_position(pos),
-                GlobalTransform(Mat4::from_translation(pos)),
-                RigidBody::new_static(),
-                Collider {
-                    shape: ColliderShape::Box {
-                        half_extents: Vec3::splat(0.5),
-                    },
-                    offset: Vec3::ZERO,
-                    is_sensor: false,
-                },
-            ));
-        }
-    }
-    world
+    BenchScene::load(include_str!("../scenes/mixed.ron")).spawn(n)
 }
 
 /// Sparse scene: bodies spread far apart (no overlaps).
 pub fn setup_sparse_world(n: usize) -> hecs::World {
-    let mut world = hecs::World::new();
-    let cols = (n as f32).sqrt().ceil() as usize;
-
-    for i in 0..n {
-        let x = (i % cols) as f32 * 10.0;
-        let z = (i / cols) as f32 * 10.0;
-        let pos = Vec3::new(x, 0.0, z);
-
-        world.spawn((
-            Transform::from_position(pos),
-            GlobalTransform(Mat4::from_translation(pos)),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 0.5 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-    }
-    world
+    BenchScene::load(include_str!("../scenes/sparse.ron")).spawn(n)
 }
 
 /// Ground plane + `n` dynamic bodies above it (mixed spheres/boxes).
 pub fn setup_scene(n: usize) -> (hecs::World, PhysicsWorld) {
-    let mut world = hecs::World::new();
-    let physics = PhysicsWorld::new(PhysicsConfig::default());
-
-    let ground_pos = Vec3::new(0.0, -0.5, 0.0);
-    world.spawn((
-        Transform::from_position(ground_pos),
-        GlobalTransform(Mat4::from_translation(ground_pos)),
-        RigidBody::new_static(),
-        Collider {
-            shape: ColliderShape::Box {
-                half_extents: Vec3::new(100.0, 0.5, 100.0),
-            },
-            offset: Vec3::ZERO,
-            is_sensor: false,
-        },
-    ));
-
-    let cols = (n as f32).sqrt().ceil() as usize;
-    for i in 0..n {
-        let x = (i % cols) as f32 * 2.0 - (cols as f32);
-        let z = (i / cols) as f32 * 2.0 - (cols as f32);
-        let y = 1.0 + (i % 5) as f32 * 1.5;
-        let pos = Vec3::new(x, y, z);
-
-        if i % 2 == 0 {
-            world.spawn((
-                Transform::from_position(pos),
-                GlobalTransform(Mat4::from_translation(pos)),
-                RigidBody::new_dynamic(1.0),
-                SleepInfo::default(),
-                Collider {
-                    shape: ColliderShape::Sphere { radius: 0.5 },
-                    offset: Vec3::ZERO,
-                    is_sensor: false,
-                },
-            ));
-        } else {
-            world.spawn((
-                Transform::from_position(pos),
-                GlobalTransform(Mat4::from_translation(pos)),
-                RigidBody::new_dynamic(1.0),
-                SleepInfo::default(),
-                Collider {
-                    shape: ColliderShape::Box {
-                        half_extents: Vec3::splat(0.4),
-                    },
-                    offset: Vec3::ZERO,
-                    is_sensor: false,
-                },
-            ));
-        }
-    }
-
-    (world, physics)
+    let world = BenchScene::load(include_str!("../scenes/drop.ron")).spawn(n);
+    (world, PhysicsWorld::new(PhysicsConfig::default()))
 }
 
 // ---------------------------------------------------------------------------
@@ -178,45 +198,18 @@ pub fn setup_scene(n: usize) -> (hecs::World, PhysicsWorld) {
 /// Stacked bodies with pre-built contact manifolds for solver benchmarks.
 pub fn setup_contacts(n: usize) -> (hecs::World, Vec<ContactManifold>) {
     let mut world = hecs::World::new();
-    let mut entities = Vec::with_capacity(n + 1);
-
-    let ground_pos = Vec3::new(0.0, -0.5, 0.0);
-    let ground = world.spawn((
-        Transform::from_position(ground_pos),
-        GlobalTransform(Mat4::from_translation(ground_pos)),
-        RigidBody::new_static(),
-        Collider {
-            shape: ColliderShape::Box {
-                half_extents: Vec3::new(50.0, 0.5, 50.0),
-            },
-            offset: Vec3::ZERO,
-            is_sensor: false,
-        },
-    ));
-    entities.push(ground);
 
+    let scene = BenchScene::load(include_str!("../scenes/stack.ron"));
+    let mut entities = scene.spawn_scene(&mut world);
     for i in 0..n {
-        let pos = Vec3::new(0.0, 0.5 + i as f32, 0.0);
-        let entity = world.spawn((
-            Transform::from_position(pos),
-            GlobalTransform(Mat4::from_translation(pos)),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Box {
-                    half_extents: Vec3::splat(0.5),
-                },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-        entities.push(entity);
+        entities.push(scene.spawn_body(&mut world, i, n));
     }
 
     let mut manifolds = Vec::new();
     for i in 0..n {
         let entity_a = entities[i];
         let entity_b = entities[i + 1];
-        let contact_y = 0.5 * (i as f32 + (i + 1) as f32);
+        let contact_y = scene.layout.position(i, n).y;
 
         manifolds.push(ContactManifold {
             entity_a,
@@ -228,6 +221,7 @@ pub fn setup_contacts(n: usize) -> (hecs::World, Vec<ContactManifold>) {
                 normal_impulse: 0.0,
                 tangent_impulse: [0.0; 2],
             }],
+            material: ContactMaterial::default(),
         });
     }
 
@@ -238,67 +232,17 @@ pub fn setup_contacts(n: usize) -> (hecs::World, Vec<ContactManifold>) {
 // Mass physics scenario (mirrors the mass_physics demo)
 // ---------------------------------------------------------------------------
 
-const SPAWN_RADIUS: f32 = 8.0;
-const SPAWN_HEIGHT: f32 = 15.0;
-
-/// Spawn a single physics object at a deterministic position.
-fn spawn_object(world: &mut hecs::World, index: usize) {
-    let is_sphere = index % 2 == 0;
-    let angle = (index * 137) as f32 * 0.01;
-    let r = SPAWN_RADIUS * (((index * 73 + 17) % 100) as f32 / 100.0).sqrt();
-    let height_jitter = (index % 5) as f32 * 0.6;
-    let pos = Vec3::new(
-        r * angle.cos(),
-        SPAWN_HEIGHT + height_jitter,
-        r * angle.sin(),
-    );
-
-    let shape = if is_sphere {
-        ColliderShape::Sphere { radius: 0.4 }
-    } else {
-        ColliderShape::Box {
-            half_extents: Vec3::splat(0.4),
-        }
-    };
-
-    world.spawn((
-        Transform::from_position(pos),
-        GlobalTransform(Mat4::from_translation(pos)),
-        RigidBody::new_dynamic(1.0),
-        SleepInfo::default(),
-        Collider {
-            shape,
-            offset: Vec3::ZERO,
-            is_sensor: false,
-        },
-    ));
+/// Scene of the mass physics scenario, loaded once so the spawning loops
+/// measure only spawning.
+fn mass_scene() -> &'static BenchScene {
+    static SCENE: OnceLock<BenchScene> = OnceLock::new();
+    SCENE.get_or_init(|| BenchScene::load(include_str!("../scenes/mass.ron")))
 }
 
 /// Ground + `initial` pre-existing falling bodies.
 pub fn setup_mass_scene(initial: usize) -> (hecs::World, PhysicsWorld) {
-    let mut world = hecs::World::new();
-    let physics = PhysicsWorld::new(PhysicsConfig::default());
-
-    // Ground (same as mass_physics demo)
-    let ground_pos = Vec3::ZERO;
-    world.spawn((
-        Transform::from_position(ground_pos),
-        GlobalTransform(Mat4::from_translation(ground_pos)),
-        RigidBody::new_static(),
-        Collider {
-            shape: ColliderShape::Box {
-                half_extents: Vec3::new(20.0, 5.0, 20.0),
-            },
-            offset: Vec3::new(0.0, -5.0, 0.0),
-            is_sensor: false,
-        },
-    ));
-
-    for i in 0..initial {
-        spawn_object(&mut world, i);
-    }
-
-    (world, physics)
+    let world = mass_scene().spawn(initial);
+    (world, PhysicsWorld::new(PhysicsConfig::default()))
 }
 
 /// Run `frames` frames, spawning `spawn_per_frame` objects each frame + physics step.
@@ -309,10 +253,12 @@ pub fn run_mass_physics(
     spawn_per_frame: usize,
     start_index: usize,
 ) {
+    let scene = mass_scene();
+    let total = start_index + frames * spawn_per_frame;
     let mut idx = start_index;
     for _ in 0..frames {
         for _ in 0..spawn_per_frame {
-            spawn_object(world, idx);
+            scene.spawn_body(world, idx, total);
             idx += 1;
         }
         physics.step(world, 1.0 / 60.0);
@@ -332,10 +278,7 @@ pub fn create_headless_context() -> anyhow::Result<WgpuContext> {
 }
 
 /// Setup a GPU-enabled physics scene: ground + `n` bodies + GPU physics init.
-pub fn setup_gpu_scene(
-    ctx: &WgpuContext,
-    n: usize,
-) -> anyhow::Result<(hecs::World, PhysicsWorld)> {
+pub fn setup_gpu_scene(ctx: &WgpuContext, n: usize) -> anyhow::Result<(hecs::World, PhysicsWorld)> {
     let (world, mut physics) = setup_scene(n);
     physics.init_gpu(ctx, n.max(256))?;
     Ok((world, physics))
@@ -360,12 +303,43 @@ pub fn run_gpu_mass_physics(
     spawn_per_frame: usize,
     start_index: usize,
 ) {
+    let scene = mass_scene();
+    let total = start_index + frames * spawn_per_frame;
     let mut idx = start_index;
     for _ in 0..frames {
         for _ in 0..spawn_per_frame {
-            spawn_object(world, idx);
+            scene.spawn_body(world, idx, total);
             idx += 1;
         }
         physics.step_gpu(world, 1.0 / 60.0, ctx);
     }
 }
+
+// ---------------------------------------------------------------------------
+// SPH fluid
+// ---------------------------------------------------------------------------
+
+use rein::physics::gpu::{SphConfig, SphFluid};
+
+/// Lattice spacing of the benchmark fluid block (water at the default
+/// smoothing radius).
+const FLUID_SPACING: f32 = 0.05;
+
+/// Setup a resting cube of at least `n` SPH particles at water density.
+pub fn setup_fluid(ctx: &WgpuContext, n: usize) -> anyhow::Result<SphFluid> {
+    // Half a spacing of slack so the lattice gets `side` particles per axis
+    let side = (n as f32).cbrt().ceil();
+    let min = Vec3::splat(-2.0);
+    let max = min + Vec3::splat((side - 0.5) * FLUID_SPACING);
+    let config = SphConfig {
+        particle_mass: SphConfig::mass_for_spacing(1000.0, FLUID_SPACING),
+        ..Default::default()
+    };
+    SphFluid::new_block(ctx, config, min, max, FLUID_SPACING)
+}
+
+/// Step `fluid` by one 60 Hz frame and wait for the GPU to finish it.
+pub fn step_fluid(ctx: &WgpuContext, fluid: &SphFluid) {
+    fluid.step(ctx, 1.0 / 60.0);
+    let _ = ctx.device.poll(wgpu::PollType::wait_indefinitely());
+}
@@ -97,7 +97,7 @@ fn main() -> anyhow::Result<()> {
             // Render axes with line material
             if let (Some(axes), Some(line_mat)) = (&state.axes, &state.line_material) {
                 line_mat.update_uniforms(frame.ctx, &state.camera, Mat4::IDENTITY);
-                pass.set_pipeline(line_mat.pipeline());
+                pass.set_pipeline(&line_mat.pipeline());
                 pass.set_bind_group(0, line_mat.camera_bind_group(), &[]);
                 pass.set_bind_group(1, line_mat.model_bind_group(), &[]);
                 pass.set_vertex_buffer(0, axes.vertex_buffer().slice());
@@ -4,7 +4,7 @@
 
 use std::sync::Arc;
 
-use glam::Vec3;
+use glam::{Mat4, Vec3};
 use rein::{
     Camera, ClearState, ColorMaterial, FrameOutput, Mesh, Window, WindowSettings, screen_target,
 };
@@ -13,19 +13,20 @@ use rein::ecs::components::rendering::{
     Visible,
 };
 use rein::ecs::components::transform::{GlobalTransform, Transform};
-use rein::ecs::systems::{culling_system, render_system, transform_system};
-use rein::renderer::light::LightType;
+use rein::ecs::systems::{RenderBuffers, culling_system, render_system, transform_system};
 
 fn main() -> anyhow::Result<()> {
     let window = Window::new(WindowSettings::default().title("ECS Scene"))?;
 
     struct State {
         world: hecs::World,
+        render_buffers: Option<RenderBuffers>,
         initialized: bool,
     }
 
     let state = State {
         world: hecs::World::new(),
+        render_buffers: None,
         initialized: false,
     };
 
@@ -44,21 +45,16 @@ fn main() -> anyhow::Result<()> {
             state.world.spawn((
                 Transform::identity(),
                 GlobalTransform::default(),
-                CameraComponent {
-                    camera,
-                    active: true,
-                },
+                CameraComponent::new(camera),
             ));
 
             // Light entity
             state.world.spawn((
-                Transform::from_position(Vec3::new(5.0, 5.0, 5.0)),
+                Transform::from_matrix(
+                    Mat4::look_at_rh(Vec3::new(5.0, 5.0, 5.0), Vec3::ZERO, Vec3::Y).inverse(),
+                ),
                 GlobalTransform::default(),
-                LightComponent {
-                    light_type: LightType::Directional,
-                    color: Vec3::ONE,
-                    intensity: 1.0,
-                },
+                LightComponent::directional(Vec3::ONE, 1.0),
             ));
 
             // Mesh entity (cube)
@@ -100,7 +96,11 @@ fn main() -> anyhow::Result<()> {
         {
             let clear = ClearState::color_and_depth([0.1, 0.1, 0.1, 1.0], 1.0);
             let mut pass = target.begin_render_pass(&mut encoder, clear);
-            render_system(&state.world, frame.ctx, &mut pass);
+            let buffers = state
+                .render_buffers
+                .get_or_insert_with(|| RenderBuffers::new(frame.ctx));
+            let size = (target.width(), target.height());
+            render_system(&state.world, frame.ctx, buffers, size, &mut pass);
         }
         frame.ctx.submit([encoder.finish()]);
 
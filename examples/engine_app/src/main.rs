@@ -4,14 +4,13 @@
 
 use std::sync::Arc;
 
-use glam::Vec3;
+use glam::{Mat4, Vec3};
 use rein::ecs::components::rendering::{
     CameraComponent, FrustumCullable, LightComponent, MaterialHandle, MeshHandle, MeshRenderer,
     Visible,
 };
 use rein::ecs::components::transform::{GlobalTransform, Transform};
 use rein::engine::{App, GameLoopConfig, SystemContext, run_app};
-use rein::renderer::light::LightType;
 use rein::{Camera, ColorMaterial, Mesh, WgpuContext, WindowSettings};
 
 struct MyApp {
@@ -33,21 +32,16 @@ impl App for MyApp {
         world.spawn((
             Transform::identity(),
             GlobalTransform::default(),
-            CameraComponent {
-                camera,
-                active: true,
-            },
+            CameraComponent::new(camera),
         ));
 
         // Light
         world.spawn((
-            Transform::from_position(Vec3::new(5.0, 5.0, 5.0)),
+            Transform::from_matrix(
+                Mat4::look_at_rh(Vec3::new(5.0, 5.0, 5.0), Vec3::ZERO, Vec3::Y).inverse(),
+            ),
             GlobalTransform::default(),
-            LightComponent {
-                light_type: LightType::Directional,
-                color: Vec3::ONE,
-                intensity: 1.0,
-            },
+            LightComponent::directional(Vec3::ONE, 1.0),
         ));
     }
 
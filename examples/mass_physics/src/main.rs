@@ -14,7 +14,6 @@ use rein::ecs::components::rendering::{
 use rein::ecs::components::transform::{GlobalTransform, Transform};
 use rein::engine::{App, GameLoopConfig, SystemContext, run_app};
 use rein::physics::{PhysicsConfig, PhysicsWorld};
-use rein::renderer::light::LightType;
 use rein::{Camera, ColorMaterial, Mesh, WgpuContext, WindowSettings};
 
 /// Objects spawned per frame
@@ -51,21 +50,16 @@ impl App for MassPhysicsApp {
         world.spawn((
             Transform::identity(),
             GlobalTransform::default(),
-            CameraComponent {
-                camera,
-                active: true,
-            },
+            CameraComponent::new(camera),
         ));
 
         // Directional light
         world.spawn((
-            Transform::from_position(Vec3::new(10.0, 20.0, 10.0)),
+            Transform::from_matrix(
+                Mat4::look_at_rh(Vec3::new(10.0, 20.0, 10.0), Vec3::ZERO, Vec3::Y).inverse(),
+            ),
             GlobalTransform::default(),
-            LightComponent {
-                light_type: LightType::Directional,
-                color: Vec3::ONE,
-                intensity: 1.0,
-            },
+            LightComponent::directional(Vec3::ONE, 1.0),
         ));
     }
 
@@ -142,6 +136,7 @@ impl MassPhysicsApp {
                 },
                 offset: Vec3::new(0.0, -5.0, 0.0),
                 is_sensor: false,
+                material: None,
             },
         ));
     }
@@ -193,6 +188,7 @@ impl MassPhysicsApp {
                 shape: collider_shape,
                 offset: Vec3::ZERO,
                 is_sensor: false,
+                material: None,
             },
         ));
 
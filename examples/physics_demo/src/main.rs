@@ -8,24 +8,19 @@ use glam::{Mat4, Vec3};
 use rein::ecs::components::physics::{Collider, ColliderShape, RigidBody};
 use rein::ecs::components::rendering::{
     CameraComponent, FrustumCullable, LightComponent, MaterialHandle, MeshHandle, MeshRenderer,
-    Visible,
+    ShadowCaster, Visible,
 };
 use rein::ecs::components::transform::{GlobalTransform, Transform};
 use rein::engine::{App, GameLoopConfig, SystemContext, run_app};
-use rein::physics::{PhysicsConfig, PhysicsWorld};
-use rein::renderer::light::LightType;
-use rein::{Camera, ColorMaterial, Mesh, WgpuContext, WindowSettings};
+use rein::physics::PhysicsConfig;
+use rein::{Camera, ColorMaterial, Mesh, ShadowConfig, WgpuContext, WindowSettings};
 
 struct PhysicsApp {
-    physics_world: Option<PhysicsWorld>,
     scene_spawned: bool,
 }
 
 impl App for PhysicsApp {
-    fn init(&mut self, _ctx: &WgpuContext, world: &mut hecs::World) {
-        // Create physics world
-        self.physics_world = Some(PhysicsWorld::new(PhysicsConfig::default()));
-
+    fn init(&mut self, ctx: &WgpuContext, world: &mut hecs::World) {
         // Camera
         let camera = Camera::new_perspective(
             Vec3::new(5.0, 5.0, 8.0),
@@ -39,21 +34,16 @@ impl App for PhysicsApp {
         world.spawn((
             Transform::identity(),
             GlobalTransform::default(),
-            CameraComponent {
-                camera,
-                active: true,
-            },
+            CameraComponent::new(camera),
         ));
 
-        // Light
+        // Shadow-casting light aimed at the origin
+        let light_pose = Mat4::look_at_rh(Vec3::new(5.0, 10.0, 5.0), Vec3::ZERO, Vec3::Y).inverse();
         world.spawn((
-            Transform::from_position(Vec3::new(5.0, 10.0, 5.0)),
+            Transform::from_matrix(light_pose),
             GlobalTransform::default(),
-            LightComponent {
-                light_type: LightType::Directional,
-                color: Vec3::ONE,
-                intensity: 1.0,
-            },
+            LightComponent::directional(Vec3::ONE, 1.0),
+            ShadowCaster::new(ctx, ShadowConfig::default()),
         ));
     }
 
@@ -85,6 +75,7 @@ impl App for PhysicsApp {
                     },
                     offset: Vec3::ZERO,
                     is_sensor: false,
+                    material: None,
                 },
             ));
 
@@ -113,6 +104,7 @@ impl App for PhysicsApp {
                     },
                     offset: Vec3::ZERO,
                     is_sensor: false,
+                    material: None,
                 },
             ));
 
@@ -125,19 +117,14 @@ impl App for PhysicsApp {
                 cam.camera.set_viewport(ctx.viewport);
             }
         }
-
-        // Step physics
-        if let Some(physics) = &mut self.physics_world {
-            physics.step(world, ctx.delta_time);
-        }
     }
 }
 
 fn main() -> anyhow::Result<()> {
     let settings = WindowSettings::default().title("Physics Demo");
-    let config = GameLoopConfig::default();
+    // The engine steps physics in its fixed loop
+    let config = GameLoopConfig::default().with_physics(PhysicsConfig::default());
     let app = PhysicsApp {
-        physics_world: None,
         scene_spawned: false,
     };
     run_app(settings, config, app)
@@ -98,7 +98,7 @@ fn main() -> anyhow::Result<()> {
             );
             let lights: Vec<&dyn Light> = vec![&state.ambient_light, &state.directional_light];
 
-            if let Some(robot) = &state.robot {
+            if let Some(robot) = &mut state.robot {
                 robot.render(frame.ctx, &state.camera, &lights, &mut pass);
             }
         }
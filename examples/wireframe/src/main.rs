@@ -111,7 +111,7 @@ fn main() -> anyhow::Result<()> {
                 // Render wireframe cube
                 if let Some(lines) = &state.cube_lines {
                     line_mat.update_uniforms(frame.ctx, &state.camera, state.cube_transform);
-                    pass.set_pipeline(line_mat.pipeline());
+                    pass.set_pipeline(&line_mat.pipeline());
                     pass.set_bind_group(0, line_mat.camera_bind_group(), &[]);
                     pass.set_bind_group(1, line_mat.model_bind_group(), &[]);
                     pass.set_vertex_buffer(0, lines.vertex_buffer().slice());
@@ -121,7 +121,7 @@ fn main() -> anyhow::Result<()> {
                 // Render spiral
                 if let Some(strip) = &state.spiral {
                     line_mat.update_uniforms(frame.ctx, &state.camera, state.spiral_transform);
-                    pass.set_pipeline(line_mat.pipeline());
+                    pass.set_pipeline(&line_mat.pipeline());
                     pass.set_bind_group(0, line_mat.camera_bind_group(), &[]);
                     pass.set_bind_group(1, line_mat.model_bind_group(), &[]);
                     pass.set_vertex_buffer(0, strip.vertex_buffer().slice());
@@ -0,0 +1,375 @@
+//! Asset store with typed handles.
+//!
+//! [`Assets<T>`] owns shared values of one type. Values are added directly or
+//! loaded from a path, either blocking or on a background thread; loading a
+//! path twice returns the same asset. [`Handle`]s are reference counted, and
+//! [`Assets::unload_unused`] frees assets nothing refers to anymore.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+
+/// Identifier of an asset within its [`Assets`] store.
+pub type AssetId = u64;
+
+/// Loading state of an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetState {
+    /// A background load is still running.
+    Loading,
+    /// The asset is available from [`Assets::get`].
+    Loaded,
+    /// The background load failed with this error.
+    Failed(String),
+}
+
+enum Slot<T: ?Sized> {
+    Loading,
+    Loaded(Arc<T>),
+    Failed(String),
+}
+
+struct Entry<T: ?Sized> {
+    path: Option<PathBuf>,
+    slot: Mutex<Slot<T>>,
+}
+
+/// Reference-counted handle to an asset in an [`Assets<T>`] store.
+///
+/// The asset stays loaded while a handle exists; cloning is cheap.
+pub struct Handle<T: ?Sized> {
+    id: AssetId,
+    entry: Arc<Entry<T>>,
+}
+
+impl<T: ?Sized> Handle<T> {
+    /// The asset id.
+    pub fn id(&self) -> AssetId {
+        self.id
+    }
+
+    /// The path the asset was loaded from, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.entry.path.as_deref()
+    }
+}
+
+impl<T: ?Sized> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            entry: self.entry.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entry, &other.entry)
+    }
+}
+
+impl<T: ?Sized> Eq for Handle<T> {}
+
+impl<T: ?Sized> std::hash::Hash for Handle<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T: ?Sized> std::fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handle")
+            .field("id", &self.id)
+            .field("path", &self.entry.path)
+            .finish()
+    }
+}
+
+/// Store of shared assets of type `T`.
+///
+/// `T` may be unsized, e.g. `Assets<dyn MaterialResource>` for mixed
+/// material types. Values are handed out as `Arc<T>`, so ECS components such
+/// as `MeshHandle` can share them without copying GPU resources.
+pub struct Assets<T: ?Sized> {
+    next_id: AssetId,
+    entries: HashMap<AssetId, Arc<Entry<T>>>,
+    paths: HashMap<PathBuf, AssetId>,
+}
+
+impl<T: ?Sized> Default for Assets<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            entries: HashMap::new(),
+            paths: HashMap::new(),
+        }
+    }
+}
+
+impl<T: ?Sized + Send + Sync + 'static> Assets<T> {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a value that was not loaded from a path.
+    pub fn add(&mut self, value: impl Into<Arc<T>>) -> Handle<T> {
+        self.insert(None, Slot::Loaded(value.into()))
+    }
+
+    /// Load the asset at `path` with `loader`, blocking until done.
+    ///
+    /// If `path` was loaded (or is loading) already, returns the existing
+    /// asset without calling `loader`. Errors are returned and not stored.
+    pub fn load<V: Into<Arc<T>>>(
+        &mut self,
+        path: impl AsRef<Path>,
+        loader: impl FnOnce(&Path) -> anyhow::Result<V>,
+    ) -> anyhow::Result<Handle<T>> {
+        let path = normalize(path.as_ref());
+        if let Some(handle) = self.handle_for_key(&path) {
+            return Ok(handle);
+        }
+        let value = loader(&path)?;
+        Ok(self.insert(Some(path), Slot::Loaded(value.into())))
+    }
+
+    /// Load the asset at `path` with `loader` on a background thread.
+    ///
+    /// The handle is [`AssetState::Loading`] until the loader finishes.
+    /// Deduplicates like [`load`](Self::load); a failed load is kept as
+    /// [`AssetState::Failed`] until unloaded.
+    pub fn load_async<V: Into<Arc<T>>>(
+        &mut self,
+        path: impl AsRef<Path>,
+        loader: impl FnOnce(&Path) -> anyhow::Result<V> + Send + 'static,
+    ) -> Handle<T> {
+        let path = normalize(path.as_ref());
+        if let Some(handle) = self.handle_for_key(&path) {
+            return handle;
+        }
+        let handle = self.insert(Some(path.clone()), Slot::Loading);
+
+        // The thread holds a weak reference so an unloaded asset is dropped
+        // as soon as it finishes
+        let entry = Arc::downgrade(&handle.entry);
+        std::thread::spawn(move || {
+            let slot = match loader(&path) {
+                Ok(value) => Slot::Loaded(value.into()),
+                Err(e) => {
+                    tracing::warn!("Failed to load {}: {e:#}", path.display());
+                    Slot::Failed(format!("{e:#}"))
+                }
+            };
+            if let Some(entry) = Weak::upgrade(&entry) {
+                *entry.slot.lock().unwrap() = slot;
+            }
+        });
+        handle
+    }
+
+    /// The asset, if loaded and still in the store.
+    pub fn get(&self, handle: &Handle<T>) -> Option<Arc<T>> {
+        if !self.contains(handle) {
+            return None;
+        }
+        match &*handle.entry.slot.lock().unwrap() {
+            Slot::Loaded(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Loading state of an asset in the store, or `None` if it was unloaded.
+    pub fn state(&self, handle: &Handle<T>) -> Option<AssetState> {
+        if !self.contains(handle) {
+            return None;
+        }
+        Some(match &*handle.entry.slot.lock().unwrap() {
+            Slot::Loading => AssetState::Loading,
+            Slot::Loaded(_) => AssetState::Loaded,
+            Slot::Failed(error) => AssetState::Failed(error.clone()),
+        })
+    }
+
+    /// Whether every asset in the store finished loading, successfully or not.
+    pub fn all_loaded(&self) -> bool {
+        self.entries
+            .values()
+            .all(|entry| !matches!(*entry.slot.lock().unwrap(), Slot::Loading))
+    }
+
+    /// Handle to the asset loaded from `path`, if any.
+    pub fn handle_for_path(&self, path: impl AsRef<Path>) -> Option<Handle<T>> {
+        self.handle_for_key(&normalize(path.as_ref()))
+    }
+
+    /// Whether the asset is still in the store.
+    pub fn contains(&self, handle: &Handle<T>) -> bool {
+        self.entries
+            .get(&handle.id)
+            .is_some_and(|entry| Arc::ptr_eq(entry, &handle.entry))
+    }
+
+    /// Number of live [`Handle`]s to the asset.
+    pub fn handle_count(&self, handle: &Handle<T>) -> usize {
+        // Minus the store's own reference
+        Arc::strong_count(&handle.entry) - usize::from(self.contains(handle))
+    }
+
+    /// Remove an asset from the store.
+    ///
+    /// Existing handles and `Arc<T>`s keep the value alive, but the store no
+    /// longer returns it and loading its path again loads a fresh copy.
+    pub fn unload(&mut self, handle: &Handle<T>) -> bool {
+        if !self.contains(handle) {
+            return false;
+        }
+        self.remove(handle.id);
+        true
+    }
+
+    /// Unload every asset without [`Handle`]s or outside `Arc<T>` users.
+    /// Returns the number of unloaded assets.
+    pub fn unload_unused(&mut self) -> usize {
+        let unused: Vec<AssetId> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| {
+                Arc::strong_count(entry) == 1
+                    && match &*entry.slot.lock().unwrap() {
+                        Slot::Loaded(value) => Arc::strong_count(value) == 1,
+                        _ => true,
+                    }
+            })
+            .map(|(&id, _)| id)
+            .collect();
+        for &id in &unused {
+            self.remove(id);
+        }
+        unused.len()
+    }
+
+    /// Number of assets in the store.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the store holds no assets.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn insert(&mut self, path: Option<PathBuf>, slot: Slot<T>) -> Handle<T> {
+        let id = self.next_id;
+        self.next_id += 1;
+        if let Some(path) = &path {
+            self.paths.insert(path.clone(), id);
+        }
+        let entry = Arc::new(Entry {
+            path,
+            slot: Mutex::new(slot),
+        });
+        self.entries.insert(id, entry.clone());
+        Handle { id, entry }
+    }
+
+    fn remove(&mut self, id: AssetId) {
+        if let Some(entry) = self.entries.remove(&id) {
+            if let Some(path) = &entry.path {
+                self.paths.remove(path);
+            }
+        }
+    }
+
+    fn handle_for_key(&self, path: &Path) -> Option<Handle<T>> {
+        let id = *self.paths.get(path)?;
+        Some(Handle {
+            id,
+            entry: self.entries[&id].clone(),
+        })
+    }
+}
+
+/// Dedup key for `path`: the canonical path if it exists, else as given.
+fn normalize(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_dedups_and_unloads_unused() {
+        let mut assets = Assets::<String>::new();
+        let mut loads = 0;
+        let a = assets
+            .load("virtual/a.txt", |path| {
+                loads += 1;
+                Ok(path.display().to_string())
+            })
+            .unwrap();
+        let again = assets
+            .load("virtual/a.txt", |_| -> anyhow::Result<String> {
+                unreachable!()
+            })
+            .unwrap();
+        assert_eq!(loads, 1);
+        assert_eq!(a, again);
+        assert_eq!(assets.handle_count(&a), 2);
+        assert!(assets
+            .load("missing", |_| -> anyhow::Result<String> {
+                anyhow::bail!("not found")
+            })
+            .is_err());
+
+        let b = assets.add("b".to_string());
+        let value = assets.get(&b).unwrap();
+        drop(b);
+        drop(again);
+        // `a` has a handle and b's value is still shared
+        assert_eq!(assets.unload_unused(), 0);
+        drop(value);
+        assert_eq!(assets.unload_unused(), 1);
+        assert_eq!(assets.len(), 1);
+
+        assert!(assets.unload(&a));
+        assert!(assets.get(&a).is_none());
+        assert!(assets.handle_for_path("virtual/a.txt").is_none());
+    }
+
+    #[test]
+    fn test_load_async_reports_loading_state() {
+        let mut assets = Assets::<[u8]>::new();
+        let (release, wait) = std::sync::mpsc::channel::<()>();
+        let handle = assets.load_async("virtual/data.bin", move |_| {
+            wait.recv()?;
+            Ok(vec![1u8, 2, 3])
+        });
+        let failing = assets.load_async("virtual/bad.bin", |_| -> anyhow::Result<Vec<u8>> {
+            anyhow::bail!("corrupt")
+        });
+        assert_eq!(assets.state(&handle), Some(AssetState::Loading));
+        assert!(assets.get(&handle).is_none());
+        assert_eq!(
+            assets.load_async("virtual/data.bin", |_| Ok(vec![])),
+            handle
+        );
+
+        release.send(()).unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !assets.all_loaded() {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "background loads did not finish"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert_eq!(&*assets.get(&handle).unwrap(), &[1, 2, 3]);
+        assert_eq!(
+            assets.state(&failing),
+            Some(AssetState::Failed("corrupt".to_string()))
+        );
+    }
+}
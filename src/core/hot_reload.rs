@@ -0,0 +1,273 @@
+//! Shader hot-reloading for development.
+//!
+//! Built-in shaders are embedded with `include_str!`. With hot reload
+//! enabled (the `REIN_HOT_RELOAD` environment variable, or
+//! [`set_hot_reload`]), they are read from the crate's `src/shaders`
+//! directory instead, and [`HotShader::reload_if_changed`] rebuilds their
+//! pipelines when the file's modification time changes. A shader that fails
+//! to compile is logged and the previous pipeline kept.
+//!
+//! Reloading only needs `&self`, so materials shared through
+//! [`MaterialHandle`](crate::ecs::components::rendering::MaterialHandle) are
+//! reloaded too; the engine polls them once per frame while hot reload is
+//! enabled, along with the `EffectChain` and `IdBuffer` resources, its shadow
+//! caster and selection mask passes, and GPU physics. Types the engine does
+//! not own (`Skybox`, `LineMaterial`, `SphFluid`, ...) have their own
+//! `reload_shaders` to call from the app. The GUI shader is the only one
+//! built in that is not reloadable.
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, RwLock, RwLockReadGuard};
+use std::time::SystemTime;
+
+/// 0 = not decided yet (read the environment), 1 = off, 2 = on.
+static HOT_RELOAD: AtomicU8 = AtomicU8::new(0);
+
+/// Whether built-in shaders are loaded from disk and watched.
+///
+/// Defaults to whether the `REIN_HOT_RELOAD` environment variable is set.
+pub fn hot_reload_enabled() -> bool {
+    match HOT_RELOAD.load(Ordering::Relaxed) {
+        0 => {
+            let enabled = std::env::var_os("REIN_HOT_RELOAD").is_some();
+            HOT_RELOAD.store(if enabled { 2 } else { 1 }, Ordering::Relaxed);
+            enabled
+        }
+        state => state == 2,
+    }
+}
+
+/// Enable or disable hot reload for shaders created from now on.
+pub fn set_hot_reload(enabled: bool) {
+    HOT_RELOAD.store(if enabled { 2 } else { 1 }, Ordering::Relaxed);
+}
+
+/// A WGSL shader file, optionally watched for changes.
+#[derive(Debug, Clone)]
+pub struct ShaderFile {
+    path: PathBuf,
+    embedded: Option<&'static str>,
+    watch: bool,
+}
+
+impl ShaderFile {
+    /// A built-in shader at `relative` in `src/shaders`, embedded as `source`.
+    ///
+    /// Watched when [`hot_reload_enabled`].
+    pub fn embedded(relative: &str, source: &'static str) -> Self {
+        Self {
+            path: Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src/shaders")
+                .join(relative),
+            embedded: Some(source),
+            watch: hot_reload_enabled(),
+        }
+    }
+
+    /// A shader file on disk, always watched.
+    pub fn watched(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            embedded: None,
+            watch: true,
+        }
+    }
+
+    /// The file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file is read from disk and checked for changes.
+    pub fn is_watched(&self) -> bool {
+        self.watch
+    }
+
+    /// Current source: from disk if watched, else the embedded copy.
+    ///
+    /// Falls back to the embedded copy if the file cannot be read.
+    pub fn source(&self) -> anyhow::Result<Cow<'static, str>> {
+        if self.watch {
+            match std::fs::read_to_string(&self.path) {
+                Ok(source) => return Ok(Cow::Owned(source)),
+                Err(e) if self.embedded.is_none() => {
+                    anyhow::bail!("Failed to read shader {}: {e}", self.path.display())
+                }
+                Err(e) => {
+                    tracing::warn!("Using embedded {}: {e}", self.path.display());
+                }
+            }
+        }
+        Ok(Cow::Borrowed(self.embedded.unwrap_or_default()))
+    }
+
+    fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+}
+
+/// Build function of a [`HotShader`].
+pub type ShaderBuildFn<P> = Box<dyn Fn(&str) -> anyhow::Result<P> + Send + Sync>;
+
+/// A value built from a shader, typically one or more pipelines, that is
+/// rebuilt when the shader file changes.
+pub struct HotShader<P> {
+    file: ShaderFile,
+    modified: Mutex<Option<SystemTime>>,
+    value: RwLock<P>,
+    build: ShaderBuildFn<P>,
+}
+
+impl<P> HotShader<P> {
+    /// Build the value from the current source of `file`.
+    ///
+    /// `build` receives the WGSL source, usually passing it to a
+    /// [`PipelineBuilder`](super::PipelineBuilder).
+    pub fn new(
+        file: ShaderFile,
+        build: impl Fn(&str) -> anyhow::Result<P> + Send + Sync + 'static,
+    ) -> anyhow::Result<Self> {
+        let modified = if file.watch { file.modified() } else { None };
+        let value = build(&file.source()?)?;
+        Ok(Self {
+            file,
+            modified: Mutex::new(modified),
+            value: RwLock::new(value),
+            build: Box::new(build),
+        })
+    }
+
+    /// The current value.
+    ///
+    /// Blocks a concurrent reload while the guard is held, so drop it before
+    /// calling [`reload_if_changed`](Self::reload_if_changed) on this thread.
+    pub fn get(&self) -> RwLockReadGuard<'_, P> {
+        self.value.read().unwrap()
+    }
+
+    /// The shader file.
+    pub fn file(&self) -> &ShaderFile {
+        &self.file
+    }
+
+    /// Rebuild if the watched file was modified since the last check.
+    ///
+    /// Returns whether the value was replaced. Read or build errors are
+    /// logged and the previous value kept until the file changes again.
+    pub fn reload_if_changed(&self) -> bool {
+        if !self.file.watch {
+            return false;
+        }
+        let modified = self.file.modified();
+        {
+            let mut last = self.modified.lock().unwrap();
+            if modified == *last {
+                return false;
+            }
+            *last = modified;
+        }
+
+        let rebuilt = self.file.source().and_then(|source| (self.build)(&source));
+        match rebuilt {
+            Ok(value) => {
+                *self.value.write().unwrap() = value;
+                tracing::info!("Reloaded shader {}", self.file.path.display());
+                true
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to reload shader {}, keeping the previous version: {e:#}",
+                    self.file.path.display()
+                );
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_keeps_previous_value_on_error() {
+        let path = std::env::temp_dir().join(format!("rein_hot_{}.wgsl", std::process::id()));
+        let write = |source: &str, secs: u64| {
+            std::fs::write(&path, source).unwrap();
+            let time = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(time)
+                .unwrap();
+        };
+        write("fn a() {}", 1);
+
+        let shader = HotShader::new(ShaderFile::watched(&path), |source| {
+            anyhow::ensure!(!source.contains("syntax error"), "parse failed");
+            Ok(source.to_string())
+        })
+        .unwrap();
+        assert!(!shader.reload_if_changed());
+
+        write("fn b() {}", 2);
+        assert!(shader.reload_if_changed());
+        assert_eq!(*shader.get(), "fn b() {}");
+
+        write("syntax error", 3);
+        assert!(!shader.reload_if_changed());
+        assert_eq!(*shader.get(), "fn b() {}");
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_compile_error_keeps_previous_pipeline() {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            return;
+        }
+        let Ok(ctx) = crate::context::WgpuContext::new_blocking(None) else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let path = std::env::temp_dir().join(format!("rein_hot_cs_{}.wgsl", std::process::id()));
+        std::fs::write(&path, "@compute @workgroup_size(1) fn cs_main() {}").unwrap();
+
+        let shader = HotShader::new(ShaderFile::watched(&path), move |source| {
+            crate::core::ComputePipelineBuilder::new(&ctx)
+                .label("hot reload test")
+                .shader(source)
+                .catch_errors(true)
+                .build()
+        })
+        .unwrap();
+
+        std::fs::write(&path, "@compute fn cs_main( {").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        // Logged instead of panicking in the device error handler
+        assert!(!shader.reload_if_changed());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_embedded_shader_is_not_watched_by_default() {
+        if hot_reload_enabled() {
+            return;
+        }
+        let file = ShaderFile::embedded("effects/fog.wgsl", "embedded");
+        assert!(!file.is_watched());
+        assert_eq!(file.source().unwrap(), "embedded");
+    }
+}
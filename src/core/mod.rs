@@ -3,6 +3,7 @@
 //! This module provides mid-level abstractions over wgpu primitives.
 
 pub mod buffer;
+pub mod hot_reload;
 pub mod instance;
 pub mod pipeline;
 pub mod render_states;
@@ -11,6 +12,7 @@ pub mod texture;
 pub mod vertex;
 
 pub use buffer::{IndexBuffer, RawUniformBuffer, StorageBuffer, UniformBuffer, VertexBuffer};
+pub use hot_reload::{hot_reload_enabled, set_hot_reload, HotShader, ShaderFile};
 pub use instance::{InstanceBuffer, InstanceData};
 pub use pipeline::{ComputePipelineBuilder, PipelineBuilder};
 pub use render_states::{BlendState, ClearState, CullState, DepthState};
@@ -20,6 +20,7 @@ pub struct PipelineBuilder<'a> {
     blend_state: BlendState,
     cull_state: CullState,
     topology: wgpu::PrimitiveTopology,
+    catch_errors: bool,
 }
 
 impl<'a> PipelineBuilder<'a> {
@@ -38,6 +39,7 @@ impl<'a> PipelineBuilder<'a> {
             blend_state: BlendState::Opaque,
             cull_state: CullState::Back,
             topology: wgpu::PrimitiveTopology::TriangleList,
+            catch_errors: false,
         }
     }
 
@@ -107,12 +109,28 @@ impl<'a> PipelineBuilder<'a> {
         self
     }
 
+    /// Return shader compile and validation errors from the build as `Err`
+    /// instead of raising them on the device, whose default handler panics.
+    ///
+    /// Waits for the device to report errors, so only enable it for shaders
+    /// that can change at runtime, such as watched
+    /// [`HotShader`](super::hot_reload::HotShader) files.
+    pub fn catch_errors(mut self, enabled: bool) -> Self {
+        self.catch_errors = enabled;
+        self
+    }
+
     /// Build a depth-only render pipeline (no color output).
     /// Used for shadow map generation.
     pub fn build_depth_only(self) -> anyhow::Result<wgpu::RenderPipeline> {
         let shader_source = self
             .shader_source
             .ok_or_else(|| anyhow::anyhow!("Shader source is required"))?;
+        let scope = self.catch_errors.then(|| {
+            self.ctx
+                .device
+                .push_error_scope(wgpu::ErrorFilter::Validation)
+        });
 
         let shader_module = self
             .ctx
@@ -173,6 +191,9 @@ impl<'a> PipelineBuilder<'a> {
                 cache: None,
             });
 
+        if let Some(scope) = scope {
+            pop_validation_scope(scope, self.label)?;
+        }
         Ok(pipeline)
     }
 
@@ -181,6 +202,11 @@ impl<'a> PipelineBuilder<'a> {
         let shader_source = self
             .shader_source
             .ok_or_else(|| anyhow::anyhow!("Shader source is required"))?;
+        let scope = self.catch_errors.then(|| {
+            self.ctx
+                .device
+                .push_error_scope(wgpu::ErrorFilter::Validation)
+        });
 
         let shader_module = self
             .ctx
@@ -244,6 +270,9 @@ impl<'a> PipelineBuilder<'a> {
                 cache: None,
             });
 
+        if let Some(scope) = scope {
+            pop_validation_scope(scope, self.label)?;
+        }
         Ok(pipeline)
     }
 }
@@ -264,6 +293,7 @@ pub struct ComputePipelineBuilder<'a> {
     shader_source: Option<&'a str>,
     entry_point: &'a str,
     bind_group_layouts: Vec<&'a wgpu::BindGroupLayout>,
+    catch_errors: bool,
 }
 
 impl<'a> ComputePipelineBuilder<'a> {
@@ -275,6 +305,7 @@ impl<'a> ComputePipelineBuilder<'a> {
             shader_source: None,
             entry_point: "cs_main",
             bind_group_layouts: Vec::new(),
+            catch_errors: false,
         }
     }
 
@@ -302,11 +333,27 @@ impl<'a> ComputePipelineBuilder<'a> {
         self
     }
 
+    /// Return shader compile and validation errors from the build as `Err`
+    /// instead of raising them on the device, whose default handler panics.
+    ///
+    /// Waits for the device to report errors, so only enable it for shaders
+    /// that can change at runtime, such as watched
+    /// [`HotShader`](super::hot_reload::HotShader) files.
+    pub fn catch_errors(mut self, enabled: bool) -> Self {
+        self.catch_errors = enabled;
+        self
+    }
+
     /// Build the compute pipeline.
     pub fn build(self) -> anyhow::Result<wgpu::ComputePipeline> {
         let shader_source = self
             .shader_source
             .ok_or_else(|| anyhow::anyhow!("Shader source is required"))?;
+        let scope = self.catch_errors.then(|| {
+            self.ctx
+                .device
+                .push_error_scope(wgpu::ErrorFilter::Validation)
+        });
 
         let shader_module = self
             .ctx
@@ -325,7 +372,7 @@ impl<'a> ComputePipelineBuilder<'a> {
                     immediate_size: 0,
                 });
 
-        Ok(self
+        let pipeline = self
             .ctx
             .device
             .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
@@ -335,7 +382,25 @@ impl<'a> ComputePipelineBuilder<'a> {
                 entry_point: Some(self.entry_point),
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
                 cache: None,
-            }))
+            });
+
+        if let Some(scope) = scope {
+            pop_validation_scope(scope, self.label)?;
+        }
+        Ok(pipeline)
+    }
+}
+
+/// Return validation errors raised since `scope` was pushed, such as WGSL
+/// compile errors, as an `Err` instead of letting the device's uncaptured
+/// error handler panic.
+fn pop_validation_scope(scope: wgpu::ErrorScopeGuard, label: Option<&str>) -> anyhow::Result<()> {
+    match pollster::block_on(scope.pop()) {
+        Some(error) => Err(anyhow::anyhow!(
+            "Failed to build pipeline '{}': {error}",
+            label.unwrap_or("unlabeled")
+        )),
+        None => Ok(()),
     }
 }
 
@@ -113,6 +113,64 @@ impl Texture2D {
     pub fn format(&self) -> wgpu::TextureFormat {
         self.format
     }
+
+    /// Get the raw wgpu texture.
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
+    /// Copy the texture back to the CPU, blocking until the GPU is done.
+    ///
+    /// Returns tightly packed rows, top row first. The texture must have been
+    /// created with `COPY_SRC` usage.
+    pub fn read_pixels(&self, ctx: &WgpuContext) -> anyhow::Result<Vec<u8>> {
+        let texel_size = self
+            .format
+            .block_copy_size(None)
+            .ok_or_else(|| anyhow::anyhow!("Cannot read back texture format {:?}", self.format))?;
+        let (width, height) = self.size();
+        let row_bytes = width * texel_size;
+        let padded_row_bytes = row_bytes.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let staging = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("texture readback staging"),
+            size: padded_row_bytes as u64 * height as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx.create_encoder(Some("texture readback"));
+        encoder.copy_texture_to_buffer(
+            self.texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &staging,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            self.size,
+        );
+        ctx.submit([encoder.finish()]);
+
+        let slice = staging.slice(..);
+        let (tx, rx) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        let _ = ctx.device.poll(wgpu::PollType::wait_indefinitely());
+        rx.recv()??;
+
+        let data = slice.get_mapped_range();
+        let mut pixels = Vec::with_capacity((row_bytes * height) as usize);
+        for row in data.chunks(padded_row_bytes as usize) {
+            pixels.extend_from_slice(&row[..row_bytes as usize]);
+        }
+        drop(data);
+        staging.unmap();
+        Ok(pixels)
+    }
 }
 
 /// A depth texture for depth testing.
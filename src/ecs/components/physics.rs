@@ -33,6 +33,7 @@ impl Default for SleepInfo {
 
 /// Rigid body type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
 pub enum RigidBodyType {
     /// Affected by forces and collisions.
     Dynamic,
@@ -58,8 +59,12 @@ pub struct RigidBody {
     /// Angular damping factor (default: 0.01).
     pub angular_damping: f32,
     /// Coefficient of restitution (0.0 - 1.0).
+    ///
+    /// Used only when the entity's [`Collider`] has no `material`.
     pub restitution: f32,
     /// Friction coefficient (0.0 - 1.0).
+    ///
+    /// Used only when the entity's [`Collider`] has no `material`.
     pub friction: f32,
     /// Gravity scale (default: 1.0).
     pub gravity_scale: f32,
@@ -125,6 +130,7 @@ impl RigidBody {
 
 /// Collider shape.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColliderShape {
     Sphere { radius: f32 },
     Box { half_extents: Vec3 },
@@ -134,13 +140,26 @@ pub enum ColliderShape {
 }
 
 /// Collision detection component.
+///
+/// Usually sits on the entity with the `RigidBody`. A collider on a child
+/// entity (with `Parent` and a local `Transform`) but no `RigidBody` of its
+/// own is a part of the parent's compound body: it moves with the parent and
+/// has its own `material`. The body's mass and inertia are still those set on
+/// its `RigidBody`.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
 pub struct Collider {
     pub shape: ColliderShape,
     /// Offset from the entity's transform origin.
+    #[cfg_attr(feature = "scene", serde(default))]
     pub offset: Vec3,
     /// If true, generates collision events but no physics response.
+    #[cfg_attr(feature = "scene", serde(default))]
     pub is_sensor: bool,
+    /// Surface material; without one, contacts use the `friction` and
+    /// `restitution` of the body's `RigidBody`.
+    #[cfg_attr(feature = "scene", serde(default))]
+    pub material: Option<PhysicsMaterial>,
 }
 
 impl Default for Collider {
@@ -149,6 +168,268 @@ impl Default for Collider {
             shape: ColliderShape::Sphere { radius: 0.5 },
             offset: Vec3::ZERO,
             is_sensor: false,
+            material: None,
+        }
+    }
+}
+
+/// How two materials' coefficients are combined at a contact.
+///
+/// When the two sides request different modes, the one with the higher
+/// priority wins: `Average < Min < Multiply < Max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
+pub enum CombineMode {
+    #[default]
+    Average,
+    Min,
+    Multiply,
+    Max,
+}
+
+impl CombineMode {
+    /// Combine two coefficients with this mode.
+    pub fn combine(self, a: f32, b: f32) -> f32 {
+        match self {
+            CombineMode::Average => (a + b) * 0.5,
+            CombineMode::Min => a.min(b),
+            CombineMode::Multiply => a * b,
+            CombineMode::Max => a.max(b),
+        }
+    }
+
+    /// Resolve the mode used when two materials meet.
+    pub fn resolve(a: CombineMode, b: CombineMode) -> CombineMode {
+        a.max(b)
+    }
+}
+
+/// Surface material for a collider.
+///
+/// Set as `Collider::material` to override the `restitution`/`friction`
+/// stored on `RigidBody`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhysicsMaterial {
+    /// Friction coefficient resisting the onset of sliding.
+    pub static_friction: f32,
+    /// Friction coefficient while sliding.
+    pub dynamic_friction: f32,
+    /// Coefficient of restitution (0.0 - 1.0).
+    pub restitution: f32,
+    /// Rolling resistance coefficient (length units; 0 disables).
+    pub rolling_friction: f32,
+    /// Resistance to spinning about the contact normal (length units; 0 disables).
+    pub spinning_friction: f32,
+    /// Combine rule for static, dynamic, rolling and spinning friction.
+    pub friction_combine: CombineMode,
+    /// Combine rule for restitution.
+    pub restitution_combine: CombineMode,
+}
+
+impl Default for PhysicsMaterial {
+    fn default() -> Self {
+        Self {
+            static_friction: 0.6,
+            dynamic_friction: 0.5,
+            restitution: 0.3,
+            rolling_friction: 0.0,
+            spinning_friction: 0.0,
+            friction_combine: CombineMode::Average,
+            restitution_combine: CombineMode::Average,
+        }
+    }
+}
+
+impl PhysicsMaterial {
+    /// Material with a single friction coefficient for static and dynamic friction.
+    pub fn new(friction: f32, restitution: f32) -> Self {
+        Self {
+            static_friction: friction,
+            dynamic_friction: friction,
+            restitution,
+            ..Default::default()
+        }
+    }
+
+    /// Slippery surface that stays slippery against anything.
+    pub fn ice() -> Self {
+        Self {
+            static_friction: 0.1,
+            dynamic_friction: 0.03,
+            restitution: 0.05,
+            rolling_friction: 0.0,
+            spinning_friction: 0.0,
+            friction_combine: CombineMode::Min,
+            restitution_combine: CombineMode::Average,
+        }
+    }
+
+    /// High-grip, bouncy surface.
+    pub fn rubber() -> Self {
+        Self {
+            static_friction: 1.0,
+            dynamic_friction: 0.8,
+            restitution: 0.8,
+            rolling_friction: 0.02,
+            spinning_friction: 0.02,
+            friction_combine: CombineMode::Average,
+            restitution_combine: CombineMode::Max,
+        }
+    }
+
+    /// Hard, moderately slippery surface.
+    pub fn metal() -> Self {
+        Self {
+            static_friction: 0.6,
+            dynamic_friction: 0.4,
+            restitution: 0.2,
+            rolling_friction: 0.001,
+            spinning_friction: 0.001,
+            friction_combine: CombineMode::Average,
+            restitution_combine: CombineMode::Average,
+        }
+    }
+
+    /// Material equivalent to a rigid body's legacy `friction`/`restitution`.
+    pub fn from_rigid_body(rb: &RigidBody) -> Self {
+        Self::new(rb.friction, rb.restitution)
+    }
+}
+
+/// Direction-dependent friction for a collider, e.g. treads, skis or wheels.
+///
+/// Friction along `direction` is scaled by `primary_scale` and friction across
+/// it by `secondary_scale`. When both colliders of a contact carry one, the
+/// first entity of the pair wins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnisotropicFriction {
+    /// Primary friction direction in the collider's local space.
+    pub direction: Vec3,
+    /// Friction multiplier along `direction`.
+    pub primary_scale: f32,
+    /// Friction multiplier across `direction`.
+    pub secondary_scale: f32,
+}
+
+impl AnisotropicFriction {
+    pub fn new(direction: Vec3, primary_scale: f32, secondary_scale: f32) -> Self {
+        Self {
+            direction,
+            primary_scale,
+            secondary_scale,
+        }
+    }
+}
+
+/// Kind of force applied by a [`ForceField`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForceFieldKind {
+    /// Constant acceleration (per-region gravity, updraft).
+    Directional { acceleration: Vec3 },
+    /// Acceleration toward the field origin, falling off with squared distance.
+    /// Negative strength repels.
+    PointAttractor {
+        strength: f32,
+        /// Distance below which the falloff is clamped, avoiding singularities.
+        min_distance: f32,
+    },
+    /// Swirl around an axis through the field origin.
+    Vortex {
+        /// Rotation axis in the field's local space.
+        axis: Vec3,
+        /// Tangential acceleration.
+        strength: f32,
+        /// Acceleration toward the axis (negative pushes outward).
+        inward: f32,
+    },
+    /// Drag relative to a moving medium: `F = -(linear * v + quadratic * |v| * v)`
+    /// where `v` is the body velocity minus `flow_velocity`. A non-zero
+    /// `flow_velocity` models wind or current.
+    Drag {
+        linear: f32,
+        quadratic: f32,
+        flow_velocity: Vec3,
+    },
+    /// Archimedes buoyancy for a fluid whose surface is the top of the field volume.
+    Buoyancy {
+        /// Fluid density (kg/m^3, water = 1000).
+        fluid_density: f32,
+        /// Linear drag applied proportionally to the submerged fraction.
+        linear_drag: f32,
+        /// Angular drag applied proportionally to the submerged fraction.
+        angular_drag: f32,
+    },
+}
+
+/// Force field component.
+///
+/// Affects dynamic rigid bodies inside the volume given by the entity's
+/// `Collider` (usually a sensor). Without a `Collider` the field is unbounded.
+#[derive(Debug, Clone)]
+pub struct ForceField {
+    pub kind: ForceFieldKind,
+}
+
+impl ForceField {
+    /// Constant acceleration inside the volume.
+    pub fn directional(acceleration: Vec3) -> Self {
+        Self {
+            kind: ForceFieldKind::Directional { acceleration },
+        }
+    }
+
+    /// Inverse-square attraction toward the field origin.
+    pub fn point_attractor(strength: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::PointAttractor {
+                strength,
+                min_distance: 0.5,
+            },
+        }
+    }
+
+    /// Swirl around `axis` (local space) through the field origin.
+    pub fn vortex(axis: Vec3, strength: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::Vortex {
+                axis,
+                strength,
+                inward: 0.0,
+            },
+        }
+    }
+
+    /// Drag in a still medium.
+    pub fn drag(linear: f32, quadratic: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::Drag {
+                linear,
+                quadratic,
+                flow_velocity: Vec3::ZERO,
+            },
+        }
+    }
+
+    /// Quadratic drag toward a moving air velocity.
+    pub fn wind(velocity: Vec3, quadratic: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::Drag {
+                linear: 0.0,
+                quadratic,
+                flow_velocity: velocity,
+            },
+        }
+    }
+
+    /// Water-like buoyancy with mild damping.
+    pub fn buoyancy(fluid_density: f32) -> Self {
+        Self {
+            kind: ForceFieldKind::Buoyancy {
+                fluid_density,
+                linear_drag: 1.0,
+                angular_drag: 0.5,
+            },
         }
     }
 }
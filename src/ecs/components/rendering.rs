@@ -2,10 +2,18 @@
 
 use std::sync::Arc;
 
-use glam::Vec3;
+use glam::{Mat4, Vec3};
 
-use crate::renderer::light::LightType;
-use crate::renderer::viewer::Camera;
+use crate::assets::{Assets, Handle};
+use crate::context::WgpuContext;
+use crate::core::render_states::ClearState;
+use crate::core::texture::{DepthTexture, Texture2D};
+use crate::ecs::components::transform::GlobalTransform;
+use crate::renderer::light::{Attenuation, GpuLight, LightType};
+use crate::renderer::shadow::{ShadowConfig, ShadowMap};
+use crate::renderer::viewer::{Camera, Viewer};
+#[cfg(feature = "window")]
+use crate::window::frame_io::Viewport;
 
 use super::super::bridge::MaterialResource;
 use crate::renderer::geometry::Geometry;
@@ -13,9 +21,39 @@ use crate::renderer::geometry::Geometry;
 /// Shared mesh resource handle.
 pub struct MeshHandle(pub Arc<dyn Geometry + Send + Sync>);
 
+impl MeshHandle {
+    /// Share a loaded mesh from an asset store. `None` while it is loading
+    /// or after it was unloaded.
+    pub fn from_asset<G: Geometry + Send + Sync + 'static>(
+        assets: &Assets<G>,
+        handle: &Handle<G>,
+    ) -> Option<Self> {
+        Some(Self(assets.get(handle)?))
+    }
+}
+
 /// Shared material resource handle.
 pub struct MaterialHandle(pub Arc<dyn MaterialResource>);
 
+impl MaterialHandle {
+    /// Share a loaded material from an asset store. `None` while it is
+    /// loading or after it was unloaded.
+    pub fn from_asset<M: MaterialResource + 'static>(
+        assets: &Assets<M>,
+        handle: &Handle<M>,
+    ) -> Option<Self> {
+        Some(Self(assets.get(handle)?))
+    }
+
+    /// Share a loaded material from a store of mixed material types.
+    pub fn from_dyn_asset(
+        assets: &Assets<dyn MaterialResource>,
+        handle: &Handle<dyn MaterialResource>,
+    ) -> Option<Self> {
+        Some(Self(assets.get(handle)?))
+    }
+}
+
 /// Mesh + material rendering component. ECS equivalent of `Gm<G, M>`.
 pub struct MeshRenderer {
     pub mesh: MeshHandle,
@@ -26,16 +64,369 @@ pub struct MeshRenderer {
 }
 
 /// Light component.
+///
+/// Directional and spot lights shine along the entity's -Z axis; point and
+/// spot lights sit at its origin.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "scene", serde(default))]
 pub struct LightComponent {
     pub light_type: LightType,
     pub color: Vec3,
     pub intensity: f32,
+    /// Distance attenuation of point and spot lights.
+    pub attenuation: Attenuation,
+    /// Distance at which point and spot lights fade out completely, or 0 for
+    /// no cutoff.
+    pub range: f32,
+    /// Spot cone half-angle with full intensity, in radians.
+    pub inner_angle: f32,
+    /// Spot cone half-angle where the light fades to zero, in radians.
+    pub outer_angle: f32,
+}
+
+impl LightComponent {
+    /// A light of `light_type` with default attenuation, a 50 unit range
+    /// and a 30/45 degree spot cone.
+    pub fn new(light_type: LightType, color: Vec3, intensity: f32) -> Self {
+        Self {
+            light_type,
+            color,
+            intensity,
+            attenuation: Attenuation::default(),
+            range: 50.0,
+            inner_angle: 30f32.to_radians(),
+            outer_angle: 45f32.to_radians(),
+        }
+    }
+
+    pub fn ambient(color: Vec3, intensity: f32) -> Self {
+        Self::new(LightType::Ambient, color, intensity)
+    }
+
+    pub fn directional(color: Vec3, intensity: f32) -> Self {
+        Self::new(LightType::Directional, color, intensity)
+    }
+
+    pub fn point(color: Vec3, intensity: f32, range: f32) -> Self {
+        Self::new(LightType::Point, color, intensity).with_range(range)
+    }
+
+    /// A spot light with cone half-angles in degrees.
+    pub fn spot(
+        color: Vec3,
+        intensity: f32,
+        range: f32,
+        inner_degrees: f32,
+        outer_degrees: f32,
+    ) -> Self {
+        Self::new(LightType::Spot, color, intensity)
+            .with_range(range)
+            .with_cone(inner_degrees, outer_degrees)
+    }
+
+    pub fn with_attenuation(mut self, attenuation: Attenuation) -> Self {
+        self.attenuation = attenuation;
+        self
+    }
+
+    pub fn with_range(mut self, range: f32) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Set the spot cone half-angles, in degrees.
+    pub fn with_cone(mut self, inner_degrees: f32, outer_degrees: f32) -> Self {
+        self.inner_angle = inner_degrees.to_radians();
+        self.outer_angle = outer_degrees.to_radians();
+        self
+    }
+
+    /// The light's entry in the scene light buffer for an entity with
+    /// `global` transform, sampling the shadow map if `shadowed`.
+    pub fn gpu_light(&self, global: &GlobalTransform, shadowed: bool) -> GpuLight {
+        let position = global.0.transform_point3(Vec3::ZERO);
+        let direction = global
+            .0
+            .transform_vector3(-Vec3::Z)
+            .normalize_or(Vec3::NEG_Z);
+        let a = self.attenuation;
+        GpuLight {
+            position_range: [position.x, position.y, position.z, self.range],
+            direction_kind: [
+                direction.x,
+                direction.y,
+                direction.z,
+                GpuLight::kind(self.light_type),
+            ],
+            color_intensity: [self.color.x, self.color.y, self.color.z, self.intensity],
+            attenuation: [a.constant, a.linear, a.quadratic, 0.0],
+            cone_shadow: [
+                self.inner_angle.cos(),
+                self.outer_angle.cos(),
+                if shadowed { 1.0 } else { 0.0 },
+                0.0,
+            ],
+        }
+    }
+}
+
+impl Default for LightComponent {
+    /// A white directional light of intensity 1.
+    fn default() -> Self {
+        Self::directional(Vec3::ONE, 1.0)
+    }
+}
+
+/// Region of a render target, in fractions of its size with the origin at
+/// the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewportRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl ViewportRect {
+    /// The whole render target.
+    pub const FULL: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Pixel rectangle `[x, y, width, height]` for a target of the given size,
+    /// clamped to the target.
+    pub fn to_pixels(&self, target_width: u32, target_height: u32) -> [f32; 4] {
+        let (w, h) = (target_width as f32, target_height as f32);
+        let x = (self.x * w).clamp(0.0, w);
+        let y = (self.y * h).clamp(0.0, h);
+        let width = (self.width * w).clamp(0.0, w - x);
+        let height = (self.height * h).clamp(0.0, h - y);
+        [x, y, width, height]
+    }
+}
+
+impl Default for ViewportRect {
+    fn default() -> Self {
+        Self::FULL
+    }
 }
 
 /// Camera component.
+///
+/// `camera.position`, `target` and `up` are in the entity's local space, so the
+/// camera follows its `GlobalTransform` (e.g. when parented to a robot link).
+/// With an identity transform they are world-space as usual. The projection,
+/// including its aspect ratio, comes from `camera` unchanged.
+#[derive(Debug, Clone)]
 pub struct CameraComponent {
     pub camera: Camera,
     pub active: bool,
+    /// Region of the render target this camera draws into. Several active
+    /// cameras with different rectangles give split-screen or
+    /// picture-in-picture views.
+    pub viewport: ViewportRect,
+    /// Cameras render in ascending priority, so higher priorities draw on
+    /// top. Ties are broken by entity order.
+    pub priority: i32,
+}
+
+impl CameraComponent {
+    /// An active camera covering the whole render target.
+    pub fn new(camera: Camera) -> Self {
+        Self {
+            camera,
+            active: true,
+            viewport: ViewportRect::FULL,
+            priority: 0,
+        }
+    }
+
+    pub fn with_viewport(mut self, viewport: ViewportRect) -> Self {
+        self.viewport = viewport;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// World-space view of this camera on an entity with `global` transform.
+    ///
+    /// Scale in `global` is ignored so scaled parents do not distort the view.
+    pub fn view(&self, global: &GlobalTransform) -> CameraView {
+        let (_, rotation, translation) = global.0.to_scale_rotation_translation();
+        let entity_to_world = Mat4::from_rotation_translation(rotation, translation);
+        let camera_to_world = entity_to_world * self.camera.view_matrix().inverse();
+        CameraView {
+            position: camera_to_world.transform_point3(Vec3::ZERO),
+            view: camera_to_world.inverse(),
+            projection: self.camera.projection_matrix(),
+            #[cfg(feature = "window")]
+            viewport: self.camera.viewport(),
+        }
+    }
+}
+
+/// Offscreen target for a camera entity.
+///
+/// A camera with this component renders into `color` instead of the main
+/// surface. The texture can be sampled by a material (monitors, mirrors) via
+/// `color().view()` and `color().sampler()`, or read back on the CPU with
+/// `color().read_pixels()` for simulated camera images.
+pub struct RenderTexture {
+    color: Texture2D,
+    depth: DepthTexture,
+    /// How the target is cleared before the camera renders.
+    pub clear: ClearState,
+}
+
+impl RenderTexture {
+    pub fn new(ctx: &WgpuContext, width: u32, height: u32, format: wgpu::TextureFormat) -> Self {
+        let color = Texture2D::new(
+            ctx,
+            width,
+            height,
+            format,
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            Some("render texture"),
+        );
+        let depth = DepthTexture::new(ctx, width, height, Some("render texture depth"));
+        Self {
+            color,
+            depth,
+            clear: ClearState::color_and_depth([0.0, 0.0, 0.0, 1.0], 1.0),
+        }
+    }
+
+    pub fn with_clear(mut self, clear: ClearState) -> Self {
+        self.clear = clear;
+        self
+    }
+
+    pub fn color(&self) -> &Texture2D {
+        &self.color
+    }
+
+    pub fn depth(&self) -> &DepthTexture {
+        &self.depth
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        self.color.size()
+    }
+}
+
+/// Shadow map for a directional `LightComponent` entity.
+///
+/// `render_shadow_maps` draws every `MeshRenderer` with `cast_shadow` into
+/// the map along the light's -Z axis, covering a square light-space box of
+/// `half_extent` centered in front of the main camera. Shadow-receiving
+/// materials sample it for entities with `receive_shadow`, filtered with the
+/// PCF settings of the `ShadowConfig`. Only the first directional light with a
+/// `ShadowCaster` casts shadows; remove the component to turn its shadows
+/// off.
+pub struct ShadowCaster {
+    map: ShadowMap,
+    /// Half the width of the light-space box covered by the shadow map.
+    pub half_extent: f32,
+    rendered: bool,
+}
+
+impl ShadowCaster {
+    /// Default `half_extent`, in world units.
+    pub const DEFAULT_HALF_EXTENT: f32 = 20.0;
+
+    pub fn new(ctx: &WgpuContext, config: ShadowConfig) -> Self {
+        Self {
+            map: ShadowMap::new(ctx, config),
+            half_extent: Self::DEFAULT_HALF_EXTENT,
+            rendered: false,
+        }
+    }
+
+    pub fn with_half_extent(mut self, half_extent: f32) -> Self {
+        self.half_extent = half_extent;
+        self
+    }
+
+    pub fn config(&self) -> &ShadowConfig {
+        &self.map.config
+    }
+
+    pub fn shadow_map(&self) -> &ShadowMap {
+        &self.map
+    }
+
+    /// World to light clip space matrix of the last shadow pass.
+    pub fn light_matrix(&self) -> Mat4 {
+        self.map.light_matrix
+    }
+
+    /// Whether the shadow map has been rendered, so receivers may sample it.
+    pub fn is_rendered(&self) -> bool {
+        self.rendered
+    }
+
+    /// Fit the light-space box around `focus` for a light shining along
+    /// `direction` and mark the map rendered. Returns the light position
+    /// used for the depth pass.
+    pub(crate) fn update(&mut self, direction: Vec3, focus: Vec3) -> Vec3 {
+        let direction = direction.normalize_or(Vec3::NEG_Y);
+        let up = if direction.dot(Vec3::Y).abs() > 0.99 {
+            Vec3::Z
+        } else {
+            Vec3::Y
+        };
+        let extent = self.half_extent;
+        let eye = focus - direction * extent * 2.0;
+        let view = Mat4::look_at_rh(eye, focus, up);
+        let projection = Mat4::orthographic_rh(-extent, extent, -extent, extent, 0.0, extent * 4.0);
+        self.map.light_matrix = projection * view;
+        self.rendered = true;
+        eye
+    }
+}
+
+/// A camera's world-space view, derived from `CameraComponent` and the
+/// entity's `GlobalTransform`.
+#[derive(Debug, Clone, Copy)]
+pub struct CameraView {
+    pub position: Vec3,
+    pub view: Mat4,
+    pub projection: Mat4,
+    #[cfg(feature = "window")]
+    viewport: Viewport,
+}
+
+impl Viewer for CameraView {
+    fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    fn view_matrix(&self) -> Mat4 {
+        self.view
+    }
+
+    fn projection_matrix(&self) -> Mat4 {
+        self.projection
+    }
+
+    #[cfg(feature = "window")]
+    fn viewport(&self) -> Viewport {
+        self.viewport
+    }
 }
 
 /// Marker for entities subject to frustum culling.
@@ -43,3 +434,79 @@ pub struct FrustumCullable;
 
 /// Marker for entities that passed the culling test (updated each frame).
 pub struct Visible;
+
+/// Marker for selected entities, drawn into the selection outline mask by
+/// `render_selection_mask`.
+pub struct Selected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+
+    fn camera() -> Camera {
+        Camera::new_perspective(
+            Vec3::new(0.0, 0.0, 5.0),
+            Vec3::ZERO,
+            Vec3::Y,
+            45.0,
+            1.0,
+            0.1,
+            100.0,
+        )
+    }
+
+    #[test]
+    fn test_identity_transform_keeps_camera_pose() {
+        let cam = CameraComponent::new(camera());
+        let view = cam.view(&GlobalTransform::default());
+        assert!((view.position - Vec3::new(0.0, 0.0, 5.0)).length() < 1e-5);
+        assert!(view.view.abs_diff_eq(cam.camera.view_matrix(), 1e-5));
+    }
+
+    #[test]
+    fn test_camera_follows_global_transform() {
+        let cam = CameraComponent::new(camera());
+        // Parent moved up and turned 90 degrees about Y, with a scale that must be ignored.
+        let global = GlobalTransform(Mat4::from_scale_rotation_translation(
+            Vec3::splat(3.0),
+            Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            Vec3::new(0.0, 2.0, 0.0),
+        ));
+        let view = cam.view(&global);
+        assert!((view.position - Vec3::new(5.0, 2.0, 0.0)).length() < 1e-4);
+        // The parent's origin is straight ahead of the camera.
+        let ahead = view.view.transform_point3(Vec3::new(0.0, 2.0, 0.0));
+        assert!(
+            (ahead - Vec3::new(0.0, 0.0, -5.0)).length() < 1e-4,
+            "ahead = {ahead}"
+        );
+    }
+
+    #[test]
+    fn test_viewport_rect_to_pixels() {
+        let rect = ViewportRect::new(0.5, 0.0, 0.5, 1.0);
+        assert_eq!(rect.to_pixels(800, 600), [400.0, 0.0, 400.0, 600.0]);
+        let overflowing = ViewportRect::new(0.75, 0.75, 0.5, 0.5);
+        assert_eq!(overflowing.to_pixels(100, 100), [75.0, 75.0, 25.0, 25.0]);
+    }
+
+    #[test]
+    fn test_spot_light_gpu_data_follows_transform() {
+        let light = LightComponent::spot(Vec3::ONE, 2.0, 15.0, 20.0, 30.0);
+        let global = GlobalTransform(Mat4::from_rotation_translation(
+            Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2),
+            Vec3::new(1.0, 4.0, 0.0),
+        ));
+
+        let gpu = light.gpu_light(&global, false);
+        assert_eq!(gpu.position_range, [1.0, 4.0, 0.0, 15.0]);
+        let direction = Vec3::from_slice(&gpu.direction_kind[..3]);
+        assert!(direction.abs_diff_eq(Vec3::NEG_Y, 1e-6));
+        assert_eq!(gpu.direction_kind[3], GpuLight::kind(LightType::Spot));
+        assert!((gpu.cone_shadow[0] - 20f32.to_radians().cos()).abs() < 1e-6);
+        assert!((gpu.cone_shadow[1] - 30f32.to_radians().cos()).abs() < 1e-6);
+        assert_eq!(gpu.cone_shadow[2], 0.0);
+        assert_eq!(light.gpu_light(&global, true).cone_shadow[2], 1.0);
+    }
+}
@@ -3,7 +3,9 @@
 use glam::{Mat4, Quat, Vec3};
 
 /// Local-space transform. Stores position, rotation, and scale separately.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "scene", serde(default))]
 pub struct Transform {
     pub position: Vec3,
     pub rotation: Quat,
@@ -70,6 +72,12 @@ pub struct Parent(pub hecs::Entity);
 /// List of child entities.
 pub struct Children(pub Vec<hecs::Entity>);
 
+/// Marker forcing the transform system to recompute an entity's subtree.
+///
+/// Changed `Transform`s are detected automatically; this is only needed after
+/// editing `Parent`/`Children` by hand. Removed once handled.
+pub struct TransformDirty;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,367 @@
+//! Hierarchy management for `Parent`/`Children` links.
+//!
+//! These helpers keep both sides of a link in sync. [`validate_hierarchy`]
+//! reports worlds where they went out of sync, e.g. after editing the
+//! components by hand.
+
+use std::collections::HashSet;
+
+use anyhow::bail;
+use glam::Mat4;
+
+use crate::ecs::components::transform::{
+    Children, GlobalTransform, Parent, Transform, TransformDirty,
+};
+
+/// Make `child` a child of `parent`, detaching it from its previous parent.
+///
+/// The local [`Transform`] is kept, so the child moves with its new parent.
+/// Fails if either entity is missing or `parent` is `child` or one of its
+/// descendants.
+pub fn set_parent(
+    world: &mut hecs::World,
+    child: hecs::Entity,
+    parent: hecs::Entity,
+) -> anyhow::Result<()> {
+    if !world.contains(child) || !world.contains(parent) {
+        bail!("Cannot parent {child:?} to {parent:?}: entity does not exist");
+    }
+    if child == parent || is_ancestor(world, child, parent) {
+        bail!("Cannot parent {child:?} to {parent:?}: would create a cycle");
+    }
+    detach(world, child);
+    world.insert(child, (Parent(parent), TransformDirty))?;
+    if let Ok(mut children) = world.get::<&mut Children>(parent) {
+        children.0.push(child);
+        return Ok(());
+    }
+    world.insert_one(parent, Children(vec![child]))?;
+    Ok(())
+}
+
+/// Like [`set_parent`], but adjusts the child's local [`Transform`] so its
+/// world transform does not change.
+pub fn set_parent_keep_world(
+    world: &mut hecs::World,
+    child: hecs::Entity,
+    parent: hecs::Entity,
+) -> anyhow::Result<()> {
+    let child_world = world_matrix(world, child);
+    set_parent(world, child, parent)?;
+    let local = world_matrix(world, parent).inverse() * child_world;
+    set_local(world, child, local, child_world);
+    Ok(())
+}
+
+/// Detach `child` from its parent, making it a root. The local
+/// [`Transform`] is kept. Does nothing for roots.
+pub fn remove_parent(world: &mut hecs::World, child: hecs::Entity) -> anyhow::Result<()> {
+    if !world.contains(child) {
+        bail!("Cannot unparent {child:?}: entity does not exist");
+    }
+    detach(world, child);
+    Ok(())
+}
+
+/// Like [`remove_parent`], but sets the local [`Transform`] to the child's
+/// world transform so it stays in place.
+pub fn remove_parent_keep_world(
+    world: &mut hecs::World,
+    child: hecs::Entity,
+) -> anyhow::Result<()> {
+    let child_world = world_matrix(world, child);
+    remove_parent(world, child)?;
+    set_local(world, child, child_world, child_world);
+    Ok(())
+}
+
+/// Despawn `entity` and all of its descendants, detaching it from its
+/// parent. Returns the number of despawned entities.
+pub fn despawn_recursive(world: &mut hecs::World, entity: hecs::Entity) -> anyhow::Result<usize> {
+    if !world.contains(entity) {
+        bail!("Cannot despawn {entity:?}: entity does not exist");
+    }
+    detach(world, entity);
+
+    let mut pending = vec![entity];
+    let mut despawned = 0;
+    while let Some(next) = pending.pop() {
+        if let Ok(children) = world.get::<&Children>(next) {
+            pending.extend(children.0.iter().copied());
+        }
+        // Children listed twice or already gone are skipped
+        if world.despawn(next).is_ok() {
+            despawned += 1;
+        }
+    }
+    Ok(despawned)
+}
+
+/// World matrix of `entity` computed from the [`Transform`]s of it and its
+/// ancestors, without relying on an up-to-date [`GlobalTransform`].
+///
+/// Entities without a `Transform` contribute identity.
+pub fn world_matrix(world: &hecs::World, entity: hecs::Entity) -> Mat4 {
+    let mut matrix = Mat4::IDENTITY;
+    let mut visited = HashSet::new();
+    let mut current = Some(entity);
+    while let Some(e) = current {
+        if !visited.insert(e) {
+            tracing::warn!("Parent cycle through {e:?}");
+            break;
+        }
+        if let Ok(transform) = world.get::<&Transform>(e) {
+            matrix = transform.to_matrix() * matrix;
+        }
+        current = world.get::<&Parent>(e).ok().map(|parent| parent.0);
+    }
+    matrix
+}
+
+/// Whether `ancestor` is a (transitive) parent of `entity`.
+pub fn is_ancestor(world: &hecs::World, ancestor: hecs::Entity, entity: hecs::Entity) -> bool {
+    let mut visited = HashSet::new();
+    let mut current = world.get::<&Parent>(entity).ok().map(|parent| parent.0);
+    while let Some(e) = current {
+        if e == ancestor {
+            return true;
+        }
+        if !visited.insert(e) {
+            return false;
+        }
+        current = world.get::<&Parent>(e).ok().map(|parent| parent.0);
+    }
+    false
+}
+
+/// Remove `child`'s `Parent` and its entry in the parent's `Children`,
+/// dropping the `Children` component once empty. The child is marked
+/// [`TransformDirty`] so its new place in the hierarchy gets propagated.
+fn detach(world: &mut hecs::World, child: hecs::Entity) {
+    let Ok(Parent(parent)) = world.remove_one::<Parent>(child) else {
+        return;
+    };
+    let _ = world.insert_one(child, TransformDirty);
+    let now_empty = match world.get::<&mut Children>(parent) {
+        Ok(mut children) => {
+            children.0.retain(|&c| c != child);
+            children.0.is_empty()
+        }
+        Err(_) => false,
+    };
+    if now_empty {
+        let _ = world.remove_one::<Children>(parent);
+    }
+}
+
+/// Set the local transform of `entity` and its [`GlobalTransform`], if any.
+fn set_local(world: &mut hecs::World, entity: hecs::Entity, local: Mat4, global: Mat4) {
+    let _ = world.insert_one(entity, Transform::from_matrix(local));
+    if let Ok(mut global_transform) = world.get::<&mut GlobalTransform>(entity) {
+        global_transform.0 = global;
+    }
+}
+
+/// A broken `Parent`/`Children` link found by [`validate_hierarchy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HierarchyError {
+    #[error("{child:?} has parent {parent:?}, which does not exist")]
+    MissingParent {
+        child: hecs::Entity,
+        parent: hecs::Entity,
+    },
+    #[error("{parent:?} lists child {child:?}, which does not exist")]
+    MissingChild {
+        parent: hecs::Entity,
+        child: hecs::Entity,
+    },
+    #[error("{child:?} has parent {parent:?}, which does not list it as a child")]
+    NotInChildren {
+        child: hecs::Entity,
+        parent: hecs::Entity,
+    },
+    #[error("{parent:?} lists child {child:?}, whose parent is {actual:?}")]
+    ParentMismatch {
+        parent: hecs::Entity,
+        child: hecs::Entity,
+        actual: Option<hecs::Entity>,
+    },
+    #[error("{entity:?} is its own ancestor")]
+    Cycle { entity: hecs::Entity },
+}
+
+/// Check every `Parent`/`Children` link in `world`.
+///
+/// Intended for debug builds and tests; returns an empty list for a
+/// consistent hierarchy.
+pub fn validate_hierarchy(world: &hecs::World) -> Vec<HierarchyError> {
+    let mut errors = Vec::new();
+
+    for (child, parent) in world.query::<&Parent>().iter() {
+        let parent = parent.0;
+        match world.get::<&Children>(parent) {
+            Ok(children) if children.0.contains(&child) => {}
+            _ if !world.contains(parent) => {
+                errors.push(HierarchyError::MissingParent { child, parent })
+            }
+            _ => errors.push(HierarchyError::NotInChildren { child, parent }),
+        }
+        if is_ancestor(world, child, child) {
+            errors.push(HierarchyError::Cycle { entity: child });
+        }
+    }
+
+    for (parent, children) in world.query::<&Children>().iter() {
+        for &child in &children.0 {
+            if !world.contains(child) {
+                errors.push(HierarchyError::MissingChild { parent, child });
+                continue;
+            }
+            let actual = world.get::<&Parent>(child).ok().map(|p| p.0);
+            if actual != Some(parent) {
+                errors.push(HierarchyError::ParentMismatch {
+                    parent,
+                    child,
+                    actual,
+                });
+            }
+        }
+    }
+    errors
+}
+
+/// Log every [`validate_hierarchy`] error as a warning in debug builds.
+///
+/// Compiles to nothing in release builds.
+pub fn debug_validate_hierarchy(world: &hecs::World) {
+    if cfg!(debug_assertions) {
+        for error in validate_hierarchy(world) {
+            tracing::warn!("Hierarchy: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::systems::transform_system;
+    use glam::{Quat, Vec3};
+
+    fn spawn_at(world: &mut hecs::World, position: Vec3) -> hecs::Entity {
+        world.spawn((
+            Transform::from_position(position),
+            GlobalTransform::default(),
+        ))
+    }
+
+    fn position(world: &hecs::World, entity: hecs::Entity) -> Vec3 {
+        world
+            .get::<&GlobalTransform>(entity)
+            .unwrap()
+            .0
+            .transform_point3(Vec3::ZERO)
+    }
+
+    #[test]
+    fn test_set_parent_keeps_links_in_sync() {
+        let mut world = hecs::World::new();
+        let a = spawn_at(&mut world, Vec3::X);
+        let b = spawn_at(&mut world, Vec3::Y);
+        let child = spawn_at(&mut world, Vec3::Z);
+
+        set_parent(&mut world, child, a).unwrap();
+        assert_eq!(world.get::<&Children>(a).unwrap().0, [child]);
+        set_parent(&mut world, child, b).unwrap();
+        assert!(world.get::<&Children>(a).is_err());
+        assert_eq!(world.get::<&Children>(b).unwrap().0, [child]);
+        assert_eq!(world.get::<&Parent>(child).unwrap().0, b);
+
+        transform_system(&mut world);
+        assert!((position(&world, child) - Vec3::new(0.0, 1.0, 1.0)).length() < 1e-5);
+
+        // Reparenting b under its own child would form a cycle
+        assert!(set_parent(&mut world, b, child).is_err());
+        assert!(set_parent(&mut world, b, b).is_err());
+
+        remove_parent(&mut world, child).unwrap();
+        assert!(world.get::<&Parent>(child).is_err());
+        assert!(world.get::<&Children>(b).is_err());
+        assert!(validate_hierarchy(&world).is_empty());
+    }
+
+    #[test]
+    fn test_reparenting_keeps_world_transform() {
+        let mut world = hecs::World::new();
+        let parent = world.spawn((
+            Transform {
+                position: Vec3::new(5.0, 0.0, 0.0),
+                rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+                scale: Vec3::splat(2.0),
+            },
+            GlobalTransform::default(),
+        ));
+        let child = spawn_at(&mut world, Vec3::new(1.0, 2.0, 3.0));
+
+        set_parent_keep_world(&mut world, child, parent).unwrap();
+        transform_system(&mut world);
+        assert!((position(&world, child) - Vec3::new(1.0, 2.0, 3.0)).length() < 1e-4);
+
+        remove_parent_keep_world(&mut world, child).unwrap();
+        let local = world.get::<&Transform>(child).unwrap().position;
+        assert!((local - Vec3::new(1.0, 2.0, 3.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn test_despawn_recursive_removes_subtree() {
+        let mut world = hecs::World::new();
+        let root = spawn_at(&mut world, Vec3::ZERO);
+        let arm = spawn_at(&mut world, Vec3::X);
+        let hand = spawn_at(&mut world, Vec3::X);
+        let other = spawn_at(&mut world, Vec3::Y);
+        set_parent(&mut world, arm, root).unwrap();
+        set_parent(&mut world, hand, arm).unwrap();
+        set_parent(&mut world, other, root).unwrap();
+
+        assert_eq!(despawn_recursive(&mut world, arm).unwrap(), 2);
+        assert!(!world.contains(hand));
+        assert_eq!(world.get::<&Children>(root).unwrap().0, [other]);
+        assert!(validate_hierarchy(&world).is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_broken_links() {
+        let mut world = hecs::World::new();
+        let parent = spawn_at(&mut world, Vec3::ZERO);
+        let listed = spawn_at(&mut world, Vec3::ZERO);
+        let unlisted = spawn_at(&mut world, Vec3::ZERO);
+        let gone = spawn_at(&mut world, Vec3::ZERO);
+        world
+            .insert_one(parent, Children(vec![listed, gone]))
+            .unwrap();
+        world.insert_one(unlisted, Parent(parent)).unwrap();
+        world.despawn(gone).unwrap();
+
+        let a = spawn_at(&mut world, Vec3::ZERO);
+        let b = spawn_at(&mut world, Vec3::ZERO);
+        world.insert(a, (Parent(b), Children(vec![b]))).unwrap();
+        world.insert(b, (Parent(a), Children(vec![a]))).unwrap();
+
+        let errors = validate_hierarchy(&world);
+        assert!(errors.contains(&HierarchyError::NotInChildren {
+            child: unlisted,
+            parent
+        }));
+        assert!(errors.contains(&HierarchyError::MissingChild {
+            parent,
+            child: gone
+        }));
+        assert!(errors.contains(&HierarchyError::ParentMismatch {
+            parent,
+            child: listed,
+            actual: None
+        }));
+        assert!(errors.contains(&HierarchyError::Cycle { entity: a }));
+        assert!(errors.contains(&HierarchyError::Cycle { entity: b }));
+        assert_eq!(errors.len(), 5);
+    }
+}
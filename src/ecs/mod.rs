@@ -2,10 +2,28 @@
 
 pub mod bridge;
 pub mod components;
+pub mod hierarchy;
+pub mod picking;
+#[cfg(feature = "scene")]
+pub mod prefab;
+#[cfg(feature = "scene")]
+pub mod scene;
 pub mod systems;
 
 pub mod prelude {
     pub use super::bridge::*;
     pub use super::components::*;
-    pub use super::systems::{culling_system, render_system, transform_system};
+    pub use super::hierarchy::{
+        debug_validate_hierarchy, despawn_recursive, remove_parent, remove_parent_keep_world,
+        set_parent, set_parent_keep_world, validate_hierarchy, HierarchyError,
+    };
+    pub use super::picking::{pick, PickHit};
+    #[cfg(feature = "scene")]
+    pub use super::prefab::{PrefabInstance, PrefabOverride, PrefabRegistry};
+    #[cfg(feature = "scene")]
+    pub use super::scene::{attach_mesh_renderers, PrimitiveAssets, Scene, SceneAssets};
+    pub use super::systems::{
+        culling_system, render_id_buffer, render_selection_mask, render_system, transform_system,
+        IdBuffer, IdQuery, PickId, RenderBuffers,
+    };
 }
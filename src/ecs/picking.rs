@@ -0,0 +1,284 @@
+//! Ray picking of rendered entities.
+//!
+//! [`pick`] finds the closest `MeshRenderer` entity along a [`Ray`], e.g.
+//! one built with [`Ray::from_screen`] from the camera and cursor. Entities
+//! are culled by their world AABB first, then their mesh triangles are tested
+//! for the exact hit. Meshes without CPU triangles, such as a `Mesh` not
+//! created with `Mesh::with_cpu_data`, are hit on their AABB.
+
+use glam::{Mat3, Mat4, Vec3};
+
+use crate::ecs::components::rendering::MeshRenderer;
+use crate::ecs::components::transform::GlobalTransform;
+use crate::renderer::geometry::Aabb;
+use crate::renderer::ray::Ray;
+
+/// Closest hit of a [`pick`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickHit {
+    pub entity: hecs::Entity,
+    /// World-space hit point.
+    pub point: Vec3,
+    /// World-space surface normal, facing the ray.
+    pub normal: Vec3,
+    /// Distance from the ray origin, in units of the ray direction.
+    pub distance: f32,
+}
+
+/// The closest visible `MeshRenderer` entity hit by `ray`.
+pub fn pick(world: &hecs::World, ray: &Ray) -> Option<PickHit> {
+    let mut query = world.query::<(&MeshRenderer, &GlobalTransform)>();
+    let targets = query
+        .iter()
+        .filter(|(_, (renderer, _))| renderer.visible)
+        .map(|(entity, (renderer, global))| PickTarget {
+            entity,
+            aabb: renderer.mesh.0.aabb(),
+            triangles: renderer.mesh.0.triangles(),
+            matrix: global.0,
+        });
+    pick_closest(ray, targets)
+}
+
+/// A mesh placed in the world, as [`pick`] sees it.
+struct PickTarget<'a> {
+    entity: hecs::Entity,
+    /// Local bounding box.
+    aabb: Aabb,
+    /// Local triangles, if the mesh keeps them on the CPU.
+    triangles: Option<(&'a [Vec3], &'a [u32])>,
+    matrix: Mat4,
+}
+
+/// The closest of `targets` hit by `ray`.
+fn pick_closest<'a>(
+    ray: &Ray,
+    targets: impl IntoIterator<Item = PickTarget<'a>>,
+) -> Option<PickHit> {
+    // Broad phase: world AABBs, nearest first so the triangle tests can stop
+    // once no remaining box can be closer
+    let mut candidates: Vec<(f32, PickTarget)> = targets
+        .into_iter()
+        .filter_map(|target| {
+            let t = ray.intersect_aabb(&world_aabb(target.aabb, target.matrix))?;
+            Some((t, target))
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut best: Option<PickHit> = None;
+    for (t_aabb, target) in candidates {
+        if best.is_some_and(|hit| hit.distance < t_aabb) {
+            break;
+        }
+        let Some((t, local_normal)) = intersect_target(ray, &target) else {
+            continue;
+        };
+        if best.is_some_and(|hit| hit.distance <= t) {
+            continue;
+        }
+        let normal_matrix = Mat3::from_mat4(target.matrix).inverse().transpose();
+        let mut normal = (normal_matrix * local_normal).normalize_or_zero();
+        if normal.dot(ray.direction) > 0.0 {
+            normal = -normal;
+        }
+        best = Some(PickHit {
+            entity: target.entity,
+            point: ray.at(t),
+            normal,
+            distance: t,
+        });
+    }
+    best
+}
+
+/// Closest hit of `ray` with the target in local space: ray parameter and
+/// local normal.
+fn intersect_target(ray: &Ray, target: &PickTarget) -> Option<(f32, Vec3)> {
+    // Affine transforms keep ray parameters, so local `t` is the world `t`
+    let local = ray.transform(target.matrix.inverse());
+
+    let Some((positions, indices)) = target.triangles else {
+        let t = local.intersect_aabb(&target.aabb)?;
+        return Some((t, aabb_face_normal(&target.aabb, local.at(t))));
+    };
+
+    let mut best: Option<(f32, Vec3)> = None;
+    for triangle in indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i] as usize]);
+        if let Some(t) = local.intersect_triangle(a, b, c) {
+            if best.is_none_or(|(best_t, _)| t < best_t) {
+                best = Some((t, (b - a).cross(c - a)));
+            }
+        }
+    }
+    best
+}
+
+/// Normal of the face of `aabb` closest to `point`.
+fn aabb_face_normal(aabb: &Aabb, point: Vec3) -> Vec3 {
+    let to_min = (point - aabb.min).abs();
+    let to_max = (aabb.max - point).abs();
+    let mut normal = Vec3::ZERO;
+    let mut closest = f32::MAX;
+    for axis in 0..3 {
+        for (distance, sign) in [(to_min[axis], -1.0), (to_max[axis], 1.0)] {
+            if distance < closest {
+                closest = distance;
+                normal = Vec3::ZERO;
+                normal[axis] = sign;
+            }
+        }
+    }
+    normal
+}
+
+fn world_aabb(local: Aabb, matrix: Mat4) -> Aabb {
+    Aabb::from_points(local.corners().map(|c| matrix.transform_point3(c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::WgpuContext;
+    use crate::core::pipeline::Vertex;
+    use crate::ecs::components::rendering::{MaterialHandle, MeshHandle};
+    use crate::renderer::geometry::Mesh;
+    use crate::renderer::material::ColorMaterial;
+    use std::sync::Arc;
+
+    fn try_create_ctx() -> Option<WgpuContext> {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            return None;
+        }
+        WgpuContext::new_blocking(None).ok()
+    }
+
+    /// Triangles of the box from `-half` to `half`.
+    fn box_triangles(half: f32) -> (Vec<Vec3>, Vec<u32>) {
+        // Corner index bits select +x, +y, +z
+        let positions = (0..8)
+            .map(|i| {
+                let sign = |bit| if i & bit == 0 { -half } else { half };
+                Vec3::new(sign(1), sign(2), sign(4))
+            })
+            .collect();
+        let faces = [
+            [0, 2, 6, 4],
+            [1, 3, 7, 5],
+            [0, 1, 5, 4],
+            [2, 3, 7, 6],
+            [0, 1, 3, 2],
+            [4, 5, 7, 6],
+        ];
+        let indices = faces
+            .iter()
+            .flat_map(|[a, b, c, d]| [*a, *b, *c, *a, *c, *d])
+            .collect();
+        (positions, indices)
+    }
+
+    fn target<'a>(
+        entity: hecs::Entity,
+        triangles: Option<&'a (Vec<Vec3>, Vec<u32>)>,
+        position: Vec3,
+    ) -> PickTarget<'a> {
+        let aabb = match triangles {
+            Some((positions, _)) => Aabb::from_points(positions.iter().copied()),
+            None => Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0)),
+        };
+        PickTarget {
+            entity,
+            aabb,
+            triangles: triangles.map(|(positions, indices)| (&positions[..], &indices[..])),
+            matrix: Mat4::from_translation(position),
+        }
+    }
+
+    #[test]
+    fn test_pick_closest_triangle_hit() {
+        let mut world = hecs::World::new();
+        let [near, far, corner, boxed] = [(); 4].map(|_| world.spawn(()));
+        let cube = box_triangles(1.0);
+        // A triangle covering the half of its AABB away from the ray
+        let diagonal = (
+            vec![
+                Vec3::new(-1.0, -1.0, 0.0),
+                Vec3::new(-1.0, 1.0, 0.0),
+                Vec3::new(1.0, 1.0, 0.0),
+            ],
+            vec![0, 1, 2],
+        );
+
+        let ray = Ray::new(Vec3::new(-0.95, 0.95, 10.0), -Vec3::Z);
+        let targets = || {
+            [
+                target(near, Some(&cube), Vec3::new(0.0, 0.0, 2.0)),
+                target(far, Some(&cube), Vec3::new(0.0, 0.0, -4.0)),
+                target(corner, Some(&diagonal), Vec3::new(-1.9, 1.9, 5.0)),
+            ]
+        };
+        let hit = pick_closest(&ray, targets()).unwrap();
+        assert_eq!(hit.entity, near);
+        assert!((hit.distance - 7.0).abs() < 1e-4);
+        assert!(hit.point.abs_diff_eq(Vec3::new(-0.95, 0.95, 3.0), 1e-4));
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 1e-4));
+
+        let [_, far_target, corner_target] = targets();
+        let hit = pick_closest(&ray, [far_target, corner_target]).unwrap();
+        assert_eq!(hit.entity, far);
+        let miss = Ray::new(Vec3::new(5.0, 0.0, 10.0), -Vec3::Z);
+        assert!(pick_closest(&miss, targets()).is_none());
+
+        // Without triangles the AABB is hit
+        let hit = pick_closest(&ray, [target(boxed, None, Vec3::new(-1.9, 1.9, 5.0))]).unwrap();
+        assert_eq!(hit.entity, boxed);
+        assert!((hit.distance - 4.0).abs() < 1e-4);
+        assert!(hit.normal.abs_diff_eq(Vec3::Z, 1e-4));
+    }
+
+    #[test]
+    fn test_pick_uses_mesh_cpu_data() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let material =
+            Arc::new(ColorMaterial::new(&ctx, wgpu::TextureFormat::Rgba8UnormSrgb).unwrap());
+        let renderer = |mesh: Mesh| MeshRenderer {
+            mesh: MeshHandle(Arc::new(mesh)),
+            material: MaterialHandle(material.clone()),
+            visible: true,
+            cast_shadow: true,
+            receive_shadow: true,
+        };
+        let vertex = |x, y| Vertex {
+            position: [x, y, 0.0],
+            normal: [0.0, 0.0, 1.0],
+            color: [1.0; 3],
+        };
+        let diagonal = [vertex(-1.0, -1.0), vertex(-1.0, 1.0), vertex(1.0, 1.0)];
+
+        let mut world = hecs::World::new();
+        let at = |position| GlobalTransform(Mat4::from_translation(position));
+        let sphere = world.spawn((
+            renderer(Mesh::sphere(&ctx, 1.0, 16, 8, [1.0; 3])),
+            at(Vec3::new(-1.9, 1.9, 5.0)),
+        ));
+        world.spawn((
+            renderer(Mesh::with_cpu_data(&ctx, &diagonal, None, None)),
+            at(Vec3::new(-1.9, 1.9, 3.0)),
+        ));
+        let plain = world.spawn((
+            renderer(Mesh::new(&ctx, &diagonal, None, None)),
+            at(Vec3::new(-1.9, 1.9, 1.0)),
+        ));
+
+        // The ray crosses the AABB corners, but none of the surfaces
+        let ray = Ray::new(Vec3::new(-0.95, 0.95, 10.0), -Vec3::Z);
+        assert_eq!(pick(&world, &ray).unwrap().entity, sphere);
+        world.get::<&mut MeshRenderer>(sphere).unwrap().visible = false;
+        // The triangle kept on the CPU is missed, the plain one hit on its AABB
+        assert_eq!(pick(&world, &ray).unwrap().entity, plain);
+    }
+}
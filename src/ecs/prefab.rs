@@ -0,0 +1,384 @@
+//! Prefabs: reusable entity templates.
+//!
+//! A prefab is a [`Scene`] describing one entity subtree: its first entity
+//! is the root and every other entity descends from it. Prefabs are
+//! registered by name in a [`PrefabRegistry`] and placed by
+//! [`PrefabInstance`]s, either in a scene file or at runtime.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{bail, Context};
+use glam::{Quat, Vec3};
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::components::physics::Collider;
+use crate::ecs::components::rendering::LightComponent;
+use crate::ecs::components::transform::Transform;
+use crate::ecs::scene::{
+    CameraDesc, MaterialRef, MeshRendererDesc, RigidBodyDesc, Scene, SceneEntity,
+};
+
+/// Maximum depth of prefabs instancing other prefabs.
+const MAX_PREFAB_DEPTH: usize = 16;
+
+/// One placement of a named prefab.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefabInstance {
+    /// Registered prefab name.
+    pub prefab: String,
+    /// Index of the parent in the enclosing scene's entities.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<usize>,
+    /// Placement of the instance, applied on top of the prefab root's own
+    /// transform.
+    pub transform: Transform,
+    /// Per-entity changes to this instance.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<PrefabOverride>,
+}
+
+impl PrefabInstance {
+    /// An instance of `prefab` placed at `transform`.
+    pub fn new(prefab: impl Into<String>, transform: Transform) -> Self {
+        Self {
+            prefab: prefab.into(),
+            transform,
+            ..Default::default()
+        }
+    }
+
+    /// Add an override.
+    pub fn with_override(mut self, over: PrefabOverride) -> Self {
+        self.overrides.push(over);
+        self
+    }
+}
+
+/// Changes to one entity of a prefab instance. Unset fields keep the
+/// prefab's values; set components replace the prefab's component.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefabOverride {
+    /// Index of the entity in the prefab; 0 is the root.
+    pub entity: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<Vec3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<Quat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale: Option<Vec3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rigid_body: Option<RigidBodyDesc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider: Option<Collider>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub light: Option<LightComponent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraDesc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mesh: Option<MeshRendererDesc>,
+    /// Replaces only the material of the entity's mesh.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub material: Option<MaterialRef>,
+}
+
+impl PrefabOverride {
+    /// An override of the prefab entity at `entity`.
+    pub fn entity(entity: usize) -> Self {
+        Self {
+            entity,
+            ..Default::default()
+        }
+    }
+
+    fn apply(&self, target: &mut SceneEntity) -> anyhow::Result<()> {
+        if let Some(position) = self.position {
+            target.transform.position = position;
+        }
+        if let Some(rotation) = self.rotation {
+            target.transform.rotation = rotation;
+        }
+        if let Some(scale) = self.scale {
+            target.transform.scale = scale;
+        }
+        if let Some(body) = &self.rigid_body {
+            target.rigid_body = Some(body.clone());
+        }
+        if let Some(collider) = &self.collider {
+            target.collider = Some(collider.clone());
+        }
+        if let Some(light) = &self.light {
+            target.light = Some(light.clone());
+        }
+        if let Some(camera) = &self.camera {
+            target.camera = Some(camera.clone());
+        }
+        if let Some(mesh) = &self.mesh {
+            target.mesh = Some(mesh.clone());
+        }
+        if let Some(material) = &self.material {
+            let Some(mesh) = &mut target.mesh else {
+                bail!(
+                    "Material override on prefab entity {} without a mesh",
+                    self.entity
+                );
+            };
+            mesh.material = material.clone();
+        }
+        Ok(())
+    }
+}
+
+/// Named prefabs, shared by scenes and runtime spawning.
+#[derive(Debug, Clone, Default)]
+pub struct PrefabRegistry {
+    prefabs: HashMap<String, Scene>,
+}
+
+impl PrefabRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `prefab` under `name`, replacing any previous one.
+    ///
+    /// Fails unless the prefab is a single subtree rooted at its first
+    /// entity.
+    pub fn register(&mut self, name: impl Into<String>, prefab: Scene) -> anyhow::Result<()> {
+        let name = name.into();
+        let Some(root) = prefab.entities.first() else {
+            bail!("Prefab {name:?} has no entities");
+        };
+        if root.parent.is_some() {
+            bail!("Prefab {name:?} root has a parent");
+        }
+        if let Some(i) = prefab
+            .entities
+            .iter()
+            .skip(1)
+            .position(|e| e.parent.is_none())
+        {
+            bail!("Prefab {name:?} entity {} is not under the root", i + 1);
+        }
+        if prefab
+            .instances
+            .iter()
+            .any(|instance| instance.parent.is_none())
+        {
+            bail!("Prefab {name:?} has an instance outside the root");
+        }
+        self.prefabs.insert(name, prefab);
+        Ok(())
+    }
+
+    /// Register the RON prefab file at `path` under `name`.
+    pub fn load(&mut self, name: impl Into<String>, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        self.register(name, Scene::load(path)?)
+    }
+
+    /// Register `root` and its descendants in `world` as a prefab.
+    pub fn capture(
+        &mut self,
+        name: impl Into<String>,
+        world: &hecs::World,
+        root: hecs::Entity,
+    ) -> anyhow::Result<()> {
+        self.register(name, Scene::from_entity_tree(world, root)?)
+    }
+
+    /// The prefab registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&Scene> {
+        self.prefabs.get(name)
+    }
+
+    /// Whether a prefab is registered under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.prefabs.contains_key(name)
+    }
+
+    /// Registered prefab names, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.prefabs.keys().map(String::as_str)
+    }
+
+    /// Spawn `instance` at the world root, returning its entities, root
+    /// first.
+    pub fn instantiate(
+        &self,
+        world: &mut hecs::World,
+        instance: &PrefabInstance,
+    ) -> anyhow::Result<Vec<hecs::Entity>> {
+        if instance.parent.is_some() {
+            bail!("Runtime prefab instances are spawned without a parent");
+        }
+        let scene = Scene {
+            instances: vec![instance.clone()],
+            ..Default::default()
+        };
+        scene.spawn_with(world, self)
+    }
+
+    /// Replace the instances of `scene` with the entities they describe.
+    pub(crate) fn expand(&self, scene: &Scene) -> anyhow::Result<Scene> {
+        self.expand_depth(scene, 0)
+    }
+
+    fn expand_depth(&self, scene: &Scene, depth: usize) -> anyhow::Result<Scene> {
+        if scene.instances.is_empty() {
+            return Ok(scene.clone());
+        }
+        if depth >= MAX_PREFAB_DEPTH {
+            bail!("Prefabs nested deeper than {MAX_PREFAB_DEPTH} levels, likely a cycle");
+        }
+
+        let mut entities = scene.entities.clone();
+        for instance in &scene.instances {
+            let prefab = self
+                .get(&instance.prefab)
+                .with_context(|| format!("Unknown prefab {:?}", instance.prefab))?;
+            let prefab = self
+                .expand_depth(prefab, depth + 1)
+                .with_context(|| format!("In prefab {:?}", instance.prefab))?;
+            if let Some(parent) = instance.parent.filter(|&p| p >= scene.entities.len()) {
+                bail!(
+                    "Prefab instance {:?} has parent {parent} out of range",
+                    instance.prefab
+                );
+            }
+
+            let base = entities.len();
+            entities.extend(prefab.entities.iter().cloned().map(|mut entity| {
+                entity.parent = match entity.parent {
+                    Some(parent) => Some(base + parent),
+                    None => instance.parent,
+                };
+                entity
+            }));
+            let root = &mut entities[base].transform;
+            *root = Transform::from_matrix(instance.transform.to_matrix() * root.to_matrix());
+
+            for over in &instance.overrides {
+                let Some(target) = entities[base..].get_mut(over.entity) else {
+                    bail!(
+                        "Override of entity {} in prefab {:?} with {} entities",
+                        over.entity,
+                        instance.prefab,
+                        prefab.entities.len()
+                    );
+                };
+                over.apply(target)?;
+            }
+        }
+        Ok(Scene {
+            entities,
+            instances: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::physics::RigidBody;
+    use crate::ecs::components::transform::{Children, GlobalTransform, Parent};
+    use crate::renderer::light::LightType;
+
+    const CRATE: &str = include_str!("../../tests/fixtures/crate_prefab.ron");
+
+    const LEVEL: &str = include_str!("../../tests/fixtures/prefab_level.ron");
+
+    fn registry() -> PrefabRegistry {
+        let mut prefabs = PrefabRegistry::new();
+        prefabs
+            .register("crate", Scene::from_ron(CRATE).unwrap())
+            .unwrap();
+        prefabs
+    }
+
+    #[test]
+    fn test_level_instances_prefabs_with_overrides() {
+        let prefabs = registry();
+        let level = Scene::from_ron(LEVEL).unwrap();
+        assert!(level.spawn(&mut hecs::World::new()).is_err());
+
+        let mut world = hecs::World::new();
+        let entities = level.spawn_with(&mut world, &prefabs).unwrap();
+        assert_eq!(entities.len(), 5);
+        let [anchor, first, first_light, second, second_light] = entities[..] else {
+            unreachable!()
+        };
+
+        // The instance transform applies on top of the prefab root's scale
+        let root = *world.get::<&Transform>(first).unwrap();
+        assert_eq!(root.position, Vec3::new(3.0, 0.0, 0.0));
+        assert!((root.scale - Vec3::splat(0.5)).length() < 1e-6);
+        let light_global = world.get::<&GlobalTransform>(first_light).unwrap().0;
+        let light_position = light_global.transform_point3(Vec3::ZERO);
+        assert!((light_position - Vec3::new(3.0, 1.0, 0.0)).length() < 1e-5);
+        assert_eq!(world.get::<&Parent>(first_light).unwrap().0, first);
+
+        assert_eq!(world.get::<&RigidBody>(first).unwrap().mass, 2.0);
+        assert_eq!(world.get::<&Parent>(second).unwrap().0, anchor);
+        assert_eq!(world.get::<&Children>(anchor).unwrap().0, [second]);
+        assert_eq!(world.get::<&RigidBody>(second).unwrap().mass, 0.0);
+        let light = world.get::<&LightComponent>(second_light).unwrap();
+        assert_eq!(light.color, Vec3::X);
+        assert_eq!(light.light_type, LightType::Point);
+        let mesh = world.get::<&MeshRendererDesc>(second).unwrap();
+        assert!(matches!(mesh.material, MaterialRef::Pbr { .. }));
+        assert_eq!(
+            world.get::<&MeshRendererDesc>(first).unwrap().material,
+            MaterialRef::Color
+        );
+    }
+
+    #[test]
+    fn test_prefab_capture_and_runtime_instantiate() {
+        let prefabs = registry();
+        let mut world = hecs::World::new();
+        let spawned = prefabs
+            .instantiate(
+                &mut world,
+                &PrefabInstance::new("crate", Transform::from_position(Vec3::Y)).with_override(
+                    PrefabOverride {
+                        position: Some(Vec3::ZERO),
+                        ..PrefabOverride::entity(1)
+                    },
+                ),
+            )
+            .unwrap();
+        assert_eq!(spawned.len(), 2);
+        assert_eq!(
+            world.get::<&Transform>(spawned[1]).unwrap().position,
+            Vec3::ZERO
+        );
+
+        let mut captured = PrefabRegistry::new();
+        captured.capture("copy", &world, spawned[0]).unwrap();
+        let copy = captured.get("copy").unwrap();
+        assert_eq!(copy.entities.len(), 2);
+        assert_eq!(copy.entities[1].parent, Some(0));
+
+        let unknown = PrefabInstance::new("barrel", Transform::identity());
+        assert!(prefabs.instantiate(&mut world, &unknown).is_err());
+        let bad_override = PrefabInstance::new("crate", Transform::identity())
+            .with_override(PrefabOverride::entity(5));
+        assert!(prefabs.instantiate(&mut world, &bad_override).is_err());
+    }
+
+    #[test]
+    fn test_recursive_prefabs_are_rejected() {
+        let mut prefabs = PrefabRegistry::new();
+        let looping =
+            Scene::from_ron(r#"(entities: [()], instances: [(prefab: "loop", parent: 0)])"#)
+                .unwrap();
+        prefabs.register("loop", looping).unwrap();
+        let instance = PrefabInstance::new("loop", Transform::identity());
+        assert!(prefabs
+            .instantiate(&mut hecs::World::new(), &instance)
+            .is_err());
+    }
+}
@@ -0,0 +1,757 @@
+//! Scene serialization.
+//!
+//! A [`Scene`] is a plain-data description of ECS entities that round-trips
+//! through RON. It covers transforms and hierarchy, rigid bodies, colliders,
+//! lights, cameras and mesh/material references; GPU resources are created
+//! afterwards by [`attach_mesh_renderers`] through a [`SceneAssets`]
+//! implementation, so physics-only scenes load without a GPU. Scenes can
+//! also place instances of [`Prefab`](super::prefab)s.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use glam::{Mat4, Vec3};
+use ron::extensions::Extensions;
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+
+use crate::context::WgpuContext;
+use crate::ecs::bridge::MaterialResource;
+use crate::ecs::components::physics::{Collider, RigidBody, RigidBodyType, SleepInfo};
+use crate::ecs::components::rendering::{
+    CameraComponent, FrustumCullable, LightComponent, MaterialHandle, MeshHandle, MeshRenderer,
+    ViewportRect, Visible,
+};
+use crate::ecs::components::transform::{Children, GlobalTransform, Parent, Transform};
+use crate::ecs::prefab::{PrefabInstance, PrefabRegistry};
+use crate::renderer::geometry::{Geometry, Mesh};
+use crate::renderer::material::{ColorMaterial, PbrMaterial};
+use crate::renderer::viewer::{Camera, Projection};
+
+/// Serializable description of a set of entities.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Scene {
+    pub entities: Vec<SceneEntity>,
+    /// Prefab instances, spawned after `entities` by
+    /// [`spawn_with`](Self::spawn_with).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instances: Vec<PrefabInstance>,
+}
+
+/// One entity of a [`Scene`]. Every field is optional in the file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SceneEntity {
+    /// Index of the parent in [`Scene::entities`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<usize>,
+    /// Transform relative to the parent.
+    pub transform: Transform,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rigid_body: Option<RigidBodyDesc>,
+    /// Whether the body gets a [`SleepInfo`] and may fall asleep.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub sleep: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collider: Option<Collider>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub light: Option<LightComponent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraDesc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mesh: Option<MeshRendererDesc>,
+}
+
+impl SceneEntity {
+    /// The entity's components, without hierarchy links and with its
+    /// transform as its world transform, as a bundle that spawns copies
+    /// with `world.spawn(&bundle)`. Build it once to place many copies of a
+    /// template, setting each copy's transforms after spawning.
+    pub fn bundle(&self) -> hecs::BuiltEntityClone {
+        self.builder(self.transform.to_matrix()).build()
+    }
+
+    fn builder(&self, global: Mat4) -> hecs::EntityBuilderClone {
+        let mut builder = hecs::EntityBuilderClone::new();
+        builder.add(self.transform).add(GlobalTransform(global));
+        if let Some(body) = &self.rigid_body {
+            builder.add(body.to_component());
+        }
+        if self.sleep {
+            builder.add(SleepInfo::default());
+        }
+        if let Some(collider) = &self.collider {
+            builder.add(collider.clone());
+        }
+        if let Some(light) = &self.light {
+            builder.add(light.clone());
+        }
+        if let Some(camera) = &self.camera {
+            builder.add(camera.to_component());
+        }
+        if let Some(mesh) = &self.mesh {
+            builder.add(mesh.clone());
+        }
+        builder
+    }
+}
+
+/// Rigid body settings. Unset fields take the defaults of
+/// [`RigidBody::new_dynamic`], [`new_static`](RigidBody::new_static) or
+/// [`new_kinematic`](RigidBody::new_kinematic).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RigidBodyDesc {
+    pub body_type: RigidBodyType,
+    /// Mass of dynamic bodies (default: 1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mass: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inertia_tensor: Option<[f32; 9]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linear_velocity: Option<Vec3>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub angular_velocity: Option<Vec3>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linear_damping: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub angular_damping: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restitution: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub friction: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gravity_scale: Option<f32>,
+}
+
+impl RigidBodyDesc {
+    /// Build the component. Force and torque accumulators start at zero.
+    pub fn to_component(&self) -> RigidBody {
+        let mut body = Self::base(self.body_type, self.mass);
+        if let Some(v) = self.inertia_tensor {
+            body.inertia_tensor = v;
+        }
+        if let Some(v) = self.linear_velocity {
+            body.linear_velocity = v;
+        }
+        if let Some(v) = self.angular_velocity {
+            body.angular_velocity = v;
+        }
+        if let Some(v) = self.linear_damping {
+            body.linear_damping = v;
+        }
+        if let Some(v) = self.angular_damping {
+            body.angular_damping = v;
+        }
+        if let Some(v) = self.restitution {
+            body.restitution = v;
+        }
+        if let Some(v) = self.friction {
+            body.friction = v;
+        }
+        if let Some(v) = self.gravity_scale {
+            body.gravity_scale = v;
+        }
+        body
+    }
+
+    /// Describe `body`, recording only the fields that differ from the
+    /// defaults of its type.
+    pub fn from_component(body: &RigidBody) -> Self {
+        let mass = (body.body_type == RigidBodyType::Dynamic).then_some(body.mass);
+        let base = Self::base(body.body_type, mass);
+        let changed = |value: f32, default: f32| (value != default).then_some(value);
+        let changed_vec = |value: Vec3, default: Vec3| (value != default).then_some(value);
+        Self {
+            body_type: body.body_type,
+            mass: mass.filter(|&m| m != 1.0),
+            inertia_tensor: (body.inertia_tensor != base.inertia_tensor)
+                .then_some(body.inertia_tensor),
+            linear_velocity: changed_vec(body.linear_velocity, base.linear_velocity),
+            angular_velocity: changed_vec(body.angular_velocity, base.angular_velocity),
+            linear_damping: changed(body.linear_damping, base.linear_damping),
+            angular_damping: changed(body.angular_damping, base.angular_damping),
+            restitution: changed(body.restitution, base.restitution),
+            friction: changed(body.friction, base.friction),
+            gravity_scale: changed(body.gravity_scale, base.gravity_scale),
+        }
+    }
+
+    fn base(body_type: RigidBodyType, mass: Option<f32>) -> RigidBody {
+        match body_type {
+            RigidBodyType::Dynamic => RigidBody::new_dynamic(mass.unwrap_or(1.0)),
+            RigidBodyType::Static => RigidBody::new_static(),
+            RigidBodyType::Kinematic => RigidBody::new_kinematic(),
+        }
+    }
+}
+
+/// Camera settings; see [`CameraComponent`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CameraDesc {
+    pub position: Vec3,
+    pub target: Vec3,
+    #[serde(default = "default_up")]
+    pub up: Vec3,
+    pub projection: Projection,
+    #[serde(default = "default_true")]
+    pub active: bool,
+    #[serde(default)]
+    pub viewport: ViewportRect,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl CameraDesc {
+    /// Build the component.
+    pub fn to_component(&self) -> CameraComponent {
+        let mut camera =
+            Camera::new_perspective(self.position, self.target, self.up, 60.0, 1.0, 0.1, 100.0);
+        camera.projection = self.projection;
+        CameraComponent {
+            camera,
+            active: self.active,
+            viewport: self.viewport,
+            priority: self.priority,
+        }
+    }
+
+    /// Describe `camera`.
+    pub fn from_component(camera: &CameraComponent) -> Self {
+        Self {
+            position: camera.camera.position,
+            target: camera.camera.target,
+            up: camera.camera.up,
+            projection: camera.camera.projection,
+            active: camera.active,
+            viewport: camera.viewport,
+            priority: camera.priority,
+        }
+    }
+}
+
+/// Built-in mesh shapes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Primitive {
+    Cube {
+        size: f32,
+    },
+    Sphere {
+        radius: f32,
+        segments: u32,
+        rings: u32,
+    },
+    Cylinder {
+        radius: f32,
+        height: f32,
+        segments: u32,
+    },
+    /// Quad in the XZ plane.
+    Quad {
+        width: f32,
+        depth: f32,
+    },
+}
+
+/// Reference to a mesh by primitive descriptor or asset path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MeshRef {
+    Primitive {
+        shape: Primitive,
+        /// Vertex color.
+        #[serde(default = "default_color")]
+        color: [f32; 3],
+    },
+    Asset(String),
+}
+
+/// Reference to a material by parameters or asset path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MaterialRef {
+    /// A [`ColorMaterial`] shaded with the mesh's vertex colors.
+    Color,
+    /// A [`PbrMaterial`].
+    Pbr {
+        base_color: [f32; 4],
+        #[serde(default)]
+        metallic: f32,
+        #[serde(default = "default_roughness")]
+        roughness: f32,
+        #[serde(default)]
+        emissive: [f32; 3],
+    },
+    Asset(String),
+}
+
+/// Mesh and material of a rendered entity.
+///
+/// Spawned scenes keep this as a component, so [`attach_mesh_renderers`]
+/// can build the [`MeshRenderer`] and [`Scene::from_world`] can save the
+/// references again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshRendererDesc {
+    pub mesh: MeshRef,
+    pub material: MaterialRef,
+    #[serde(default = "default_true")]
+    pub visible: bool,
+    #[serde(default = "default_true")]
+    pub cast_shadow: bool,
+    #[serde(default = "default_true")]
+    pub receive_shadow: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_up() -> Vec3 {
+    Vec3::Y
+}
+
+fn default_color() -> [f32; 3] {
+    [1.0; 3]
+}
+
+fn default_roughness() -> f32 {
+    0.5
+}
+
+impl Scene {
+    /// Parse a scene from RON. Optional fields may omit `Some(..)`.
+    pub fn from_ron(text: &str) -> anyhow::Result<Self> {
+        ron::Options::default()
+            .with_default_extension(Extensions::IMPLICIT_SOME)
+            .from_str(text)
+            .context("Failed to parse scene")
+    }
+
+    /// Serialize the scene to pretty-printed RON.
+    pub fn to_ron(&self) -> anyhow::Result<String> {
+        let config = PrettyConfig::default().extensions(Extensions::IMPLICIT_SOME);
+        ron::ser::to_string_pretty(self, config).context("Failed to serialize scene")
+    }
+
+    /// Read a RON scene file.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read scene {}", path.display()))?;
+        Self::from_ron(&text).with_context(|| format!("In scene {}", path.display()))
+    }
+
+    /// Write the scene to a RON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_ron()?)
+            .with_context(|| format!("Failed to write scene {}", path.display()))
+    }
+
+    /// Describe every entity of `world` with a [`Transform`], in entity
+    /// order.
+    ///
+    /// Meshes are saved from their [`MeshRendererDesc`]; renderers built in
+    /// code have no reference to save and are skipped. Parents outside the
+    /// saved set are dropped. Prefab instances are saved expanded.
+    pub fn from_world(world: &hecs::World) -> Self {
+        let mut saved: Vec<_> = world
+            .query::<hecs::With<(), &Transform>>()
+            .iter()
+            .map(|(entity, ())| entity)
+            .collect();
+        saved.sort_by_key(|entity| entity.id());
+        Self::from_entities(world, &saved)
+    }
+
+    /// Describe `root` and its descendants with a [`Transform`], root
+    /// first, e.g. to register them as a prefab.
+    pub fn from_entity_tree(world: &hecs::World, root: hecs::Entity) -> anyhow::Result<Self> {
+        if !world.satisfies::<&Transform>(root)? {
+            bail!("Scene root {root:?} has no transform");
+        }
+        let mut saved = vec![root];
+        let mut next = 0;
+        while next < saved.len() {
+            if let Ok(children) = world.get::<&Children>(saved[next]) {
+                saved.extend(
+                    children
+                        .0
+                        .iter()
+                        .copied()
+                        .filter(|&child| world.satisfies::<&Transform>(child).unwrap_or(false)),
+                );
+            }
+            next += 1;
+        }
+        let mut scene = Self::from_entities(world, &saved);
+        scene.entities[0].parent = None;
+        Ok(scene)
+    }
+
+    fn from_entities(world: &hecs::World, saved: &[hecs::Entity]) -> Self {
+        let index: HashMap<_, _> = saved.iter().enumerate().map(|(i, &e)| (e, i)).collect();
+        let entities = saved
+            .iter()
+            .map(|&entity| {
+                let entity = world.entity(entity).expect("saved entities exist");
+                let parent = entity.get::<&Parent>().and_then(|parent| {
+                    let found = index.get(&parent.0).copied();
+                    if found.is_none() {
+                        tracing::warn!("Scene parent {:?} is not saved, dropped", parent.0);
+                    }
+                    found
+                });
+                let mesh = entity.get::<&MeshRendererDesc>().map(|desc| {
+                    let mut desc = (*desc).clone();
+                    if let Some(renderer) = entity.get::<&MeshRenderer>() {
+                        desc.visible = renderer.visible;
+                        desc.cast_shadow = renderer.cast_shadow;
+                        desc.receive_shadow = renderer.receive_shadow;
+                    }
+                    desc
+                });
+                SceneEntity {
+                    parent,
+                    transform: *entity.get::<&Transform>().expect("saved with Transform"),
+                    rigid_body: entity
+                        .get::<&RigidBody>()
+                        .map(|body| RigidBodyDesc::from_component(&body)),
+                    sleep: entity.has::<SleepInfo>(),
+                    collider: entity.get::<&Collider>().map(|c| (*c).clone()),
+                    light: entity.get::<&LightComponent>().map(|l| (*l).clone()),
+                    camera: entity
+                        .get::<&CameraComponent>()
+                        .map(|camera| CameraDesc::from_component(&camera)),
+                    mesh,
+                }
+            })
+            .collect();
+        Self {
+            entities,
+            instances: Vec::new(),
+        }
+    }
+
+    /// Spawn the scene into `world`, returning the entities in scene order.
+    ///
+    /// Entities get a [`GlobalTransform`] composed from their parents and
+    /// [`Parent`]/[`Children`] links. Rendered entities carry their
+    /// [`MeshRendererDesc`] until [`attach_mesh_renderers`] runs. Fails if
+    /// the scene has prefab instances; use [`spawn_with`](Self::spawn_with).
+    pub fn spawn(&self, world: &mut hecs::World) -> anyhow::Result<Vec<hecs::Entity>> {
+        self.spawn_with(world, &PrefabRegistry::new())
+    }
+
+    /// Spawn the scene, instantiating its prefab instances from `prefabs`.
+    ///
+    /// Returns the scene entities followed by the entities of each instance,
+    /// root first.
+    pub fn spawn_with(
+        &self,
+        world: &mut hecs::World,
+        prefabs: &PrefabRegistry,
+    ) -> anyhow::Result<Vec<hecs::Entity>> {
+        prefabs.expand(self)?.spawn_entities(world)
+    }
+
+    /// Spawn `entities`, which must not reference prefabs.
+    fn spawn_entities(&self, world: &mut hecs::World) -> anyhow::Result<Vec<hecs::Entity>> {
+        debug_assert!(self.instances.is_empty(), "prefab instances not expanded");
+        let globals = self.global_matrices()?;
+
+        let mut spawned = Vec::with_capacity(self.entities.len());
+        for (desc, global) in self.entities.iter().zip(globals) {
+            spawned.push(world.spawn(&desc.builder(global).build()));
+        }
+
+        let mut children: HashMap<usize, Vec<hecs::Entity>> = HashMap::new();
+        for (i, desc) in self.entities.iter().enumerate() {
+            if let Some(parent) = desc.parent {
+                world.insert_one(spawned[i], Parent(spawned[parent]))?;
+                children.entry(parent).or_default().push(spawned[i]);
+            }
+        }
+        for (parent, list) in children {
+            world.insert_one(spawned[parent], Children(list))?;
+        }
+        Ok(spawned)
+    }
+
+    /// World matrix of every entity, failing on invalid or cyclic parents.
+    fn global_matrices(&self) -> anyhow::Result<Vec<Mat4>> {
+        let n = self.entities.len();
+        let mut globals: Vec<Option<Mat4>> = vec![None; n];
+        for start in 0..n {
+            // Walk up to the first resolved ancestor, then resolve downwards
+            let mut chain = vec![start];
+            let mut current = start;
+            while globals[current].is_none() {
+                let Some(parent) = self.entities[current].parent else {
+                    break;
+                };
+                if parent >= n {
+                    bail!("Scene entity {current} has parent {parent} out of range");
+                }
+                if chain.contains(&parent) {
+                    bail!("Scene entity {current} is in a parent cycle");
+                }
+                chain.push(parent);
+                current = parent;
+            }
+            for &i in chain.iter().rev() {
+                if globals[i].is_some() {
+                    continue;
+                }
+                let local = self.entities[i].transform.to_matrix();
+                let parent = self.entities[i]
+                    .parent
+                    .and_then(|p| globals[p])
+                    .unwrap_or(Mat4::IDENTITY);
+                globals[i] = Some(parent * local);
+            }
+        }
+        Ok(globals
+            .into_iter()
+            .map(|m| m.unwrap_or(Mat4::IDENTITY))
+            .collect())
+    }
+}
+
+/// Resolves scene mesh and material references to GPU resources.
+pub trait SceneAssets {
+    fn mesh(&mut self, mesh: &MeshRef) -> anyhow::Result<MeshHandle>;
+    fn material(&mut self, material: &MaterialRef) -> anyhow::Result<MaterialHandle>;
+}
+
+/// [`SceneAssets`] for primitive meshes and parameter materials, sharing
+/// one resource per distinct reference so draws batch. Asset paths are
+/// rejected.
+pub struct PrimitiveAssets<'a> {
+    ctx: &'a WgpuContext,
+    format: wgpu::TextureFormat,
+    meshes: HashMap<String, Arc<dyn Geometry + Send + Sync>>,
+    materials: HashMap<String, Arc<dyn MaterialResource>>,
+}
+
+impl<'a> PrimitiveAssets<'a> {
+    /// Create materials for color targets of `format`.
+    pub fn new(ctx: &'a WgpuContext, format: wgpu::TextureFormat) -> Self {
+        Self {
+            ctx,
+            format,
+            meshes: HashMap::new(),
+            materials: HashMap::new(),
+        }
+    }
+}
+
+impl SceneAssets for PrimitiveAssets<'_> {
+    fn mesh(&mut self, mesh: &MeshRef) -> anyhow::Result<MeshHandle> {
+        let key = format!("{mesh:?}");
+        if let Some(mesh) = self.meshes.get(&key) {
+            return Ok(MeshHandle(mesh.clone()));
+        }
+        let ctx = self.ctx;
+        let geometry = match mesh {
+            MeshRef::Primitive { shape, color } => match *shape {
+                Primitive::Cube { size } => Mesh::cube(ctx, size, *color),
+                Primitive::Sphere {
+                    radius,
+                    segments,
+                    rings,
+                } => Mesh::sphere(ctx, radius, segments, rings, *color),
+                Primitive::Cylinder {
+                    radius,
+                    height,
+                    segments,
+                } => Mesh::cylinder(ctx, radius, height, segments, *color),
+                Primitive::Quad { width, depth } => Mesh::quad(ctx, width, depth, *color),
+            },
+            MeshRef::Asset(path) => bail!("No loader for mesh asset {path:?}"),
+        };
+        let geometry: Arc<dyn Geometry + Send + Sync> = Arc::new(geometry);
+        self.meshes.insert(key, geometry.clone());
+        Ok(MeshHandle(geometry))
+    }
+
+    fn material(&mut self, material: &MaterialRef) -> anyhow::Result<MaterialHandle> {
+        let key = format!("{material:?}");
+        if let Some(material) = self.materials.get(&key) {
+            return Ok(MaterialHandle(material.clone()));
+        }
+        let resource: Arc<dyn MaterialResource> = match material {
+            MaterialRef::Color => Arc::new(ColorMaterial::new(self.ctx, self.format)?),
+            MaterialRef::Pbr {
+                base_color,
+                metallic,
+                roughness,
+                emissive,
+            } => Arc::new(PbrMaterial::with_params(
+                self.ctx,
+                self.format,
+                *base_color,
+                *metallic,
+                *roughness,
+                *emissive,
+                1.0,
+            )?),
+            MaterialRef::Asset(path) => bail!("No loader for material asset {path:?}"),
+        };
+        self.materials.insert(key, resource.clone());
+        Ok(MaterialHandle(resource))
+    }
+}
+
+/// Build a [`MeshRenderer`] for every entity with a [`MeshRendererDesc`]
+/// and no renderer yet, returning how many were attached.
+pub fn attach_mesh_renderers(
+    world: &mut hecs::World,
+    assets: &mut dyn SceneAssets,
+) -> anyhow::Result<usize> {
+    let pending: Vec<_> = world
+        .query::<hecs::Without<&MeshRendererDesc, &MeshRenderer>>()
+        .iter()
+        .map(|(entity, desc)| (entity, desc.clone()))
+        .collect();
+    for (entity, desc) in &pending {
+        let renderer = MeshRenderer {
+            mesh: assets.mesh(&desc.mesh)?,
+            material: assets.material(&desc.material)?,
+            visible: desc.visible,
+            cast_shadow: desc.cast_shadow,
+            receive_shadow: desc.receive_shadow,
+        };
+        world.insert(*entity, (renderer, FrustumCullable, Visible))?;
+    }
+    Ok(pending.len())
+}
+
+/// Spawn a test fixture scene into a new world, returning the entities in
+/// scene order.
+#[cfg(test)]
+pub(crate) fn spawn_fixture(text: &str) -> (hecs::World, Vec<hecs::Entity>) {
+    let mut world = hecs::World::new();
+    let entities = Scene::from_ron(text)
+        .and_then(|scene| scene.spawn(&mut world))
+        .expect("invalid fixture scene");
+    (world, entities)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::physics::ColliderShape;
+    use glam::Quat;
+
+    const SCENE: &str = include_str!("../../tests/fixtures/scene.ron");
+
+    #[test]
+    fn test_scene_spawns_components_and_hierarchy() {
+        let scene = Scene::from_ron(SCENE).unwrap();
+        let mut world = hecs::World::new();
+        let entities = scene.spawn(&mut world).unwrap();
+
+        let ground = world.get::<&RigidBody>(entities[0]).unwrap();
+        assert_eq!(ground.body_type, RigidBodyType::Static);
+        drop(ground);
+
+        // The child is offset in its scaled parent's space
+        let child_global = world.get::<&GlobalTransform>(entities[1]).unwrap().0;
+        let child_position = child_global.transform_point3(Vec3::ZERO);
+        assert!((child_position - Vec3::new(2.0, 2.0, 0.0)).length() < 1e-5);
+        assert_eq!(world.get::<&Parent>(entities[1]).unwrap().0, entities[2]);
+        assert_eq!(
+            world.get::<&Children>(entities[2]).unwrap().0,
+            [entities[1]]
+        );
+        assert!(
+            !world
+                .get::<&MeshRendererDesc>(entities[1])
+                .unwrap()
+                .cast_shadow
+        );
+
+        let body = world.get::<&RigidBody>(entities[2]).unwrap();
+        assert_eq!(body.mass, 3.0);
+        assert!(world.satisfies::<&SleepInfo>(entities[2]).unwrap());
+
+        let light = world.get::<&LightComponent>(entities[3]).unwrap();
+        assert_eq!(light.range, 5.0);
+        assert_eq!(light.intensity, 1.0);
+        let camera = world.get::<&CameraComponent>(entities[3]).unwrap();
+        assert_eq!(camera.priority, 2);
+        assert!(camera.active);
+    }
+
+    #[test]
+    fn test_scene_round_trips_through_world() {
+        let mut world = hecs::World::new();
+        Scene::from_ron(SCENE).unwrap().spawn(&mut world).unwrap();
+        let mut body = RigidBody::new_dynamic(2.0);
+        body.linear_velocity = Vec3::X;
+        world.spawn((
+            Transform {
+                position: Vec3::ONE,
+                rotation: Quat::from_rotation_y(0.5),
+                scale: Vec3::ONE,
+            },
+            body,
+            Collider {
+                shape: ColliderShape::Capsule {
+                    radius: 0.25,
+                    half_height: 0.5,
+                },
+                ..Default::default()
+            },
+        ));
+
+        let saved = Scene::from_world(&world);
+        let text = saved.to_ron().unwrap();
+        let loaded = Scene::from_ron(&text).unwrap();
+        assert_eq!(loaded.entities.len(), 5);
+        assert_eq!(loaded.entities[1].parent, Some(2));
+        assert_eq!(loaded.entities[1].mesh, saved.entities[1].mesh);
+        let body = loaded.entities[4].rigid_body.as_ref().unwrap();
+        assert_eq!(body.mass, Some(2.0));
+        assert_eq!(body.linear_velocity, Some(Vec3::X));
+        assert_eq!(body.friction, None);
+
+        let mut reloaded = hecs::World::new();
+        loaded.spawn(&mut reloaded).unwrap();
+        assert_eq!(Scene::from_world(&reloaded).to_ron().unwrap(), text);
+    }
+
+    #[test]
+    fn test_bundle_spawns_copies_of_a_template() {
+        let scene = Scene::from_ron(SCENE).unwrap();
+        let bundle = scene.entities[2].bundle();
+        let mut world = hecs::World::new();
+        let a = world.spawn(&bundle);
+        let b = world.spawn(&bundle);
+
+        world.get::<&mut Transform>(b).unwrap().position = Vec3::X;
+        assert_eq!(world.get::<&Transform>(a).unwrap().position, Vec3::Y * 2.0);
+        let global = world.get::<&GlobalTransform>(a).unwrap().0;
+        assert!((global.transform_point3(Vec3::ZERO) - Vec3::Y * 2.0).length() < 1e-5);
+        assert_eq!(world.get::<&RigidBody>(b).unwrap().mass, 3.0);
+        assert!(world.satisfies::<&SleepInfo>(b).unwrap());
+        assert!(!world.satisfies::<&Parent>(a).unwrap());
+    }
+
+    #[test]
+    fn test_scene_rejects_parent_cycles() {
+        let mut scene = Scene::default();
+        scene.entities.push(SceneEntity {
+            parent: Some(1),
+            ..Default::default()
+        });
+        scene.entities.push(SceneEntity {
+            parent: Some(0),
+            ..Default::default()
+        });
+        assert!(scene.spawn(&mut hecs::World::new()).is_err());
+
+        scene.entities[1].parent = Some(7);
+        assert!(scene.spawn(&mut hecs::World::new()).is_err());
+    }
+}
@@ -19,27 +19,23 @@ fn compute_world_aabb(local_aabb: Aabb, world_matrix: glam::Mat4) -> Aabb {
 
 /// Frustum culling system.
 ///
-/// Finds the active camera, builds its view frustum, then tests each
-/// `FrustumCullable` entity's world-space AABB. Entities that pass the test
-/// receive the `Visible` marker; those that fail have it removed.
+/// Builds the view frustum of every active camera from its world-space view,
+/// then tests each `FrustumCullable` entity's world-space AABB. Entities
+/// inside at least one frustum receive the `Visible` marker; those outside
+/// all of them have it removed.
 pub fn culling_system(world: &mut hecs::World) {
-    // Find the active camera and build frustum.
-    let frustum = {
-        let mut found = None;
-        for (_, (cam, _global)) in
-            world.query_mut::<hecs::Without<(&CameraComponent, &GlobalTransform), &MeshRenderer>>()
-        {
-            if cam.active {
-                let vp = cam.camera.view_projection_matrix();
-                found = Some(Frustum::from_view_projection(vp));
-                break;
-            }
-        }
-        match found {
-            Some(f) => f,
-            None => return, // No active camera, skip culling.
-        }
-    };
+    // Build a frustum per active camera.
+    let frustums: Vec<Frustum> = world
+        .query_mut::<hecs::Without<(&CameraComponent, &GlobalTransform), &MeshRenderer>>()
+        .into_iter()
+        .filter(|(_, (cam, _))| cam.active)
+        .map(|(_, (cam, global))| {
+            Frustum::from_view_projection(cam.view(global).view_projection_matrix())
+        })
+        .collect();
+    if frustums.is_empty() {
+        return; // No active camera, skip culling.
+    }
 
     // Test each FrustumCullable entity and collect results.
     let mut to_add_visible: Vec<hecs::Entity> = Vec::new();
@@ -51,7 +47,7 @@ pub fn culling_system(world: &mut hecs::World) {
         let local_aabb = renderer.mesh.0.aabb();
         let world_aabb = compute_world_aabb(local_aabb, global.0);
 
-        if frustum.contains_aabb(&world_aabb) {
+        if frustums.iter().any(|f| f.contains_aabb(&world_aabb)) {
             to_add_visible.push(entity);
         } else {
             to_remove_visible.push(entity);
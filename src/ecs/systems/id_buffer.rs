@@ -0,0 +1,672 @@
+//! GPU picking ID pass.
+//!
+//! [`render_id_buffer`] draws every visible `MeshRenderer` entity with
+//! triangle geometry into the R32Uint texture of an [`IdBuffer`], one pick ID
+//! per pixel. Meshes with
+//! their own instance buffer (e.g. `InstancedMesh` point clouds) get an ID
+//! per instance. [`IdBuffer::query_pixel`] and [`IdBuffer::query_rect`] then
+//! read the IDs under the cursor or a selection box back without stalling
+//! the frame, resolving them to entities when the copy is done.
+//!
+//! Unlike the CPU picking in [`crate::ecs::picking`], this handles any amount
+//! of geometry at the cost of a frame of latency.
+
+use std::sync::{Arc, Mutex};
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::context::WgpuContext;
+use crate::core::hot_reload::{HotShader, ShaderFile};
+use crate::core::instance::InstanceData;
+use crate::core::pipeline::{PipelineBuilder, Vertex};
+use crate::core::render_states::{CullState, DepthState};
+use crate::core::render_target::RenderTarget;
+use crate::core::texture::{DepthTexture, Texture2D};
+use crate::ecs::components::rendering::{MeshRenderer, Visible};
+use crate::ecs::components::transform::GlobalTransform;
+use crate::renderer::geometry::Geometry;
+use crate::renderer::material::CameraUniformRing;
+use crate::renderer::viewer::CameraUniform;
+
+use super::render::collect_active_cameras;
+
+/// Texture format of the ID buffer. 0 is "nothing".
+pub const ID_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Uint;
+
+/// Initial capacity of the per-draw record buffer.
+const INITIAL_RECORD_CAPACITY: u64 = 64;
+
+/// Per-draw vertex data: the entity's world matrix and its first pick ID.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct IdRecord {
+    model: [[f32; 4]; 4],
+    id: u32,
+    _padding: [u32; 3],
+}
+
+const RECORD_SIZE: u64 = std::mem::size_of::<IdRecord>() as u64;
+
+const RECORD_ATTRIBUTES: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+    9 => Float32x4,
+    10 => Float32x4,
+    11 => Float32x4,
+    12 => Float32x4,
+    13 => Uint32,
+];
+
+/// Records step once per instance of a plain mesh batch, or not at all
+/// (`array_stride` 0) for a mesh with its own instances.
+fn record_layout(array_stride: u64) -> wgpu::VertexBufferLayout<'static> {
+    wgpu::VertexBufferLayout {
+        array_stride,
+        step_mode: wgpu::VertexStepMode::Instance,
+        attributes: &RECORD_ATTRIBUTES,
+    }
+}
+
+/// Entity, and instance for meshes with their own instances, under a pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PickId {
+    pub entity: hecs::Entity,
+    /// Index into the mesh's instance buffer, `None` for plain meshes.
+    pub instance: Option<u32>,
+}
+
+/// Pick IDs `first..first + count` assigned to one entity in a pass.
+#[derive(Debug, Clone, Copy)]
+struct IdRange {
+    first: u32,
+    count: u32,
+    entity: hecs::Entity,
+    instanced: bool,
+}
+
+/// Resolve a pick ID with the ranges of the pass that wrote it.
+fn resolve(ranges: &[IdRange], id: u32) -> Option<PickId> {
+    let range = ranges[..ranges.partition_point(|range| range.first <= id)].last()?;
+    (id - range.first < range.count).then(|| PickId {
+        entity: range.entity,
+        instance: range.instanced.then_some(id - range.first),
+    })
+}
+
+/// Single-entity and instanced pipelines, rebuilt together on shader reload.
+struct IdPipelines {
+    single: wgpu::RenderPipeline,
+    instanced: wgpu::RenderPipeline,
+}
+
+impl IdPipelines {
+    fn new(ctx: &WgpuContext) -> anyhow::Result<HotShader<Self>> {
+        let shader = ShaderFile::embedded(
+            "id_buffer.wgsl",
+            include_str!("../../shaders/id_buffer.wgsl"),
+        );
+        let (pipeline_ctx, camera_layout) = (ctx.clone(), CameraUniform::bind_group_layout(ctx));
+        let watched = shader.is_watched();
+        HotShader::new(shader, move |shader| {
+            let builder = |label| {
+                PipelineBuilder::new(&pipeline_ctx)
+                    .label(label)
+                    .shader(shader)
+                    .catch_errors(watched)
+                    .vertex_layout(Vertex::layout())
+                    .bind_group_layout(&camera_layout)
+            };
+            let single = builder("id buffer pipeline")
+                .vertex_layout(record_layout(RECORD_SIZE))
+                .color_format(ID_FORMAT)
+                .depth(DepthState::read_write())
+                .cull(CullState::None)
+                .build()?;
+            let instanced = builder("id buffer instanced pipeline")
+                .vertex_entry("vs_instanced")
+                .vertex_layout(InstanceData::layout())
+                .vertex_layout(record_layout(0))
+                .color_format(ID_FORMAT)
+                .depth(DepthState::read_write())
+                .cull(CullState::None)
+                .build()?;
+            Ok(Self { single, instanced })
+        })
+    }
+}
+
+/// Offscreen ID target of the picking pass, with its depth buffer and the
+/// ID assignment of the last pass.
+///
+/// Insert one into the engine's `Resources` to have the engine render it
+/// every frame at the window size.
+pub struct IdBuffer {
+    ids: Texture2D,
+    depth: DepthTexture,
+    pipelines: HotShader<IdPipelines>,
+    cameras: CameraUniformRing,
+    records: wgpu::Buffer,
+    record_capacity: u64,
+    ranges: Arc<[IdRange]>,
+}
+
+impl IdBuffer {
+    /// Create an ID buffer of `width` x `height` pixels.
+    pub fn new(ctx: &WgpuContext, width: u32, height: u32) -> anyhow::Result<Self> {
+        Ok(Self {
+            ids: Self::create_ids(ctx, width, height),
+            depth: DepthTexture::new(ctx, width, height, Some("id buffer depth")),
+            pipelines: IdPipelines::new(ctx)?,
+            cameras: CameraUniformRing::new(ctx),
+            records: Self::create_records(ctx, INITIAL_RECORD_CAPACITY),
+            record_capacity: INITIAL_RECORD_CAPACITY,
+            ranges: Arc::new([]),
+        })
+    }
+
+    fn create_ids(ctx: &WgpuContext, width: u32, height: u32) -> Texture2D {
+        Texture2D::new(
+            ctx,
+            width.max(1),
+            height.max(1),
+            ID_FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            Some("id buffer"),
+        )
+    }
+
+    fn create_records(ctx: &WgpuContext, capacity: u64) -> wgpu::Buffer {
+        ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("id buffer records"),
+            size: capacity * RECORD_SIZE,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Rebuild the pipelines if their shader file changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// they were rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipelines.reload_if_changed()
+    }
+
+    /// Size in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        self.ids.size()
+    }
+
+    /// Resize, discarding the current IDs.
+    pub fn resize(&mut self, ctx: &WgpuContext, width: u32, height: u32) {
+        if self.size() == (width.max(1), height.max(1)) {
+            return;
+        }
+        self.ids = Self::create_ids(ctx, width, height);
+        self.depth.resize(ctx, width.max(1), height.max(1));
+        self.ranges = Arc::new([]);
+    }
+
+    /// The ID texture.
+    pub fn texture(&self) -> &Texture2D {
+        &self.ids
+    }
+
+    /// The ID texture and depth buffer as a render target.
+    pub fn target<'a>(&'a self, ctx: &'a WgpuContext) -> RenderTarget<'a> {
+        let (width, height) = self.size();
+        RenderTarget::new(
+            ctx,
+            self.ids.view(),
+            Some(self.depth.view()),
+            width,
+            height,
+            ID_FORMAT,
+        )
+    }
+
+    /// Start reading back the ID under pixel (`x`, `y`), from the top-left.
+    pub fn query_pixel(&self, ctx: &WgpuContext, x: u32, y: u32) -> IdQuery {
+        self.query_rect(ctx, x, y, 1, 1)
+    }
+
+    /// Start reading back the IDs in a `width` x `height` rectangle at
+    /// (`x`, `y`), clamped to the buffer, for box selection.
+    ///
+    /// The copy is submitted right away; the result resolves against the
+    /// pass the buffer holds now, even if it is rendered again meanwhile.
+    pub fn query_rect(
+        &self,
+        ctx: &WgpuContext,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> IdQuery {
+        let (buffer_width, buffer_height) = self.size();
+        let width = width.min(buffer_width.saturating_sub(x));
+        let height = height.min(buffer_height.saturating_sub(y));
+        let readback =
+            (width > 0 && height > 0).then(|| Readback::start(ctx, &self.ids, x, y, width, height));
+        IdQuery {
+            readback,
+            ranges: self.ranges.clone(),
+        }
+    }
+}
+
+/// Texture-to-buffer copy of a rectangle of IDs being mapped for reading.
+struct Readback {
+    staging: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_row_bytes: u32,
+    mapped: Arc<Mutex<Option<Result<(), wgpu::BufferAsyncError>>>>,
+}
+
+impl Readback {
+    fn start(ctx: &WgpuContext, ids: &Texture2D, x: u32, y: u32, width: u32, height: u32) -> Self {
+        let padded_row_bytes = (width * 4).next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let staging = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("id buffer readback staging"),
+            size: padded_row_bytes as u64 * height as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx.create_encoder(Some("id buffer readback"));
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: ids.texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &staging,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        ctx.submit([encoder.finish()]);
+
+        let mapped = Arc::new(Mutex::new(None));
+        let done = mapped.clone();
+        staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                *done.lock().unwrap() = Some(result);
+            });
+        Self {
+            staging,
+            width,
+            height,
+            padded_row_bytes,
+            mapped,
+        }
+    }
+
+    fn is_mapped(&self) -> bool {
+        self.mapped.lock().unwrap().is_some()
+    }
+}
+
+/// Pending read of pick IDs from an [`IdBuffer`].
+pub struct IdQuery {
+    readback: Option<Readback>,
+    ranges: Arc<[IdRange]>,
+}
+
+impl IdQuery {
+    /// Whether the result is available, without blocking.
+    pub fn poll(&self, ctx: &WgpuContext) -> bool {
+        let Some(readback) = &self.readback else {
+            return true;
+        };
+        let _ = ctx.device.poll(wgpu::PollType::Poll);
+        readback.is_mapped()
+    }
+
+    /// The distinct entities and instances in the queried pixels, in row
+    /// order of first appearance. Blocks until the GPU copy is done.
+    pub fn wait(self, ctx: &WgpuContext) -> anyhow::Result<Vec<PickId>> {
+        let Some(readback) = self.readback else {
+            return Ok(Vec::new());
+        };
+        if !readback.is_mapped() {
+            let _ = ctx.device.poll(wgpu::PollType::wait_indefinitely());
+        }
+        readback
+            .mapped
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("ID buffer readback was not mapped"))??;
+
+        let data = readback.staging.slice(..).get_mapped_range();
+        let mut picks = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        for row in data
+            .chunks(readback.padded_row_bytes as usize)
+            .take(readback.height as usize)
+        {
+            let ids: &[u32] = bytemuck::cast_slice(&row[..readback.width as usize * 4]);
+            for &id in ids {
+                if id != 0 && seen.insert(id) {
+                    picks.extend(resolve(&self.ranges, id));
+                }
+            }
+        }
+        drop(data);
+        readback.staging.unmap();
+        Ok(picks)
+    }
+}
+
+/// How a run of ID records is drawn.
+enum IdDraw {
+    /// Plain meshes, one instance per record in this range.
+    Plain(std::ops::Range<u32>),
+    /// A mesh with its own instances, sharing the record at this index.
+    Instanced(u32),
+}
+
+/// Render the pick IDs of every visible `MeshRenderer` entity with
+/// [triangle geometry](Geometry::is_triangle_mesh), as seen by the active
+/// main cameras in their viewports of a target of `target_size` pixels, into
+/// `id_buffer`.
+///
+/// Resizes `id_buffer` to `target_size` so pixels match the main target.
+/// Plain meshes sharing a mesh are drawn with one instanced call. The pass
+/// is submitted on its own. Returns the number of entities drawn.
+pub fn render_id_buffer(
+    world: &hecs::World,
+    ctx: &WgpuContext,
+    id_buffer: &mut IdBuffer,
+    target_size: (u32, u32),
+) -> usize {
+    id_buffer.resize(ctx, target_size.0, target_size.1);
+    let cameras = collect_active_cameras(world, false);
+
+    type SharedMesh = Arc<dyn Geometry + Send + Sync>;
+    let mut plain: Vec<(SharedMesh, glam::Mat4, hecs::Entity)> = Vec::new();
+    let mut instanced: Vec<(SharedMesh, glam::Mat4, hecs::Entity)> = Vec::new();
+    {
+        let mut query = world
+            .query::<(&MeshRenderer, &GlobalTransform)>()
+            .with::<&Visible>();
+        for (entity, (renderer, global)) in query.iter() {
+            if !renderer.visible || !renderer.mesh.0.is_triangle_mesh() {
+                continue;
+            }
+            let draw = (renderer.mesh.0.clone(), global.0, entity);
+            match renderer.mesh.0.instances() {
+                Some(instances) if instances.count() > 0 => instanced.push(draw),
+                Some(_) => {}
+                None => plain.push(draw),
+            }
+        }
+    }
+    plain.sort_by_key(|(mesh, _, entity)| (Arc::as_ptr(mesh) as *const (), *entity));
+    instanced.sort_by_key(|(_, _, entity)| *entity);
+
+    // Assign consecutive IDs from 1: one per plain entity, one per instance
+    let mut records = Vec::with_capacity(plain.len() + instanced.len());
+    let mut ranges = Vec::with_capacity(records.capacity());
+    let mut draws: Vec<(SharedMesh, IdDraw)> = Vec::new();
+    let mut next_id = 1u32;
+    let mut record = |model: glam::Mat4, entity, count, instanced| {
+        ranges.push(IdRange {
+            first: next_id,
+            count,
+            entity,
+            instanced,
+        });
+        records.push(IdRecord {
+            model: model.to_cols_array_2d(),
+            id: next_id,
+            _padding: [0; 3],
+        });
+        next_id += count;
+        records.len() as u32 - 1
+    };
+    for group in plain.chunk_by(|a, b| Arc::ptr_eq(&a.0, &b.0)) {
+        let start = group
+            .iter()
+            .map(|(_, model, entity)| record(*model, *entity, 1, false))
+            .min()
+            .unwrap_or_default();
+        draws.push((
+            group[0].0.clone(),
+            IdDraw::Plain(start..start + group.len() as u32),
+        ));
+    }
+    for (mesh, model, entity) in &instanced {
+        let count = mesh.instances().map_or(0, |instances| instances.count());
+        let index = record(*model, *entity, count, true);
+        draws.push((mesh.clone(), IdDraw::Instanced(index)));
+    }
+
+    if records.len() as u64 > id_buffer.record_capacity {
+        id_buffer.record_capacity = (records.len() as u64).next_power_of_two();
+        id_buffer.records = IdBuffer::create_records(ctx, id_buffer.record_capacity);
+    }
+    ctx.queue
+        .write_buffer(&id_buffer.records, 0, bytemuck::cast_slice(&records));
+    id_buffer.cameras.clear();
+    let camera_offsets: Vec<u32> = cameras
+        .iter()
+        .map(|(_, view, _)| id_buffer.cameras.push_viewer(view))
+        .collect();
+    id_buffer.cameras.upload(ctx);
+
+    let (width, height) = id_buffer.size();
+    let mut encoder = ctx.create_encoder(Some("id buffer pass"));
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("id buffer pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: id_buffer.ids.view(),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: id_buffer.depth.view(),
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        let pipelines = id_buffer.pipelines.get();
+        for ((_, _, rect), camera_offset) in cameras.iter().zip(camera_offsets) {
+            let [x, y, w, h] = rect.to_pixels(width, height);
+            if w < 1.0 || h < 1.0 {
+                continue;
+            }
+            pass.set_viewport(x, y, w, h, 0.0, 1.0);
+            pass.set_bind_group(0, id_buffer.cameras.bind_group(), &[camera_offset]);
+            for (mesh, draw) in &draws {
+                let instances = match draw {
+                    IdDraw::Plain(range) => {
+                        pass.set_pipeline(&pipelines.single);
+                        pass.set_vertex_buffer(1, id_buffer.records.slice(..));
+                        range.clone()
+                    }
+                    IdDraw::Instanced(index) => {
+                        let Some(instances) = mesh.instances() else {
+                            continue;
+                        };
+                        let offset = *index as u64 * RECORD_SIZE;
+                        pass.set_pipeline(&pipelines.instanced);
+                        pass.set_vertex_buffer(1, instances.slice());
+                        pass.set_vertex_buffer(
+                            2,
+                            id_buffer.records.slice(offset..offset + RECORD_SIZE),
+                        );
+                        0..instances.count()
+                    }
+                };
+                pass.set_vertex_buffer(0, mesh.vertex_buffer().slice());
+                if let Some(index_buffer) = mesh.index_buffer() {
+                    pass.set_index_buffer(index_buffer.slice(), wgpu::IndexFormat::Uint32);
+                    pass.draw_indexed(0..mesh.draw_count(), 0, instances);
+                } else {
+                    pass.draw(0..mesh.draw_count(), instances);
+                }
+            }
+        }
+    }
+    ctx.submit([encoder.finish()]);
+
+    id_buffer.ranges = ranges.into();
+    plain.len() + instanced.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::rendering::{CameraComponent, MaterialHandle, MeshHandle};
+    use crate::renderer::geometry::{Axes, InstancedMesh, Mesh};
+    use crate::renderer::material::ColorMaterial;
+    use crate::renderer::viewer::Camera;
+    use glam::{Mat4, Vec3};
+
+    fn try_create_ctx() -> Option<WgpuContext> {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            return None;
+        }
+        WgpuContext::new_blocking(None).ok()
+    }
+
+    #[test]
+    fn test_resolve_ids() {
+        let mut world = hecs::World::new();
+        let (a, b) = (world.spawn(()), world.spawn(()));
+        let ranges = [
+            IdRange {
+                first: 1,
+                count: 1,
+                entity: a,
+                instanced: false,
+            },
+            IdRange {
+                first: 2,
+                count: 3,
+                entity: b,
+                instanced: true,
+            },
+        ];
+        assert_eq!(resolve(&ranges, 0), None);
+        assert_eq!(resolve(&ranges, 1).unwrap().entity, a);
+        let pick = resolve(&ranges, 4).unwrap();
+        assert_eq!((pick.entity, pick.instance), (b, Some(2)));
+        assert_eq!(resolve(&ranges, 5), None);
+    }
+
+    #[test]
+    fn test_id_pass_picks_entities_and_instances() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let material = MaterialHandle(Arc::new(
+            ColorMaterial::new(&ctx, wgpu::TextureFormat::Rgba8UnormSrgb).unwrap(),
+        ));
+        let renderer = |mesh: Arc<dyn Geometry + Send + Sync>| MeshRenderer {
+            mesh: MeshHandle(mesh),
+            material: MaterialHandle(material.0.clone()),
+            visible: true,
+            cast_shadow: false,
+            receive_shadow: false,
+        };
+
+        let mut world = hecs::World::new();
+        world.spawn((
+            CameraComponent::new(Camera::new_orthographic(
+                Vec3::new(0.0, 0.0, 10.0),
+                Vec3::ZERO,
+                Vec3::Y,
+                8.0,
+                8.0,
+                0.1,
+                100.0,
+            )),
+            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0))),
+        ));
+        let cube = world.spawn((
+            renderer(Arc::new(Mesh::cube(&ctx, 2.0, [1.0; 3]))),
+            GlobalTransform(Mat4::from_translation(Vec3::new(-2.0, 2.0, 0.0))),
+            Visible,
+        ));
+        let instances = [-2.0, 2.0]
+            .map(|x| InstanceData::with_position_and_color(Vec3::new(x, 0.0, 0.0), [1.0; 4]));
+        let cloud = world.spawn((
+            renderer(Arc::new(InstancedMesh::new(
+                &ctx,
+                Mesh::cube(&ctx, 1.0, [1.0; 3]),
+                &instances,
+            ))),
+            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, -2.0, 0.0))),
+            Visible,
+        ));
+        // Line geometry is not drawn with the triangle pipeline
+        world.spawn((
+            renderer(Arc::new(Axes::new(&ctx, 3.0))),
+            GlobalTransform(Mat4::from_translation(Vec3::new(1.0, 1.0, 0.0))),
+            Visible,
+        ));
+
+        let mut ids = IdBuffer::new(&ctx, 1, 1).unwrap();
+        assert_eq!(render_id_buffer(&world, &ctx, &mut ids, (64, 64)), 2);
+        assert_eq!(ids.size(), (64, 64));
+
+        // 8 units across 64 pixels: world (x, y) is pixel (32 + 8x, 32 - 8y)
+        let pick = |x, y| ids.query_pixel(&ctx, x, y).wait(&ctx).unwrap();
+        assert_eq!(
+            pick(16, 16),
+            vec![PickId {
+                entity: cube,
+                instance: None
+            }]
+        );
+        assert_eq!(
+            pick(48, 48),
+            vec![PickId {
+                entity: cloud,
+                instance: Some(1)
+            }]
+        );
+        assert!(pick(48, 16).is_empty());
+
+        let query = ids.query_rect(&ctx, 0, 32, 64, 64);
+        while !query.poll(&ctx) {}
+        let boxed = query.wait(&ctx).unwrap();
+        assert_eq!(
+            boxed,
+            vec![
+                PickId {
+                    entity: cloud,
+                    instance: Some(0)
+                },
+                PickId {
+                    entity: cloud,
+                    instance: Some(1)
+                },
+            ]
+        );
+    }
+}
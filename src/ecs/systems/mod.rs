@@ -1,9 +1,14 @@
-//! ECS systems (transform propagation, culling, rendering).
+//! ECS systems (transform propagation, culling, rendering, picking IDs).
 
 pub mod culling;
+pub mod id_buffer;
 pub mod render;
 pub mod transform;
 
 pub use culling::culling_system;
-pub use render::render_system;
+pub use id_buffer::{render_id_buffer, IdBuffer, IdQuery, PickId};
+pub use render::{
+    render_camera, render_offscreen_cameras, render_selection_mask, render_shadow_maps,
+    render_system, RenderBuffers, RenderStats,
+};
 pub use transform::transform_system;
@@ -3,80 +3,351 @@
 //! Extracts rendering data from the ECS World and issues draw calls.
 
 use crate::context::WgpuContext;
-use crate::ecs::components::rendering::{CameraComponent, LightComponent, MeshRenderer, Visible};
+use crate::core::hot_reload::{HotShader, ShaderFile};
+use crate::core::instance::{InstanceBuffer, InstanceData};
+use crate::core::pipeline::{PipelineBuilder, Vertex};
+use crate::core::render_states::{BlendState, CullState, DepthState};
+use crate::core::render_target::RenderTarget;
+use crate::core::texture::DepthTexture;
+use crate::ecs::components::rendering::{
+    CameraComponent, CameraView, LightComponent, MeshRenderer, RenderTexture, Selected,
+    ShadowCaster, ViewportRect, Visible,
+};
 use crate::ecs::components::transform::GlobalTransform;
-use crate::renderer::light::{Light, LightType, LightUniforms};
-use crate::renderer::viewer::Camera;
+use crate::effect::OutlineMask;
+use crate::renderer::light::{GpuLight, LightType};
+use crate::renderer::lighting::SceneLighting;
+use crate::renderer::material::{CameraUniformRing, ModelUniform, ModelUniformRing};
+use crate::renderer::viewer::CameraUniform;
 use glam::Vec3;
 
-/// A lightweight light wrapper for ECS LightComponent data.
-///
-/// Implements the `Light` trait so it can be passed to material uniform updates.
-struct EcsLight {
-    light_type: LightType,
-    color: Vec3,
-    intensity: f32,
-    position_or_direction: Vec3,
+/// Collect the world-space views and viewport rectangles of all active
+/// cameras rendering to the main target (`offscreen == false`) or to their
+/// own `RenderTexture` (`offscreen == true`), in priority order.
+pub(crate) fn collect_active_cameras(
+    world: &hecs::World,
+    offscreen: bool,
+) -> Vec<(hecs::Entity, CameraView, ViewportRect)> {
+    let mut cameras: Vec<_> = world
+        .query::<(&CameraComponent, &GlobalTransform, Option<&RenderTexture>)>()
+        .iter()
+        .filter(|(_, (cam, _, texture))| cam.active && texture.is_some() == offscreen)
+        .map(|(entity, (cam, global, _))| (cam.priority, entity, cam.view(global), cam.viewport))
+        .collect();
+    cameras.sort_by_key(|(priority, entity, _, _)| (*priority, *entity));
+    cameras
+        .into_iter()
+        .map(|(_, entity, view, rect)| (entity, view, rect))
+        .collect()
 }
 
-impl Light for EcsLight {
-    fn light_type(&self) -> LightType {
-        self.light_type
-    }
+/// Collect all lights from the ECS World for the scene light buffer, marking
+/// `shadow_light` as shadowed.
+fn collect_lights(world: &hecs::World, shadow_light: Option<hecs::Entity>) -> Vec<GpuLight> {
+    let mut lights: Vec<_> = world
+        .query::<(&LightComponent, &GlobalTransform)>()
+        .iter()
+        .map(|(entity, (light, global))| {
+            (
+                entity,
+                light.gpu_light(global, Some(entity) == shadow_light),
+            )
+        })
+        .collect();
+    lights.sort_by_key(|(entity, _)| *entity);
+    lights.into_iter().map(|(_, light)| light).collect()
+}
+
+/// The directional light entity whose `ShadowCaster` shadows the scene, with
+/// its world-space direction: the first one in entity order.
+fn find_shadow_light(world: &hecs::World) -> Option<(hecs::Entity, Vec3)> {
+    world
+        .query::<(&LightComponent, &GlobalTransform)>()
+        .with::<&ShadowCaster>()
+        .iter()
+        .filter(|(_, (light, _))| light.light_type == LightType::Directional)
+        .map(|(entity, (_, global))| (entity, global.0.transform_vector3(-Vec3::Z)))
+        .min_by_key(|(entity, _)| *entity)
+}
+
+/// Groups of at least this many entities sharing a mesh and material are drawn
+/// with a single instanced call.
+pub const DEFAULT_INSTANCING_THRESHOLD: usize = 4;
+
+/// Initial capacity of the instance buffer in `RenderBuffers`.
+const INITIAL_INSTANCE_CAPACITY: u32 = 64;
+
+/// Initial capacity of the scene light buffer in `RenderBuffers`.
+const INITIAL_LIGHT_CAPACITY: usize = 16;
+
+/// Draw counters from the most recent `render_system` call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Cameras rendered.
+    pub cameras: usize,
+    /// Visible entities drawn per camera.
+    pub entities: usize,
+    /// Draw calls issued, instanced or not.
+    pub draw_calls: usize,
+    /// Draw calls that drew an instanced batch.
+    pub instanced_draws: usize,
+    /// Entities drawn through instanced batches.
+    pub instanced_entities: usize,
+    /// Pipeline changes between consecutive draws.
+    pub pipeline_switches: usize,
+}
+
+/// Single-entity and instanced pipelines, rebuilt together on shader reload.
+struct PipelinePair {
+    single: wgpu::RenderPipeline,
+    instanced: wgpu::RenderPipeline,
+}
 
-    fn uniforms(&self) -> LightUniforms {
-        let w = match self.light_type {
-            LightType::Directional | LightType::Ambient => 0.0,
-            LightType::Point | LightType::Spot => 1.0,
+/// Depth-only pipelines of the shadow caster pass.
+type ShadowPipelines = HotShader<PipelinePair>;
+
+fn shadow_pipelines(ctx: &WgpuContext) -> anyhow::Result<ShadowPipelines> {
+    let shader = ShaderFile::embedded(
+        "shadow_caster.wgsl",
+        include_str!("../../shaders/shadow_caster.wgsl"),
+    );
+    let pipeline_ctx = ctx.clone();
+    let layouts = [
+        CameraUniform::bind_group_layout(ctx),
+        ModelUniform::bind_group_layout(ctx),
+    ];
+    let watched = shader.is_watched();
+    HotShader::new(shader, move |shader| {
+        let builder = |label| {
+            layouts.iter().fold(
+                PipelineBuilder::new(&pipeline_ctx)
+                    .label(label)
+                    .shader(shader)
+                    .catch_errors(watched)
+                    .vertex_layout(Vertex::layout()),
+                |builder, layout| builder.bind_group_layout(layout),
+            )
         };
-        LightUniforms {
-            direction_or_position: [
-                self.position_or_direction.x,
-                self.position_or_direction.y,
-                self.position_or_direction.z,
-                w,
-            ],
-            color_intensity: [self.color.x, self.color.y, self.color.z, self.intensity],
-            attenuation: [1.0, 0.09, 0.032, 0.0],
-        }
+        let single = builder("shadow caster pipeline")
+            .depth(DepthState::read_write())
+            .cull(CullState::Back)
+            .build_depth_only()?;
+        let instanced = builder("shadow caster instanced pipeline")
+            .vertex_entry("vs_instanced")
+            .vertex_layout(InstanceData::layout())
+            .depth(DepthState::read_write())
+            .cull(CullState::Back)
+            .build_depth_only()?;
+        Ok(PipelinePair { single, instanced })
+    })
+}
+
+/// Pipelines of the selection mask pass, with the scene depth binding.
+struct SelectionPipelines {
+    pipelines: HotShader<PipelinePair>,
+    depth_layout: wgpu::BindGroupLayout,
+    depth_sampler: wgpu::Sampler,
+    /// Bound when no scene depth is given: 1x1 at the far plane, so every
+    /// selected fragment counts as visible.
+    far_depth: DepthTexture,
+}
+
+impl SelectionPipelines {
+    fn new(ctx: &WgpuContext) -> anyhow::Result<Self> {
+        let shader = ShaderFile::embedded(
+            "selection_mask.wgsl",
+            include_str!("../../shaders/selection_mask.wgsl"),
+        );
+        let depth_layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("selection mask depth layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                        count: None,
+                    },
+                ],
+            });
+        // Nearest, so each mask pixel compares against its own depth texel
+        let depth_sampler = ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("selection mask depth sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            compare: Some(wgpu::CompareFunction::LessEqual),
+            ..Default::default()
+        });
+
+        let pipeline_ctx = ctx.clone();
+        let layouts = [
+            CameraUniform::bind_group_layout(ctx),
+            ModelUniform::bind_group_layout(ctx),
+            depth_layout.clone(),
+        ];
+        let watched = shader.is_watched();
+        let pipelines = HotShader::new(shader, move |shader| {
+            let builder = |label| {
+                layouts.iter().fold(
+                    PipelineBuilder::new(&pipeline_ctx)
+                        .label(label)
+                        .shader(shader)
+                        .catch_errors(watched)
+                        .vertex_layout(Vertex::layout()),
+                    |builder, layout| builder.bind_group_layout(layout),
+                )
+            };
+            let single = builder("selection mask pipeline")
+                .color_format(OutlineMask::FORMAT)
+                .blend(BlendState::Additive)
+                .cull(CullState::None)
+                .build()?;
+            let instanced = builder("selection mask instanced pipeline")
+                .vertex_entry("vs_instanced")
+                .vertex_layout(InstanceData::layout())
+                .color_format(OutlineMask::FORMAT)
+                .blend(BlendState::Additive)
+                .cull(CullState::None)
+                .build()?;
+            Ok(PipelinePair { single, instanced })
+        })?;
+
+        let far_depth = DepthTexture::new(ctx, 1, 1, Some("selection mask far depth"));
+        let mut encoder = ctx.create_encoder(Some("selection mask far depth clear"));
+        // The pass only clears
+        let _ = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("selection mask far depth clear"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: far_depth.view(),
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        ctx.submit([encoder.finish()]);
+
+        Ok(Self {
+            pipelines,
+            depth_layout,
+            depth_sampler,
+            far_depth,
+        })
     }
 }
 
-/// Find the active camera entity and return a cloned Camera.
-///
-/// Returns `None` if no active camera exists.
-fn find_active_camera(world: &hecs::World) -> Option<Camera> {
-    for (_, (cam, _global)) in world.query::<(&CameraComponent, &GlobalTransform)>().iter() {
-        if cam.active {
-            return Some(cam.camera.clone());
+/// GPU buffers `render_system` reuses across frames.
+pub struct RenderBuffers {
+    cameras: CameraUniformRing,
+    models: ModelUniformRing,
+    instances: InstanceBuffer,
+    instance_data: Vec<InstanceData>,
+    /// Minimum group size drawn as one instanced call. Groups whose material
+    /// has no instanced pipeline are always drawn per entity.
+    pub instancing_threshold: usize,
+    stats: RenderStats,
+    lighting: SceneLighting,
+    shadow_pipelines: Option<ShadowPipelines>,
+    /// Created by the first `render_selection_mask` call.
+    selection_pipelines: Option<SelectionPipelines>,
+}
+
+impl RenderBuffers {
+    pub fn new(ctx: &WgpuContext) -> Self {
+        Self {
+            cameras: CameraUniformRing::new(ctx),
+            models: ModelUniformRing::new(ctx),
+            instances: InstanceBuffer::with_capacity(
+                ctx,
+                INITIAL_INSTANCE_CAPACITY,
+                Some("ecs instance buffer"),
+            ),
+            instance_data: Vec::new(),
+            instancing_threshold: DEFAULT_INSTANCING_THRESHOLD,
+            stats: RenderStats::default(),
+            lighting: SceneLighting::new(ctx, INITIAL_LIGHT_CAPACITY, None),
+            shadow_pipelines: shadow_pipelines(ctx)
+                .inspect_err(|e| tracing::warn!("Shadow caster pass unavailable: {e:#}"))
+                .ok(),
+            selection_pipelines: None,
         }
     }
-    None
-}
 
-/// Collect all lights from the ECS World.
-fn collect_lights(world: &hecs::World) -> Vec<EcsLight> {
-    let mut lights = Vec::new();
-    for (_, (light, global)) in world.query::<(&LightComponent, &GlobalTransform)>().iter() {
-        let position_or_direction = match light.light_type {
-            LightType::Directional => {
-                // Extract the forward direction (-Z axis) from the transform matrix.
-                global.0.transform_vector3(-Vec3::Z).normalize()
-            }
-            _ => {
-                // Extract position from the transform.
-                global.0.transform_point3(Vec3::ZERO)
-            }
-        };
+    /// Counters from the most recent `render_system` call.
+    pub fn stats(&self) -> RenderStats {
+        self.stats
+    }
 
-        lights.push(EcsLight {
-            light_type: light.light_type,
-            color: light.color,
-            intensity: light.intensity,
-            position_or_direction,
-        });
+    /// Rebuild the shadow caster and selection mask pipelines whose shader
+    /// files changed, when hot reload is enabled (see
+    /// [`hot_reload`](crate::core::hot_reload)). Returns whether any was
+    /// rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        let shadow = self
+            .shadow_pipelines
+            .as_ref()
+            .is_some_and(HotShader::reload_if_changed);
+        let selection = self
+            .selection_pipelines
+            .as_ref()
+            .is_some_and(|selection| selection.pipelines.reload_if_changed());
+        shadow || selection
+    }
+
+    /// Write the scene lights and shadow uniform, recreating the lighting
+    /// bindings when the light buffer is full or the shadow map changed.
+    fn upload_lighting(
+        &mut self,
+        ctx: &WgpuContext,
+        lights: &[GpuLight],
+        shadow: Option<&ShadowCaster>,
+    ) {
+        let shadow_map = shadow.map(|caster| caster.shadow_map().depth_view());
+        if lights.len() > self.lighting.capacity() || self.lighting.shadow_map() != shadow_map {
+            let capacity = lights
+                .len()
+                .next_power_of_two()
+                .max(self.lighting.capacity());
+            self.lighting = SceneLighting::new(ctx, capacity, shadow_map);
+        }
+        self.lighting.write_lights(ctx, lights);
+        if let Some(caster) = shadow {
+            self.lighting
+                .write_shadow(ctx, &caster.shadow_map().uniform());
+        }
+    }
+
+    /// Write the instance data collected this frame, growing the buffer if needed.
+    fn upload_instances(&mut self, ctx: &WgpuContext) {
+        let count = self.instance_data.len() as u32;
+        if count > self.instances.capacity() {
+            self.instances = InstanceBuffer::with_capacity(
+                ctx,
+                count.next_power_of_two(),
+                Some("ecs instance buffer"),
+            );
+        }
+        if let Err(e) = self.instances.update(ctx, &self.instance_data) {
+            tracing::warn!("Failed to upload instance data: {e}");
+        }
     }
-    lights
 }
 
 /// Pre-collected draw command with owned Arc handles.
@@ -84,34 +355,428 @@ struct DrawCommand {
     material: std::sync::Arc<dyn crate::ecs::bridge::MaterialResource>,
     mesh: std::sync::Arc<dyn crate::renderer::geometry::Geometry + Send + Sync>,
     global_transform: glam::Mat4,
+    receive_shadow: bool,
+}
+
+impl DrawCommand {
+    fn material_key(&self) -> *const () {
+        std::sync::Arc::as_ptr(&self.material) as *const ()
+    }
+
+    fn mesh_key(&self) -> *const () {
+        std::sync::Arc::as_ptr(&self.mesh) as *const ()
+    }
+
+    /// Sort and batching key: entities with equal keys share mesh, material
+    /// and shadow receiving.
+    fn key(&self) -> (*const (), *const (), bool) {
+        (self.material_key(), self.mesh_key(), self.receive_shadow)
+    }
+}
+
+/// How a run of draw commands is submitted.
+enum BatchKind {
+    /// One entity, with its model uniform at this dynamic offset.
+    Single(u32),
+    /// Every entity in the batch, as this range of the instance buffer.
+    Instanced(std::ops::Range<u32>),
+}
+
+struct Batch {
+    /// Index of the first draw command in the batch.
+    first: usize,
+    kind: BatchKind,
 }
 
 /// ECS render system.
 ///
-/// Queries the World for the active camera, lights, and visible MeshRenderer
-/// entities, updates material uniforms, then issues draw calls.
+/// Queries the World for the active cameras, lights, and visible MeshRenderer
+/// entities, updates material uniforms, then issues draw calls for each
+/// camera into its `viewport` rectangle of a target of `target_size` pixels.
+/// Cameras render in `priority` order; those with a `RenderTexture` are
+/// skipped and drawn by [`render_offscreen_cameras`] instead.
 ///
 /// # Rendering approach
 ///
-/// Material uniform updates (camera, model, lights) are performed first using
-/// the collected data. Then draw calls are issued using the Arc-shared GPU
-/// resources, which remain valid as long as the Arcs are alive.
+/// Draws are sorted by material and mesh so pipeline and bind group changes
+/// only happen between groups. Shared material uniforms (lights, parameters)
+/// are updated once per material. Groups of at least
+/// `RenderBuffers::instancing_threshold` entities whose material has an
+/// instanced pipeline are drawn with one instanced call; other entities push
+/// their model matrix into a uniform ring and are bound at group 1 with their
+/// own dynamic offset. Each camera's uniform is bound at group 0 from a
+/// second ring, so the same batches are replayed for every camera. Draw calls
+/// are issued using the Arc-shared GPU resources, which remain valid as long
+/// as the Arcs are alive.
 pub fn render_system(
     world: &hecs::World,
     ctx: &WgpuContext,
+    buffers: &mut RenderBuffers,
+    target_size: (u32, u32),
     render_pass: &mut wgpu::RenderPass<'_>,
 ) {
-    // 1. Find active camera.
-    let camera = match find_active_camera(world) {
-        Some(cam) => cam,
-        None => return,
+    let cameras: Vec<_> = collect_active_cameras(world, false)
+        .into_iter()
+        .map(|(_, view, rect)| (view, rect))
+        .collect();
+    render_views(world, ctx, buffers, &cameras, target_size, render_pass);
+}
+
+/// Render every active camera that has a `RenderTexture` into its texture,
+/// in `priority` order.
+///
+/// Each camera is encoded and submitted on its own, since all cameras share
+/// the uniform rings in `buffers`. Call this before rendering the main
+/// target so materials sampling the textures see this frame's images.
+pub fn render_offscreen_cameras(
+    world: &hecs::World,
+    ctx: &WgpuContext,
+    buffers: &mut RenderBuffers,
+) {
+    for (entity, view, rect) in collect_active_cameras(world, true) {
+        let Ok(texture) = world.get::<&RenderTexture>(entity) else {
+            continue;
+        };
+        let (width, height) = texture.size();
+        let target = RenderTarget::new(
+            ctx,
+            texture.color().view(),
+            Some(texture.depth().view()),
+            width,
+            height,
+            texture.color().format(),
+        );
+        let mut encoder = ctx.create_encoder(Some("offscreen camera"));
+        {
+            let mut pass = target.begin_render_pass(&mut encoder, texture.clear);
+            render_views(
+                world,
+                ctx,
+                buffers,
+                &[(view, rect)],
+                (width, height),
+                &mut pass,
+            );
+        }
+        ctx.submit([encoder.finish()]);
+    }
+}
+
+/// Render the shadow map of the scene's `ShadowCaster` light.
+///
+/// Draws every visible `MeshRenderer` with `cast_shadow` and
+/// [triangle geometry](crate::renderer::geometry::Geometry::is_triangle_mesh)
+/// from the first directional `LightComponent` that has a `ShadowCaster`,
+/// centering the light-space box `half_extent` in front of the first active
+/// main camera (or on the origin without one).
+/// Large groups sharing a mesh are instanced as in `render_system`. The pass
+/// is submitted on its own, so call this before the camera passes that
+/// sample the map. Returns the number of casters drawn.
+pub fn render_shadow_maps(
+    world: &hecs::World,
+    ctx: &WgpuContext,
+    buffers: &mut RenderBuffers,
+) -> usize {
+    if buffers.shadow_pipelines.is_none() {
+        return 0;
+    }
+    let Some((light, direction)) = find_shadow_light(world) else {
+        return 0;
+    };
+    let Ok(mut caster) = world.get::<&mut ShadowCaster>(light) else {
+        return 0;
     };
+    let focus = collect_active_cameras(world, false)
+        .first()
+        .map_or(Vec3::ZERO, |(_, view, _)| {
+            let forward = view.view.inverse().transform_vector3(-Vec3::Z);
+            view.position + forward * caster.half_extent
+        });
+    let eye = caster.update(direction, focus);
 
-    // 2. Collect lights.
-    let ecs_lights = collect_lights(world);
-    let light_refs: Vec<&dyn Light> = ecs_lights.iter().map(|l| l as &dyn Light).collect();
+    let mut casters: Vec<DrawCommand> = world
+        .query::<(&MeshRenderer, &GlobalTransform)>()
+        .iter()
+        .filter(|(_, (renderer, _))| {
+            renderer.visible && renderer.cast_shadow && renderer.mesh.0.is_triangle_mesh()
+        })
+        .map(|(_, (renderer, global))| DrawCommand {
+            material: renderer.material.0.clone(),
+            mesh: renderer.mesh.0.clone(),
+            global_transform: global.0,
+            receive_shadow: renderer.receive_shadow,
+        })
+        .collect();
+    casters.sort_by_key(DrawCommand::mesh_key);
 
-    // 3. Collect drawable entities with Arc clones.
+    buffers.cameras.clear();
+    let light_offset = buffers.cameras.push(&CameraUniform {
+        view_proj: caster.light_matrix().to_cols_array_2d(),
+        eye: [eye.x, eye.y, eye.z, 1.0],
+    });
+    buffers.models.clear();
+    buffers.instance_data.clear();
+    let mut batches = Vec::new();
+    let mut first = 0;
+    for group in casters.chunk_by(|a, b| a.mesh_key() == b.mesh_key()) {
+        if group.len() >= buffers.instancing_threshold {
+            let start = buffers.instance_data.len() as u32;
+            buffers.instance_data.extend(
+                group.iter().map(|cmd| {
+                    InstanceData::with_transform_and_color(cmd.global_transform, [1.0; 4])
+                }),
+            );
+            let end = buffers.instance_data.len() as u32;
+            batches.push(Batch {
+                first,
+                kind: BatchKind::Instanced(start..end),
+            });
+        } else {
+            for (i, cmd) in group.iter().enumerate() {
+                batches.push(Batch {
+                    first: first + i,
+                    kind: BatchKind::Single(buffers.models.push_matrix(cmd.global_transform)),
+                });
+            }
+        }
+        first += group.len();
+    }
+    buffers.cameras.upload(ctx);
+    buffers.models.upload(ctx);
+    buffers.upload_instances(ctx);
+    let Some(pipelines) = &buffers.shadow_pipelines else {
+        return 0;
+    };
+    let pipelines = pipelines.get();
+
+    let mut encoder = ctx.create_encoder(Some("shadow caster pass"));
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("shadow caster pass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: caster.shadow_map().depth_view(),
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        pass.set_bind_group(0, buffers.cameras.bind_group(), &[light_offset]);
+        for batch in &batches {
+            let cmd = &casters[batch.first];
+            let (pipeline, offset, instances) = match &batch.kind {
+                BatchKind::Single(offset) => (&pipelines.single, *offset, 0..1),
+                BatchKind::Instanced(range) => (&pipelines.instanced, 0, range.clone()),
+            };
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(1, buffers.models.bind_group(), &[offset]);
+            pass.set_vertex_buffer(0, cmd.mesh.vertex_buffer().slice());
+            if let BatchKind::Instanced(_) = batch.kind {
+                pass.set_vertex_buffer(1, buffers.instances.slice());
+            }
+            if let Some(index_buffer) = cmd.mesh.index_buffer() {
+                pass.set_index_buffer(index_buffer.slice(), wgpu::IndexFormat::Uint32);
+                pass.draw_indexed(0..cmd.mesh.draw_count(), 0, instances);
+            } else {
+                pass.draw(0..cmd.mesh.draw_count(), instances);
+            }
+        }
+    }
+    ctx.submit([encoder.finish()]);
+    casters.len()
+}
+
+/// Render the selection mask of an [`OutlineEffect`](crate::effect::OutlineEffect)
+/// from every visible `MeshRenderer` entity marked `Selected` with
+/// [triangle geometry](crate::renderer::geometry::Geometry::is_triangle_mesh),
+/// as seen by the active main cameras in their viewports of a target of
+/// `target_size` pixels.
+///
+/// Fragments behind `scene_depth`, the depth buffer of the main pass, are
+/// marked hidden for the effect's x-ray outline; without it all are visible.
+/// Resizes `mask` to `target_size`. The pass is submitted on its own, after
+/// the main pass. Returns the number of entities drawn.
+pub fn render_selection_mask(
+    world: &hecs::World,
+    ctx: &WgpuContext,
+    buffers: &mut RenderBuffers,
+    mask: &OutlineMask,
+    scene_depth: Option<&wgpu::TextureView>,
+    target_size: (u32, u32),
+) -> usize {
+    if buffers.selection_pipelines.is_none() {
+        match SelectionPipelines::new(ctx) {
+            Ok(pipelines) => buffers.selection_pipelines = Some(pipelines),
+            Err(e) => {
+                tracing::warn!("Selection mask pass unavailable: {e:#}");
+                return 0;
+            }
+        }
+    }
+    mask.resize(ctx, target_size.0, target_size.1);
+    let cameras = collect_active_cameras(world, false);
+
+    let mut selected: Vec<DrawCommand> = world
+        .query::<(&MeshRenderer, &GlobalTransform)>()
+        .with::<(&Visible, &Selected)>()
+        .iter()
+        .filter(|(_, (renderer, _))| renderer.visible && renderer.mesh.0.is_triangle_mesh())
+        .map(|(_, (renderer, global))| DrawCommand {
+            material: renderer.material.0.clone(),
+            mesh: renderer.mesh.0.clone(),
+            global_transform: global.0,
+            receive_shadow: renderer.receive_shadow,
+        })
+        .collect();
+    selected.sort_by_key(DrawCommand::mesh_key);
+
+    buffers.cameras.clear();
+    let camera_offsets: Vec<u32> = cameras
+        .iter()
+        .map(|(_, view, _)| buffers.cameras.push_viewer(view))
+        .collect();
+    buffers.models.clear();
+    let model_offsets: Vec<u32> = selected
+        .iter()
+        .map(|cmd| buffers.models.push_matrix(cmd.global_transform))
+        .collect();
+    buffers.cameras.upload(ctx);
+    buffers.models.upload(ctx);
+    let Some(selection) = &buffers.selection_pipelines else {
+        return 0;
+    };
+    let pipelines = selection.pipelines.get();
+
+    let depth_bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("selection mask depth"),
+        layout: &selection.depth_layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(
+                    scene_depth.unwrap_or(selection.far_depth.view()),
+                ),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(&selection.depth_sampler),
+            },
+        ],
+    });
+    let mask_view = mask.view();
+    let mut encoder = ctx.create_encoder(Some("selection mask pass"));
+    {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("selection mask pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &mask_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        pass.set_bind_group(2, &depth_bind_group, &[]);
+        let (width, height) = mask.size();
+        for ((_, _, rect), camera_offset) in cameras.iter().zip(camera_offsets) {
+            let [x, y, w, h] = rect.to_pixels(width, height);
+            if w < 1.0 || h < 1.0 {
+                continue;
+            }
+            pass.set_viewport(x, y, w, h, 0.0, 1.0);
+            pass.set_bind_group(0, buffers.cameras.bind_group(), &[camera_offset]);
+            for (cmd, &model_offset) in selected.iter().zip(&model_offsets) {
+                let instances = match cmd.mesh.instances() {
+                    Some(instances) => {
+                        pass.set_pipeline(&pipelines.instanced);
+                        pass.set_vertex_buffer(1, instances.slice());
+                        0..instances.count()
+                    }
+                    None => {
+                        pass.set_pipeline(&pipelines.single);
+                        0..1
+                    }
+                };
+                pass.set_bind_group(1, buffers.models.bind_group(), &[model_offset]);
+                pass.set_vertex_buffer(0, cmd.mesh.vertex_buffer().slice());
+                if let Some(index_buffer) = cmd.mesh.index_buffer() {
+                    pass.set_index_buffer(index_buffer.slice(), wgpu::IndexFormat::Uint32);
+                    pass.draw_indexed(0..cmd.mesh.draw_count(), 0, instances);
+                } else {
+                    pass.draw(0..cmd.mesh.draw_count(), instances);
+                }
+            }
+        }
+    }
+    ctx.submit([encoder.finish()]);
+    selected.len()
+}
+
+/// Render the scene from a single camera entity, active or not, into its
+/// `viewport` rectangle of `render_pass`.
+///
+/// Use this to draw a camera into its own `RenderTarget`, e.g. a robot-eye
+/// view rendered to a texture. Does nothing if `camera` has no
+/// `CameraComponent` and `GlobalTransform`.
+pub fn render_camera(
+    world: &hecs::World,
+    ctx: &WgpuContext,
+    buffers: &mut RenderBuffers,
+    camera: hecs::Entity,
+    target_size: (u32, u32),
+    render_pass: &mut wgpu::RenderPass<'_>,
+) {
+    let view = match world.query_one::<(&CameraComponent, &GlobalTransform)>(camera) {
+        Ok(mut query) => query
+            .get()
+            .map(|(cam, global)| (cam.view(global), cam.viewport)),
+        Err(_) => None,
+    };
+    let cameras: Vec<_> = view.into_iter().collect();
+    render_views(world, ctx, buffers, &cameras, target_size, render_pass);
+}
+
+fn render_views(
+    world: &hecs::World,
+    ctx: &WgpuContext,
+    buffers: &mut RenderBuffers,
+    cameras: &[(CameraView, ViewportRect)],
+    target_size: (u32, u32),
+    render_pass: &mut wgpu::RenderPass<'_>,
+) {
+    buffers.stats = RenderStats::default();
+    let Some((first_view, _)) = cameras.first() else {
+        return;
+    };
+
+    // 1. Upload the scene lights and the shadow map rendered by `render_shadow_maps`.
+    let shadow_light = find_shadow_light(world).and_then(|(entity, _)| {
+        world
+            .get::<&ShadowCaster>(entity)
+            .ok()
+            .filter(|caster| caster.is_rendered())
+            .map(|caster| (entity, caster))
+    });
+    let lights = collect_lights(world, shadow_light.as_ref().map(|(entity, _)| *entity));
+    buffers.upload_lighting(
+        ctx,
+        &lights,
+        shadow_light.as_ref().map(|(_, caster)| &**caster),
+    );
+    drop(shadow_light);
+
+    // 2. Collect drawable entities with Arc clones, grouped by material and mesh.
     let mut draw_commands: Vec<DrawCommand> = Vec::new();
     {
         let mut query = world
@@ -125,33 +790,676 @@ pub fn render_system(
                 material: renderer.material.0.clone(),
                 mesh: renderer.mesh.0.clone(),
                 global_transform: global.0,
+                receive_shadow: renderer.receive_shadow,
             });
         }
     }
+    draw_commands.sort_by_key(DrawCommand::key);
 
-    // 4. Update uniforms and issue draw calls.
-    for cmd in &draw_commands {
-        // Update material uniforms with camera, model matrix, and lights.
-        cmd.material
-            .update_uniforms(ctx, &camera, cmd.global_transform, &light_refs);
+    // 3. Update shared uniforms once per material and build batches.
+    buffers.models.clear();
+    buffers.instance_data.clear();
+    let mut batches = Vec::new();
+    let mut first = 0;
+    for group in draw_commands.chunk_by(|a, b| a.key() == b.key()) {
+        let material = &group[0].material;
+        if first == 0 || draw_commands[first - 1].material_key() != group[0].material_key() {
+            material.update_shared_uniforms(ctx, first_view, &[]);
+        }
 
-        // Set pipeline and bind groups.
-        render_pass.set_pipeline(cmd.material.pipeline());
-        render_pass.set_bind_group(0, cmd.material.camera_bind_group(), &[]);
-        render_pass.set_bind_group(1, cmd.material.model_bind_group(), &[]);
+        if group.len() >= buffers.instancing_threshold && material.instanced_pipeline().is_some() {
+            let start = buffers.instance_data.len() as u32;
+            buffers.instance_data.extend(
+                group.iter().map(|cmd| {
+                    InstanceData::with_transform_and_color(cmd.global_transform, [1.0; 4])
+                }),
+            );
+            let end = buffers.instance_data.len() as u32;
+            batches.push(Batch {
+                first,
+                kind: BatchKind::Instanced(start..end),
+            });
+        } else {
+            for (i, cmd) in group.iter().enumerate() {
+                batches.push(Batch {
+                    first: first + i,
+                    kind: BatchKind::Single(buffers.models.push_matrix(cmd.global_transform)),
+                });
+            }
+        }
+        first += group.len();
+    }
+    buffers.cameras.clear();
+    let camera_offsets: Vec<u32> = cameras
+        .iter()
+        .map(|(view, _)| buffers.cameras.push_viewer(view))
+        .collect();
+    buffers.cameras.upload(ctx);
+    buffers.models.upload(ctx);
+    buffers.upload_instances(ctx);
 
-        // Bind any additional material-specific bind groups (e.g. group 2+)
-        for (group, bind_group) in cmd.material.extra_bind_groups() {
-            render_pass.set_bind_group(group, bind_group, &[]);
+    // 4. Issue draw calls per camera, rebinding state only when it changes.
+    for ((_, rect), camera_offset) in cameras.iter().zip(camera_offsets) {
+        let [x, y, width, height] = rect.to_pixels(target_size.0, target_size.1);
+        if width < 1.0 || height < 1.0 {
+            continue;
         }
+        render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
+        render_pass.set_bind_group(0, buffers.cameras.bind_group(), &[camera_offset]);
+        buffers.stats.cameras += 1;
 
-        // Draw the mesh manually (avoiding the Geometry::draw lifetime issue).
-        render_pass.set_vertex_buffer(0, cmd.mesh.vertex_buffer().slice());
-        if let Some(index_buffer) = cmd.mesh.index_buffer() {
-            render_pass.set_index_buffer(index_buffer.slice(), wgpu::IndexFormat::Uint32);
-            render_pass.draw_indexed(0..cmd.mesh.draw_count(), 0, 0..1);
-        } else {
-            render_pass.draw(0..cmd.mesh.draw_count(), 0..1);
+        let mut bound_material = None;
+        let mut bound_pipeline = None;
+        let mut bound_lighting = None;
+        for batch in &batches {
+            let cmd = &draw_commands[batch.first];
+            let material = &cmd.material;
+
+            let (pipeline, offset, instances) = match &batch.kind {
+                BatchKind::Single(offset) => (material.pipeline(), *offset, 0..1),
+                BatchKind::Instanced(range) => match material.instanced_pipeline() {
+                    Some(pipeline) => (pipeline, 0, range.clone()),
+                    None => continue,
+                },
+            };
+
+            if bound_pipeline.as_ref() != Some(&pipeline) {
+                render_pass.set_pipeline(&pipeline);
+                bound_pipeline = Some(pipeline);
+                buffers.stats.pipeline_switches += 1;
+            }
+            if bound_material != Some(cmd.material_key()) {
+                // Bind any additional material-specific bind groups (e.g. group 2+)
+                for (group, bind_group) in material.extra_bind_groups() {
+                    render_pass.set_bind_group(group, bind_group, &[]);
+                }
+                bound_material = Some(cmd.material_key());
+                bound_lighting = None;
+            }
+            // Replace the material's own lighting with the scene's.
+            if let Some(group) = material.lighting_group() {
+                if bound_lighting != Some(cmd.receive_shadow) {
+                    let lighting = buffers.lighting.bind_group(cmd.receive_shadow);
+                    render_pass.set_bind_group(group, lighting, &[]);
+                    bound_lighting = Some(cmd.receive_shadow);
+                }
+            }
+            // The instanced pipeline ignores group 1, but its layout still requires it.
+            render_pass.set_bind_group(1, buffers.models.bind_group(), &[offset]);
+
+            // Draw the mesh manually (avoiding the Geometry::draw lifetime issue).
+            render_pass.set_vertex_buffer(0, cmd.mesh.vertex_buffer().slice());
+            if let BatchKind::Instanced(_) = batch.kind {
+                render_pass.set_vertex_buffer(1, buffers.instances.slice());
+                buffers.stats.instanced_draws += 1;
+                buffers.stats.instanced_entities += instances.len();
+            }
+            if let Some(index_buffer) = cmd.mesh.index_buffer() {
+                render_pass.set_index_buffer(index_buffer.slice(), wgpu::IndexFormat::Uint32);
+                render_pass.draw_indexed(0..cmd.mesh.draw_count(), 0, instances);
+            } else {
+                render_pass.draw(0..cmd.mesh.draw_count(), instances);
+            }
+            buffers.stats.draw_calls += 1;
+        }
+    }
+    buffers.stats.entities = draw_commands.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::core::render_states::ClearState;
+    use crate::core::render_target::RenderTarget;
+    use crate::core::texture::{DepthTexture, Texture2D};
+    use crate::ecs::components::rendering::{MaterialHandle, MeshHandle, ViewportRect};
+    use crate::renderer::geometry::{Axes, Mesh};
+    use crate::renderer::light::Attenuation;
+    use crate::renderer::material::ColorMaterial;
+    use crate::renderer::viewer::Camera;
+
+    const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+    fn try_create_ctx() -> Option<WgpuContext> {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            return None;
+        }
+        WgpuContext::new_blocking(None).ok()
+    }
+
+    fn spawn_renderer(
+        world: &mut hecs::World,
+        mesh: &MeshHandle,
+        material: &MaterialHandle,
+        x: f32,
+    ) {
+        let transform = glam::Mat4::from_translation(Vec3::new(x, 0.0, 0.0));
+        world.spawn((
+            GlobalTransform(transform),
+            MeshRenderer {
+                mesh: MeshHandle(mesh.0.clone()),
+                material: MaterialHandle(material.0.clone()),
+                visible: true,
+                cast_shadow: false,
+                receive_shadow: false,
+            },
+            Visible,
+        ));
+    }
+
+    fn render(ctx: &WgpuContext, world: &hecs::World, buffers: &mut RenderBuffers) {
+        let color = Texture2D::new(
+            ctx,
+            64,
+            64,
+            FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+            Some("test color"),
+        );
+        let depth = DepthTexture::new(ctx, 64, 64, Some("test depth"));
+        let target = RenderTarget::new(ctx, color.view(), Some(depth.view()), 64, 64, FORMAT);
+        let mut encoder = ctx.create_encoder(Some("render system test"));
+        {
+            let clear = ClearState::color_and_depth([0.0, 0.0, 0.0, 1.0], 1.0);
+            let mut pass = target.begin_render_pass(&mut encoder, clear);
+            render_system(world, ctx, buffers, (64, 64), &mut pass);
+        }
+        ctx.submit([encoder.finish()]);
+    }
+
+    #[test]
+    fn test_shared_mesh_and_material_are_instanced() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let mut world = hecs::World::new();
+        world.spawn((
+            GlobalTransform::default(),
+            CameraComponent::new(Camera::new_perspective(
+                Vec3::new(0.0, 2.0, 20.0),
+                Vec3::ZERO,
+                Vec3::Y,
+                45.0,
+                1.0,
+                0.1,
+                100.0,
+            )),
+        ));
+
+        let material = MaterialHandle(Arc::new(ColorMaterial::new(&ctx, FORMAT).unwrap()));
+        let cube = MeshHandle(Arc::new(Mesh::cube(&ctx, 1.0, [1.0, 0.0, 0.0])));
+        let sphere = MeshHandle(Arc::new(Mesh::sphere(&ctx, 0.5, 8, 8, [0.0, 1.0, 0.0])));
+        for i in 0..6 {
+            spawn_renderer(&mut world, &cube, &material, i as f32 * 2.0 - 5.0);
+        }
+        spawn_renderer(&mut world, &sphere, &material, 0.0);
+        spawn_renderer(&mut world, &sphere, &material, 3.0);
+
+        let mut buffers = RenderBuffers::new(&ctx);
+        render(&ctx, &world, &mut buffers);
+        let stats = buffers.stats();
+        assert_eq!(stats.entities, 8);
+        assert_eq!(stats.instanced_draws, 1);
+        assert_eq!(stats.instanced_entities, 6);
+        assert_eq!(stats.draw_calls, 3);
+        assert_eq!(stats.pipeline_switches, 2);
+
+        // Disabling instancing draws every entity on its own.
+        buffers.instancing_threshold = usize::MAX;
+        render(&ctx, &world, &mut buffers);
+        let stats = buffers.stats();
+        assert_eq!(stats.instanced_draws, 0);
+        assert_eq!(stats.draw_calls, 8);
+        assert_eq!(stats.pipeline_switches, 1);
+    }
+
+    #[test]
+    fn test_split_screen_cameras_replay_batches() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let mut world = hecs::World::new();
+        let camera = Camera::new_perspective(
+            Vec3::new(0.0, 0.0, 10.0),
+            Vec3::ZERO,
+            Vec3::Y,
+            45.0,
+            0.5,
+            0.1,
+            100.0,
+        );
+        let left = ViewportRect::new(0.0, 0.0, 0.5, 1.0);
+        let right = ViewportRect::new(0.5, 0.0, 0.5, 1.0);
+        world.spawn((
+            GlobalTransform::default(),
+            CameraComponent::new(camera.clone()).with_viewport(left),
+        ));
+        let side = world.spawn((
+            GlobalTransform(glam::Mat4::from_rotation_y(std::f32::consts::FRAC_PI_2)),
+            CameraComponent::new(camera).with_viewport(right),
+        ));
+
+        let material = MaterialHandle(Arc::new(ColorMaterial::new(&ctx, FORMAT).unwrap()));
+        let cube = MeshHandle(Arc::new(Mesh::cube(&ctx, 1.0, [1.0, 0.0, 0.0])));
+        spawn_renderer(&mut world, &cube, &material, 0.0);
+        spawn_renderer(&mut world, &cube, &material, 2.0);
+
+        let mut buffers = RenderBuffers::new(&ctx);
+        render(&ctx, &world, &mut buffers);
+        let stats = buffers.stats();
+        assert_eq!(stats.cameras, 2);
+        assert_eq!(stats.entities, 2);
+        assert_eq!(stats.draw_calls, 4);
+
+        // Inactive cameras are skipped by render_system.
+        world.get::<&mut CameraComponent>(side).unwrap().active = false;
+        render(&ctx, &world, &mut buffers);
+        assert_eq!(buffers.stats().cameras, 1);
+    }
+
+    #[test]
+    fn test_cameras_sorted_by_priority() {
+        let mut world = hecs::World::new();
+        let camera = Camera::new_perspective(Vec3::Z, Vec3::ZERO, Vec3::Y, 45.0, 1.0, 0.1, 10.0);
+        let overlay = world.spawn((
+            GlobalTransform::default(),
+            CameraComponent::new(camera.clone()).with_priority(1),
+        ));
+        let main = world.spawn((GlobalTransform::default(), CameraComponent::new(camera)));
+
+        let order: Vec<_> = collect_active_cameras(&world, false)
+            .into_iter()
+            .map(|(entity, _, _)| entity)
+            .collect();
+        assert_eq!(order, vec![main, overlay]);
+        assert!(collect_active_cameras(&world, true).is_empty());
+    }
+
+    #[test]
+    fn test_offscreen_camera_renders_into_texture() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut world = hecs::World::new();
+        let camera = Camera::new_perspective(
+            Vec3::new(0.0, 0.0, 3.0),
+            Vec3::ZERO,
+            Vec3::Y,
+            45.0,
+            1.0,
+            0.1,
+            100.0,
+        );
+        let clear = ClearState::color_and_depth([0.0, 0.0, 1.0, 1.0], 1.0);
+        world.spawn((
+            GlobalTransform::default(),
+            CameraComponent::new(camera),
+            RenderTexture::new(&ctx, 32, 32, format).with_clear(clear),
+        ));
+        let material = MaterialHandle(Arc::new(ColorMaterial::new(&ctx, format).unwrap()));
+        let cube = MeshHandle(Arc::new(Mesh::cube(&ctx, 1.0, [1.0, 0.0, 0.0])));
+        spawn_renderer(&mut world, &cube, &material, 0.0);
+
+        // The surface pass ignores cameras with a render texture.
+        let mut buffers = RenderBuffers::new(&ctx);
+        render(&ctx, &world, &mut buffers);
+        assert_eq!(buffers.stats().cameras, 0);
+
+        render_offscreen_cameras(&world, &ctx, &mut buffers);
+        assert_eq!(buffers.stats().cameras, 1);
+
+        let mut query = world.query::<&RenderTexture>();
+        let (_, texture) = query.iter().next().unwrap();
+        let pixels = texture.color().read_pixels(&ctx).unwrap();
+        assert_eq!(pixels.len(), 32 * 32 * 4);
+        let pixel = |x: usize, y: usize| &pixels[(y * 32 + x) * 4..(y * 32 + x) * 4 + 4];
+        let center = pixel(16, 16);
+        assert!(
+            center[0] > 50 && center[1] < 10 && center[2] < 10,
+            "center = {center:?}"
+        );
+        assert_eq!(pixel(0, 0), &[0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_shadow_caster_darkens_receivers() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut world = hecs::World::new();
+        let camera = Camera::new_perspective(
+            Vec3::new(0.0, 10.0, 10.0),
+            Vec3::ZERO,
+            Vec3::Y,
+            45.0,
+            1.0,
+            0.1,
+            100.0,
+        );
+        world.spawn((
+            GlobalTransform::default(),
+            CameraComponent::new(camera.clone()),
+        ));
+        world.spawn((
+            GlobalTransform::default(),
+            CameraComponent::new(camera),
+            RenderTexture::new(&ctx, 32, 32, format),
+        ));
+        // Light shining straight down.
+        world.spawn((
+            GlobalTransform(glam::Mat4::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+            LightComponent::directional(Vec3::ONE, 1.0),
+            ShadowCaster::new(
+                &ctx,
+                crate::renderer::shadow::ShadowConfig {
+                    resolution: 256,
+                    ..Default::default()
+                },
+            )
+            .with_half_extent(8.0),
+        ));
+
+        let material = MaterialHandle(Arc::new(ColorMaterial::new(&ctx, format).unwrap()));
+        let white = MeshHandle(Arc::new(Mesh::cube(&ctx, 1.0, [1.0, 1.0, 1.0])));
+        world.spawn((
+            GlobalTransform(glam::Mat4::from_scale(Vec3::new(10.0, 0.1, 10.0))),
+            MeshRenderer {
+                mesh: MeshHandle(white.0.clone()),
+                material: MaterialHandle(material.0.clone()),
+                visible: true,
+                cast_shadow: false,
+                receive_shadow: true,
+            },
+            Visible,
+        ));
+        let blocker = world.spawn((
+            GlobalTransform(glam::Mat4::from_translation(Vec3::new(0.0, 2.0, 0.0))),
+            MeshRenderer {
+                mesh: MeshHandle(white.0.clone()),
+                material: MaterialHandle(material.0.clone()),
+                visible: true,
+                cast_shadow: true,
+                receive_shadow: false,
+            },
+            Visible,
+        ));
+
+        let mut buffers = RenderBuffers::new(&ctx);
+        let mut center = |world: &hecs::World| {
+            let casters = render_shadow_maps(world, &ctx, &mut buffers);
+            render_offscreen_cameras(world, &ctx, &mut buffers);
+            let mut query = world.query::<&RenderTexture>();
+            let (_, texture) = query.iter().next().unwrap();
+            let pixels = texture.color().read_pixels(&ctx).unwrap();
+            let i = (16 * 32 + 16) * 4;
+            (casters, u32::from(pixels[i]))
+        };
+
+        let (casters, shadowed) = center(&world);
+        assert_eq!(casters, 1);
+        world.get::<&mut MeshRenderer>(blocker).unwrap().cast_shadow = false;
+        let (casters, lit) = center(&world);
+        assert_eq!(casters, 0);
+        assert!(shadowed + 50 < lit, "shadowed = {shadowed}, lit = {lit}");
+
+        // Line geometry does not cast shadows
+        world.spawn((
+            GlobalTransform::default(),
+            MeshRenderer {
+                mesh: MeshHandle(Arc::new(Axes::new(&ctx, 1.0))),
+                material: MaterialHandle(material.0.clone()),
+                visible: true,
+                cast_shadow: true,
+                receive_shadow: false,
+            },
+            Visible,
+        ));
+        assert_eq!(render_shadow_maps(&world, &ctx, &mut buffers), 0);
+    }
+
+    #[test]
+    fn test_point_light_range_limits_lighting() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut world = hecs::World::new();
+        let camera = Camera::new_perspective(
+            Vec3::new(0.0, 0.0, 3.0),
+            Vec3::ZERO,
+            Vec3::Y,
+            45.0,
+            1.0,
+            0.1,
+            100.0,
+        );
+        world.spawn((
+            GlobalTransform::default(),
+            CameraComponent::new(camera),
+            RenderTexture::new(&ctx, 32, 32, format),
+        ));
+        world.spawn((
+            GlobalTransform::default(),
+            LightComponent::ambient(Vec3::ONE, 0.1),
+        ));
+        let lamp = world.spawn((
+            GlobalTransform(glam::Mat4::from_translation(Vec3::new(0.0, 0.0, 3.0))),
+            LightComponent::point(Vec3::ONE, 1.0, 2.0).with_attenuation(Attenuation::none()),
+        ));
+        let material = MaterialHandle(Arc::new(ColorMaterial::new(&ctx, format).unwrap()));
+        let cube = MeshHandle(Arc::new(Mesh::cube(&ctx, 1.0, [1.0, 1.0, 1.0])));
+        spawn_renderer(&mut world, &cube, &material, 0.0);
+
+        let mut buffers = RenderBuffers::new(&ctx);
+        let mut center = |world: &hecs::World| {
+            render_offscreen_cameras(world, &ctx, &mut buffers);
+            let mut query = world.query::<&RenderTexture>();
+            let (_, texture) = query.iter().next().unwrap();
+            let pixels = texture.color().read_pixels(&ctx).unwrap();
+            u32::from(pixels[(16 * 32 + 16) * 4])
+        };
+
+        // The cube face is 2.5 units from the lamp: outside a range of 2.
+        let ambient_only = center(&world);
+        world.get::<&mut LightComponent>(lamp).unwrap().range = 10.0;
+        let lit = center(&world);
+        assert!(ambient_only < 40, "ambient_only = {ambient_only}");
+        assert!(lit > ambient_only + 100, "lit = {lit}");
+    }
+
+    #[test]
+    fn test_selection_outline_with_x_ray() {
+        use crate::effect::{Effect, OutlineEffect};
+
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let mut world = hecs::World::new();
+        world.spawn((
+            GlobalTransform(glam::Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0))),
+            CameraComponent::new(Camera::new_orthographic(
+                Vec3::new(0.0, 0.0, 10.0),
+                Vec3::ZERO,
+                Vec3::Y,
+                8.0,
+                8.0,
+                0.1,
+                100.0,
+            )),
+        ));
+        let material = MaterialHandle(Arc::new(ColorMaterial::new(&ctx, FORMAT).unwrap()));
+        let cube = |size| MeshHandle(Arc::new(Mesh::cube(&ctx, size, [0.2, 0.2, 0.2])));
+        let spawn = |world: &mut hecs::World, mesh: &MeshHandle, position: Vec3| {
+            world.spawn((
+                GlobalTransform(glam::Mat4::from_translation(position)),
+                MeshRenderer {
+                    mesh: MeshHandle(mesh.0.clone()),
+                    material: MaterialHandle(material.0.clone()),
+                    visible: true,
+                    cast_shadow: false,
+                    receive_shadow: false,
+                },
+                Visible,
+            ))
+        };
+        // 8 units across 64 pixels: world (x, y) is pixel (32 + 8x, 32 - 8y).
+        // The right cube is hidden behind a larger unselected one.
+        let small = cube(2.0);
+        let shown = spawn(&mut world, &small, Vec3::new(-2.0, 0.0, 0.0));
+        let hidden = spawn(&mut world, &small, Vec3::new(2.0, 0.0, 0.0));
+        spawn(&mut world, &cube(4.0), Vec3::new(2.0, 0.0, 5.0));
+        world.insert_one(shown, Selected).unwrap();
+        world.insert_one(hidden, Selected).unwrap();
+
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC;
+        let scene = Texture2D::new(&ctx, 64, 64, FORMAT, usage, Some("scene"));
+        let output = Texture2D::new(&ctx, 64, 64, FORMAT, usage, Some("outlined"));
+        let depth = DepthTexture::new(&ctx, 64, 64, Some("scene depth"));
+        let target = RenderTarget::new(&ctx, scene.view(), Some(depth.view()), 64, 64, FORMAT);
+        let mut buffers = RenderBuffers::new(&ctx);
+        let mut encoder = ctx.create_encoder(Some("outline test scene"));
+        {
+            let clear = ClearState::color_and_depth([0.0, 0.0, 0.0, 1.0], 1.0);
+            let mut pass = target.begin_render_pass(&mut encoder, clear);
+            render_system(&world, &ctx, &mut buffers, (64, 64), &mut pass);
+        }
+        ctx.submit([encoder.finish()]);
+
+        // Selected line geometry is left out of the mask
+        let axes = MeshHandle(Arc::new(Axes::new(&ctx, 1.0)));
+        let axes = spawn(&mut world, &axes, Vec3::new(0.0, 3.0, 0.0));
+        world.insert_one(axes, Selected).unwrap();
+
+        let mask = OutlineMask::new(&ctx, 1, 1);
+        let drawn = render_selection_mask(
+            &world,
+            &ctx,
+            &mut buffers,
+            &mask,
+            Some(depth.view()),
+            (64, 64),
+        );
+        assert_eq!(drawn, 2);
+        assert_eq!(mask.size(), (64, 64));
+
+        let mut outline = OutlineEffect::new(&ctx, FORMAT, mask).unwrap();
+        outline.color = glam::Vec4::new(1.0, 0.0, 0.0, 1.0);
+        outline.hidden_color = glam::Vec4::new(0.0, 1.0, 0.0, 1.0);
+        let pixel = |outline: &OutlineEffect, x: usize, y: usize| {
+            let mut encoder = ctx.create_encoder(Some("outline test effect"));
+            outline.apply(&ctx, &mut encoder, scene.view(), output.view());
+            ctx.submit([encoder.finish()]);
+            let pixels = output.read_pixels(&ctx).unwrap();
+            let i = (y * 64 + x) * 4;
+            [pixels[i], pixels[i + 1], pixels[i + 2]]
+        };
+
+        // Left of the visible cube, and on the occluder left of the hidden one
+        assert_eq!(pixel(&outline, 6, 32), [255, 0, 0]);
+        assert_ne!(pixel(&outline, 16, 32), [255, 0, 0]);
+        assert_ne!(pixel(&outline, 38, 32), [0, 255, 0]);
+        outline.x_ray = true;
+        assert_eq!(pixel(&outline, 38, 32), [0, 255, 0]);
+        assert_eq!(pixel(&outline, 6, 32), [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_selection_mask_matches_scene_depth() {
+        use crate::effect::{Effect, OutlineEffect};
+
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        // A distant tilted cube under a deep perspective projection, where
+        // any depth mismatch between the passes shows up
+        let mut world = hecs::World::new();
+        let eye = Vec3::new(0.0, 0.0, 20.0);
+        world.spawn((
+            GlobalTransform(glam::Mat4::from_translation(eye)),
+            CameraComponent::new(Camera::new_perspective(
+                eye,
+                Vec3::ZERO,
+                Vec3::Y,
+                10.0,
+                1.0,
+                0.01,
+                1000.0,
+            )),
+        ));
+        let material = MaterialHandle(Arc::new(ColorMaterial::new(&ctx, FORMAT).unwrap()));
+        let rotation = glam::Quat::from_euler(glam::EulerRot::XYZ, 0.7, 0.5, 0.2);
+        let cube = world.spawn((
+            GlobalTransform(glam::Mat4::from_quat(rotation)),
+            MeshRenderer {
+                mesh: MeshHandle(Arc::new(Mesh::cube(&ctx, 2.0, [0.8, 0.8, 0.8]))),
+                material,
+                visible: true,
+                cast_shadow: false,
+                receive_shadow: false,
+            },
+            Visible,
+            Selected,
+        ));
+
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC;
+        let scene = Texture2D::new(&ctx, 64, 64, FORMAT, usage, Some("scene"));
+        let output = Texture2D::new(&ctx, 64, 64, FORMAT, usage, Some("outlined"));
+        let depth = DepthTexture::new(&ctx, 64, 64, Some("scene depth"));
+        let target = RenderTarget::new(&ctx, scene.view(), Some(depth.view()), 64, 64, FORMAT);
+        let mut buffers = RenderBuffers::new(&ctx);
+        let mut encoder = ctx.create_encoder(Some("mask depth test scene"));
+        {
+            let clear = ClearState::color_and_depth([0.0, 0.0, 0.0, 1.0], 1.0);
+            let mut pass = target.begin_render_pass(&mut encoder, clear);
+            render_system(&world, &ctx, &mut buffers, (64, 64), &mut pass);
+        }
+        ctx.submit([encoder.finish()]);
+
+        let mask = OutlineMask::new(&ctx, 64, 64);
+        let drawn = render_selection_mask(
+            &world,
+            &ctx,
+            &mut buffers,
+            &mask,
+            Some(depth.view()),
+            (64, 64),
+        );
+        assert_eq!(drawn, 1);
+        assert!(world.satisfies::<&Selected>(cube).unwrap());
+
+        let mut outline = OutlineEffect::new(&ctx, FORMAT, mask).unwrap();
+        outline.color = glam::Vec4::new(1.0, 0.0, 0.0, 1.0);
+        let mut encoder = ctx.create_encoder(Some("mask depth test effect"));
+        outline.apply(&ctx, &mut encoder, scene.view(), output.view());
+        ctx.submit([encoder.finish()]);
+
+        // The whole cube is marked visible: it is outlined, and no outline is
+        // drawn over it
+        let scene = scene.read_pixels(&ctx).unwrap();
+        let output = output.read_pixels(&ctx).unwrap();
+        let covered = scene.chunks(4).filter(|p| p[..3] != [0, 0, 0]).count();
+        assert!(covered > 100, "covered = {covered}");
+        let outlined = output.chunks(4).filter(|p| p[..3] == [255, 0, 0]).count();
+        assert!(outlined > 20, "outlined = {outlined}");
+        for (i, (before, after)) in scene.chunks(4).zip(output.chunks(4)).enumerate() {
+            if before[..3] != [0, 0, 0] {
+                assert_eq!(before, after, "pixel ({}, {})", i % 64, i / 64);
+            }
         }
     }
 }
@@ -1,70 +1,214 @@
 //! Transform hierarchy propagation system.
 
-use crate::ecs::components::transform::{Children, GlobalTransform, Parent, Transform};
+use std::collections::HashSet;
+
+use crate::ecs::components::transform::{
+    Children, GlobalTransform, Parent, Transform, TransformDirty,
+};
+
+/// Changed-root count from which the root phase is split across threads.
+const PARALLEL_ROOT_THRESHOLD: usize = 4096;
+
+/// Local transform as of the last propagation, used to detect changes.
+///
+/// `None` forces a recompute; inserted by [`transform_system`] on first sight.
+struct PropagatedTransform(Option<Transform>);
 
 /// Propagate transforms through the Parent/Children hierarchy.
 ///
-/// Phase 1: Update root entities (no Parent) - GlobalTransform = Transform.to_matrix()
-/// Phase 2: Recursively propagate through Children hierarchy.
+/// Only subtrees whose local [`Transform`] changed since the last run are
+/// recomputed, so mostly static worlds are cheap:
+///
+/// Phase 1: Update changed root entities (no Parent) - GlobalTransform = Transform.to_matrix().
+/// Large sets of changed roots are processed in parallel.
+/// Phase 2: Re-propagate below changed roots and changed children.
+///
+/// Changes to `Parent`/`Children` alone are not detected; the helpers in
+/// [`hierarchy`](crate::ecs::hierarchy) insert [`TransformDirty`] for that.
 pub fn transform_system(world: &mut hecs::World) {
+    track_new_entities(world);
+
     // Phase 1: Root entities (entities with Transform + GlobalTransform but no Parent).
-    // Collect root entities and their matrices first to avoid borrow conflicts.
-    let roots: Vec<(hecs::Entity, glam::Mat4)> = world
-        .query_mut::<hecs::Without<(&Transform, &GlobalTransform), &Parent>>()
+    let dirty_roots = update_roots(world);
+
+    // Phase 2: Changed children whose ancestors are all unchanged start their own
+    // propagation from the parent's current GlobalTransform.
+    let dirty_children: Vec<(hecs::Entity, hecs::Entity)> = world
+        .query_mut::<(&Transform, &PropagatedTransform, &Parent)>()
         .into_iter()
-        .map(|(entity, (transform, _))| (entity, transform.to_matrix()))
+        .filter(|(_, (transform, last, _))| last.0.as_ref() != Some(*transform))
+        .map(|(entity, (_, _, parent))| (entity, parent.0))
+        .collect();
+
+    let world = &*world;
+    let dirty: HashSet<hecs::Entity> = dirty_roots
+        .iter()
+        .map(|(entity, _)| *entity)
+        .chain(dirty_children.iter().map(|(entity, _)| *entity))
         .collect();
 
-    for (entity, matrix) in &roots {
-        if let Ok(mut global) = world.get::<&mut GlobalTransform>(*entity) {
-            global.0 = *matrix;
+    for (entity, matrix) in &dirty_roots {
+        propagate_children(world, *entity, *matrix);
+    }
+    for (entity, parent) in dirty_children {
+        if has_dirty_ancestor(world, parent, &dirty) {
+            continue;
         }
+        let parent_global = match world.get::<&GlobalTransform>(parent) {
+            Ok(global) => global.0,
+            Err(_) => crate::ecs::hierarchy::world_matrix(world, parent),
+        };
+        propagate_entity(world, entity, parent_global);
     }
+}
 
-    // Phase 2: Propagate through hierarchy.
-    // Collect root entities that have children to start traversal.
-    let root_with_children: Vec<(hecs::Entity, glam::Mat4)> = roots
-        .iter()
-        .filter(|(entity, _)| world.satisfies::<&Children>(*entity).unwrap_or(false))
-        .copied()
+/// Start tracking entities seen for the first time and reset entities marked
+/// with [`TransformDirty`], so both count as changed.
+fn track_new_entities(world: &mut hecs::World) {
+    let untracked: Vec<hecs::Entity> = world
+        .query_mut::<hecs::Without<&Transform, &PropagatedTransform>>()
+        .into_iter()
+        .map(|(entity, _)| entity)
         .collect();
+    for entity in untracked {
+        let _ = world.insert_one(entity, PropagatedTransform(None));
+    }
 
-    for (entity, parent_matrix) in root_with_children {
-        propagate_children(world, entity, parent_matrix);
+    let marked: Vec<hecs::Entity> = world
+        .query_mut::<&TransformDirty>()
+        .into_iter()
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in marked {
+        let _ = world.remove_one::<TransformDirty>(entity);
+        if let Ok(mut last) = world.get::<&mut PropagatedTransform>(entity) {
+            last.0 = None;
+        }
     }
 }
 
-/// Recursively propagate GlobalTransform to children.
-fn propagate_children(world: &mut hecs::World, parent: hecs::Entity, parent_global: glam::Mat4) {
-    // Get the list of children (clone to release borrow).
-    let children = match world.get::<&Children>(parent) {
-        Ok(c) => c.0.clone(),
-        Err(_) => return,
-    };
+/// Update the GlobalTransform of changed roots, returning the ones with
+/// children and their new matrices.
+///
+/// The change check is a serial pass; threads are only spawned when at least
+/// [`PARALLEL_ROOT_THRESHOLD`] roots changed.
+fn update_roots(world: &mut hecs::World) -> Vec<(hecs::Entity, glam::Mat4)> {
+    let query = world.query_mut::<hecs::Without<
+        (
+            &Transform,
+            &mut GlobalTransform,
+            &mut PropagatedTransform,
+            Option<&Children>,
+        ),
+        &Parent,
+    >>();
+    let mut changed: Vec<RootItem> = query
+        .into_iter()
+        .filter(|(_, (transform, _, last, _))| last.0.as_ref() != Some(*transform))
+        .collect();
 
-    for child in children {
-        // Compute child's global transform.
-        let child_global = match world.get::<&Transform>(child) {
-            Ok(transform) => parent_global * transform.to_matrix(),
-            Err(_) => parent_global,
-        };
+    if changed.len() < PARALLEL_ROOT_THRESHOLD {
+        return update_root_chunk(&mut changed);
+    }
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if threads == 1 {
+        return update_root_chunk(&mut changed);
+    }
 
-        // Update the child's GlobalTransform.
-        if let Ok(mut global) = world.get::<&mut GlobalTransform>(child) {
-            global.0 = child_global;
+    let chunk_size = changed.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = changed
+            .chunks_mut(chunk_size)
+            .map(|chunk| scope.spawn(move || update_root_chunk(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("transform worker panicked"))
+            .collect()
+    })
+}
+
+type RootItem<'a> = (
+    hecs::Entity,
+    (
+        &'a Transform,
+        &'a mut GlobalTransform,
+        &'a mut PropagatedTransform,
+        Option<&'a Children>,
+    ),
+);
+
+/// Recompute changed roots.
+fn update_root_chunk(roots: &mut [RootItem]) -> Vec<(hecs::Entity, glam::Mat4)> {
+    let mut dirty = Vec::new();
+    for (entity, (transform, global, last, children)) in roots {
+        last.0 = Some(**transform);
+        global.0 = transform.to_matrix();
+        if children.is_some() {
+            dirty.push((*entity, global.0));
         }
+    }
+    dirty
+}
+
+/// Whether `entity` or one of its ancestors is in `dirty`.
+fn has_dirty_ancestor(
+    world: &hecs::World,
+    entity: hecs::Entity,
+    dirty: &HashSet<hecs::Entity>,
+) -> bool {
+    let mut current = Some(entity);
+    // Bounded walk in case of a Parent cycle
+    let mut steps = 0;
+    while let Some(e) = current {
+        if dirty.contains(&e) || steps > dirty.len() + 1024 {
+            return true;
+        }
+        steps += 1;
+        current = world.get::<&Parent>(e).ok().map(|parent| parent.0);
+    }
+    false
+}
+
+/// Recompute `entity` from `parent_global`, then its whole subtree.
+fn propagate_entity(world: &hecs::World, entity: hecs::Entity, parent_global: glam::Mat4) {
+    // Compute the entity's global transform.
+    let global = match world.get::<&Transform>(entity) {
+        Ok(transform) => {
+            if let Ok(mut last) = world.get::<&mut PropagatedTransform>(entity) {
+                last.0 = Some(*transform);
+            }
+            parent_global * transform.to_matrix()
+        }
+        Err(_) => parent_global,
+    };
+
+    // Update the entity's GlobalTransform.
+    if let Ok(mut global_transform) = world.get::<&mut GlobalTransform>(entity) {
+        global_transform.0 = global;
+    }
+
+    propagate_children(world, entity, global);
+}
+
+/// Recursively propagate GlobalTransform to children.
+fn propagate_children(world: &hecs::World, parent: hecs::Entity, parent_global: glam::Mat4) {
+    let Ok(children) = world.get::<&Children>(parent) else {
+        return;
+    };
 
-        // Recurse into grandchildren.
-        if world.satisfies::<&Children>(child).unwrap_or(false) {
-            propagate_children(world, child, child_global);
+    for &child in &children.0 {
+        if cfg!(debug_assertions) && !world.contains(child) {
+            tracing::warn!("{parent:?} lists missing child {child:?}; see validate_hierarchy");
+            continue;
         }
+        propagate_entity(world, child, parent_global);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ecs::components::transform::{Children, GlobalTransform, Parent, Transform};
     use glam::{Mat4, Vec3};
 
     #[test]
@@ -157,4 +301,97 @@ mod tests {
             actual_pos
         );
     }
+
+    #[test]
+    fn test_only_changed_subtrees_are_recomputed() {
+        let mut world = hecs::World::new();
+        let parent = world.spawn((
+            Transform::from_position(Vec3::X),
+            GlobalTransform::default(),
+        ));
+        let child = world.spawn((
+            Transform::from_position(Vec3::Y),
+            GlobalTransform::default(),
+            Parent(parent),
+        ));
+        world.insert_one(parent, Children(vec![child])).unwrap();
+        let other = world.spawn((
+            Transform::from_position(Vec3::Z),
+            GlobalTransform::default(),
+        ));
+        transform_system(&mut world);
+
+        // Static entities keep whatever GlobalTransform they have
+        world.get::<&mut GlobalTransform>(other).unwrap().0 = Mat4::ZERO;
+        world.get::<&mut GlobalTransform>(parent).unwrap().0 = Mat4::ZERO;
+        world.get::<&mut Transform>(child).unwrap().position = Vec3::new(0.0, 3.0, 0.0);
+        transform_system(&mut world);
+        assert_eq!(world.get::<&GlobalTransform>(other).unwrap().0, Mat4::ZERO);
+        // The changed child starts from its parent's current GlobalTransform
+        assert_eq!(world.get::<&GlobalTransform>(child).unwrap().0, Mat4::ZERO);
+
+        world.get::<&mut Transform>(parent).unwrap().position = Vec3::new(2.0, 0.0, 0.0);
+        transform_system(&mut world);
+        let child_pos = world
+            .get::<&GlobalTransform>(child)
+            .unwrap()
+            .0
+            .transform_point3(Vec3::ZERO);
+        assert!((child_pos - Vec3::new(2.0, 3.0, 0.0)).length() < 1e-5);
+
+        // Hierarchy edits need a TransformDirty marker
+        world.remove_one::<Parent>(child).unwrap();
+        world.insert_one(child, TransformDirty).unwrap();
+        transform_system(&mut world);
+        let child_pos = world
+            .get::<&GlobalTransform>(child)
+            .unwrap()
+            .0
+            .transform_point3(Vec3::ZERO);
+        assert!((child_pos - Vec3::new(0.0, 3.0, 0.0)).length() < 1e-5);
+        assert!(world.get::<&TransformDirty>(child).is_err());
+    }
+
+    #[test]
+    fn test_parallel_root_phase() {
+        let mut world = hecs::World::new();
+        let entities: Vec<_> = (0..PARALLEL_ROOT_THRESHOLD * 2)
+            .map(|i| {
+                let pos = Vec3::new(i as f32, 0.0, 0.0);
+                world.spawn((Transform::from_position(pos), GlobalTransform::default()))
+            })
+            .collect();
+        let child = world.spawn((
+            Transform::from_position(Vec3::Y),
+            GlobalTransform::default(),
+            Parent(entities[7]),
+        ));
+        world
+            .insert_one(entities[7], Children(vec![child]))
+            .unwrap();
+
+        transform_system(&mut world);
+        for (i, &entity) in entities.iter().enumerate() {
+            let global = world.get::<&GlobalTransform>(entity).unwrap();
+            assert_eq!(
+                global.0,
+                Mat4::from_translation(Vec3::new(i as f32, 0.0, 0.0))
+            );
+        }
+        let child_global = world.get::<&GlobalTransform>(child).unwrap();
+        assert_eq!(
+            child_global.0,
+            Mat4::from_translation(Vec3::new(7.0, 1.0, 0.0))
+        );
+        drop(child_global);
+
+        // A few changed roots in a large world take the serial path
+        world.get::<&mut Transform>(entities[7]).unwrap().position.z = 2.0;
+        transform_system(&mut world);
+        let child_global = world.get::<&GlobalTransform>(child).unwrap();
+        assert_eq!(
+            child_global.0,
+            Mat4::from_translation(Vec3::new(7.0, 1.0, 2.0))
+        );
+    }
 }
@@ -2,13 +2,14 @@
 
 use super::{Effect, FullscreenQuad};
 use crate::context::WgpuContext;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::PipelineBuilder;
 use crate::core::render_states::{BlendState, CullState};
 use crate::core::vertex::VertexPC;
 
 /// Simple copy effect that copies input to output.
 pub struct CopyEffect {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     bind_group_layout: wgpu::BindGroupLayout,
     sampler: wgpu::Sampler,
     quad: FullscreenQuad,
@@ -17,7 +18,10 @@ pub struct CopyEffect {
 impl CopyEffect {
     /// Create a new copy effect.
     pub fn new(ctx: &WgpuContext, format: wgpu::TextureFormat) -> anyhow::Result<Self> {
-        let shader = include_str!("../shaders/effects/copy.wgsl");
+        let shader = ShaderFile::embedded(
+            "effects/copy.wgsl",
+            include_str!("../shaders/effects/copy.wgsl"),
+        );
 
         let bind_group_layout =
             ctx.device
@@ -43,15 +47,20 @@ impl CopyEffect {
                     ],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("copy effect pipeline")
-            .shader(shader)
-            .vertex_layout(VertexPC::layout())
-            .bind_group_layout(&bind_group_layout)
-            .color_format(format)
-            .blend(BlendState::Opaque)
-            .cull(CullState::None)
-            .build()?;
+        let (pipeline_ctx, layout) = (ctx.clone(), bind_group_layout.clone());
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("copy effect pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(VertexPC::layout())
+                .bind_group_layout(&layout)
+                .color_format(format)
+                .blend(BlendState::Opaque)
+                .cull(CullState::None)
+                .build()
+        })?;
 
         let sampler = ctx.device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("copy effect sampler"),
@@ -118,8 +127,12 @@ impl Effect for CopyEffect {
             multiview_mask: None,
         });
 
-        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_pipeline(&self.pipeline.get());
         render_pass.set_bind_group(0, &bind_group, &[]);
         self.quad.draw(&mut render_pass);
     }
+
+    fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
 }
@@ -3,6 +3,7 @@
 use super::{Effect, FullscreenQuad};
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::PipelineBuilder;
 use crate::core::render_states::{BlendState, CullState};
 use crate::core::vertex::VertexPC;
@@ -31,7 +32,7 @@ pub struct FogUniform {
 
 /// Fog post-processing effect.
 pub struct FogEffect {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     bind_group_layout: wgpu::BindGroupLayout,
     uniform_buffer: RawUniformBuffer,
     sampler: wgpu::Sampler,
@@ -53,8 +54,6 @@ pub struct FogEffect {
 impl FogEffect {
     /// Create a new fog effect.
     pub fn new(ctx: &WgpuContext, format: wgpu::TextureFormat) -> anyhow::Result<Self> {
-        let shader = include_str!("../shaders/effects/fog.wgsl");
-
         let bind_group_layout =
             ctx.device
                 .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -110,15 +109,24 @@ impl FogEffect {
                     ],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("fog pipeline")
-            .shader(shader)
-            .vertex_layout(VertexPC::layout())
-            .bind_group_layout(&bind_group_layout)
-            .color_format(format)
-            .blend(BlendState::Opaque)
-            .cull(CullState::None)
-            .build()?;
+        let shader = ShaderFile::embedded(
+            "effects/fog.wgsl",
+            include_str!("../shaders/effects/fog.wgsl"),
+        );
+        let (pipeline_ctx, layout) = (ctx.clone(), bind_group_layout.clone());
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("fog pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(VertexPC::layout())
+                .bind_group_layout(&layout)
+                .color_format(format)
+                .blend(BlendState::Opaque)
+                .cull(CullState::None)
+                .build()
+        })?;
 
         let uniform_buffer = RawUniformBuffer::new(
             ctx,
@@ -241,7 +249,7 @@ impl FogEffect {
             multiview_mask: None,
         });
 
-        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_pipeline(&self.pipeline.get());
         render_pass.set_bind_group(0, &bind_group, &[]);
         self.quad.draw(&mut render_pass);
     }
@@ -259,4 +267,8 @@ impl Effect for FogEffect {
         // Use apply_with_depth instead
         panic!("FogEffect requires depth texture. Use apply_with_depth instead.");
     }
+
+    fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
 }
@@ -3,6 +3,7 @@
 use super::{Effect, FullscreenQuad};
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::PipelineBuilder;
 use crate::core::render_states::{BlendState, CullState};
 use crate::core::vertex::VertexPC;
@@ -17,7 +18,7 @@ pub struct FxaaUniform {
 
 /// FXAA post-processing effect for anti-aliasing.
 pub struct FxaaEffect {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     bind_group_layout: wgpu::BindGroupLayout,
     uniform_buffer: RawUniformBuffer,
     sampler: wgpu::Sampler,
@@ -27,7 +28,10 @@ pub struct FxaaEffect {
 impl FxaaEffect {
     /// Create a new FXAA effect.
     pub fn new(ctx: &WgpuContext, format: wgpu::TextureFormat) -> anyhow::Result<Self> {
-        let shader = include_str!("../shaders/effects/fxaa.wgsl");
+        let shader = ShaderFile::embedded(
+            "effects/fxaa.wgsl",
+            include_str!("../shaders/effects/fxaa.wgsl"),
+        );
 
         let bind_group_layout =
             ctx.device
@@ -63,15 +67,20 @@ impl FxaaEffect {
                     ],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("fxaa pipeline")
-            .shader(shader)
-            .vertex_layout(VertexPC::layout())
-            .bind_group_layout(&bind_group_layout)
-            .color_format(format)
-            .blend(BlendState::Opaque)
-            .cull(CullState::None)
-            .build()?;
+        let (pipeline_ctx, layout) = (ctx.clone(), bind_group_layout.clone());
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("fxaa pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(VertexPC::layout())
+                .bind_group_layout(&layout)
+                .color_format(format)
+                .blend(BlendState::Opaque)
+                .cull(CullState::None)
+                .build()
+        })?;
 
         let uniform_buffer = RawUniformBuffer::new(
             ctx,
@@ -162,8 +171,12 @@ impl Effect for FxaaEffect {
             multiview_mask: None,
         });
 
-        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_pipeline(&self.pipeline.get());
         render_pass.set_bind_group(0, &bind_group, &[]);
         self.quad.draw(&mut render_pass);
     }
+
+    fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
 }
@@ -1,16 +1,18 @@
 //! Post-processing effects
 //!
-//! Provides screen-space effects like FXAA and fog.
+//! Provides screen-space effects like FXAA, fog and selection outlines.
 
 mod copy;
 mod fog;
 mod fullscreen;
 mod fxaa;
+mod outline;
 
 pub use copy::CopyEffect;
 pub use fog::{FogEffect, FogMode};
 pub use fullscreen::FullscreenQuad;
 pub use fxaa::FxaaEffect;
+pub use outline::{OutlineEffect, OutlineMask};
 
 use crate::context::WgpuContext;
 use crate::core::Texture2D;
@@ -25,6 +27,12 @@ pub trait Effect {
         input: &wgpu::TextureView,
         output: &wgpu::TextureView,
     );
+
+    /// Rebuild pipelines whose shader files changed, when hot reload is
+    /// enabled. Returns whether anything was rebuilt.
+    fn reload_shaders(&self) -> bool {
+        false
+    }
 }
 
 /// A chain of post-processing effects.
@@ -128,6 +136,17 @@ impl EffectChain {
         }
     }
 
+    /// Rebuild the pipelines of effects whose shader files changed; see
+    /// [`Effect::reload_shaders`]. The engine calls this once per frame for an
+    /// `EffectChain` resource while hot reload is enabled.
+    pub fn reload_shaders(&self) -> bool {
+        let mut reloaded = false;
+        for effect in &self.effects {
+            reloaded |= effect.reload_shaders();
+        }
+        reloaded
+    }
+
     /// Get the number of effects.
     pub fn len(&self) -> usize {
         self.effects.len()
@@ -0,0 +1,284 @@
+//! Selection outline post-processing effect
+
+use std::sync::{Arc, Mutex};
+
+use super::{Effect, FullscreenQuad};
+use crate::context::WgpuContext;
+use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
+use crate::core::pipeline::PipelineBuilder;
+use crate::core::render_states::{BlendState, CullState};
+use crate::core::texture::Texture2D;
+use crate::core::vertex::VertexPC;
+use glam::Vec4;
+
+/// Outline uniform parameters.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct OutlineUniform {
+    /// Outline color around visible parts, alpha = opacity.
+    pub color: [f32; 4],
+    /// Outline color around hidden parts in x-ray mode, alpha = opacity.
+    pub hidden_color: [f32; 4],
+    /// width in pixels, x-ray (0 or 1), padding
+    pub params: [f32; 4],
+}
+
+/// Mask of the selected objects that [`OutlineEffect`] outlines.
+///
+/// Red is the selected objects' coverage, green the part of it not hidden
+/// behind other geometry. The ECS renders it with
+/// `render_selection_mask`. Clones share the same texture, so the effect
+/// sees the mask after it is resized or redrawn.
+#[derive(Clone)]
+pub struct OutlineMask {
+    texture: Arc<Mutex<Texture2D>>,
+}
+
+impl OutlineMask {
+    /// Texture format of the mask.
+    pub const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+    /// Create an empty mask of `width` x `height` pixels.
+    pub fn new(ctx: &WgpuContext, width: u32, height: u32) -> Self {
+        Self {
+            texture: Arc::new(Mutex::new(Self::create_texture(ctx, width, height))),
+        }
+    }
+
+    fn create_texture(ctx: &WgpuContext, width: u32, height: u32) -> Texture2D {
+        Texture2D::new(
+            ctx,
+            width.max(1),
+            height.max(1),
+            Self::FORMAT,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            Some("outline mask"),
+        )
+    }
+
+    /// Size in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        self.texture.lock().unwrap().size()
+    }
+
+    /// Resize, clearing the mask. Does nothing if the size is unchanged.
+    pub fn resize(&self, ctx: &WgpuContext, width: u32, height: u32) {
+        let mut texture = self.texture.lock().unwrap();
+        if texture.size() != (width.max(1), height.max(1)) {
+            *texture = Self::create_texture(ctx, width, height);
+        }
+    }
+
+    /// View of the mask texture.
+    pub fn view(&self) -> wgpu::TextureView {
+        self.texture.lock().unwrap().view().clone()
+    }
+}
+
+/// Selection outline effect.
+///
+/// Copies the input and draws an outline `width` pixels wide around the
+/// visible parts of the objects in its [`OutlineMask`]. With `x_ray`, parts
+/// hidden behind other geometry are outlined too, in `hidden_color`.
+pub struct OutlineEffect {
+    pipeline: HotShader<wgpu::RenderPipeline>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    uniform_buffer: RawUniformBuffer,
+    sampler: wgpu::Sampler,
+    quad: FullscreenQuad,
+    mask: OutlineMask,
+
+    /// Outline color, alpha = opacity.
+    pub color: Vec4,
+    /// Color of outlines of hidden parts in x-ray mode, alpha = opacity.
+    pub hidden_color: Vec4,
+    /// Outline width in pixels, at most 16.
+    pub width: f32,
+    /// Outline selected objects through occluders.
+    pub x_ray: bool,
+}
+
+impl OutlineEffect {
+    /// Create a new outline effect around the objects in `mask`.
+    pub fn new(
+        ctx: &WgpuContext,
+        format: wgpu::TextureFormat,
+        mask: OutlineMask,
+    ) -> anyhow::Result<Self> {
+        let bind_group_layout =
+            ctx.device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("outline bind group layout"),
+                    entries: &[
+                        // Color texture
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        // Color sampler
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                            count: None,
+                        },
+                        // Mask texture
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 2,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Texture {
+                                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                                view_dimension: wgpu::TextureViewDimension::D2,
+                                multisampled: false,
+                            },
+                            count: None,
+                        },
+                        // Outline uniform
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 3,
+                            visibility: wgpu::ShaderStages::FRAGMENT,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        let shader = ShaderFile::embedded(
+            "effects/outline.wgsl",
+            include_str!("../shaders/effects/outline.wgsl"),
+        );
+        let (pipeline_ctx, layout) = (ctx.clone(), bind_group_layout.clone());
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("outline pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(VertexPC::layout())
+                .bind_group_layout(&layout)
+                .color_format(format)
+                .blend(BlendState::Opaque)
+                .cull(CullState::None)
+                .build()
+        })?;
+
+        let uniform_buffer = RawUniformBuffer::new(
+            ctx,
+            std::mem::size_of::<OutlineUniform>() as u64,
+            Some("outline uniform"),
+        );
+
+        let sampler = ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("outline color sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Ok(Self {
+            pipeline,
+            bind_group_layout,
+            uniform_buffer,
+            sampler,
+            quad: FullscreenQuad::new(ctx),
+            mask,
+            color: Vec4::new(1.0, 0.6, 0.1, 1.0),
+            hidden_color: Vec4::new(1.0, 0.6, 0.1, 0.4),
+            width: 2.0,
+            x_ray: false,
+        })
+    }
+
+    /// The mask this effect outlines.
+    pub fn mask(&self) -> &OutlineMask {
+        &self.mask
+    }
+
+    fn create_bind_group(&self, ctx: &WgpuContext, input: &wgpu::TextureView) -> wgpu::BindGroup {
+        let mask = self.mask.view();
+        ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("outline bind group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(&mask),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: self.uniform_buffer.buffer().as_entire_binding(),
+                },
+            ],
+        })
+    }
+
+    /// Update the outline uniform buffer.
+    pub fn update_uniform(&self, ctx: &WgpuContext) {
+        let uniform = OutlineUniform {
+            color: self.color.to_array(),
+            hidden_color: self.hidden_color.to_array(),
+            params: [self.width, f32::from(u8::from(self.x_ray)), 0.0, 0.0],
+        };
+        self.uniform_buffer.write(ctx, &uniform);
+    }
+}
+
+impl Effect for OutlineEffect {
+    fn apply(
+        &self,
+        ctx: &WgpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        input: &wgpu::TextureView,
+        output: &wgpu::TextureView,
+    ) {
+        self.update_uniform(ctx);
+        let bind_group = self.create_bind_group(ctx, input);
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("outline pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+
+        render_pass.set_pipeline(&self.pipeline.get());
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        self.quad.draw(&mut render_pass);
+    }
+
+    fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
+}
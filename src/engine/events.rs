@@ -0,0 +1,160 @@
+//! Typed event queues for communication between systems.
+
+use std::marker::PhantomData;
+
+/// Double-buffered queue of events of type `T`.
+///
+/// Stored as an engine resource by [`AppBuilder::add_event`](super::AppBuilder::add_event),
+/// which also schedules [`update`](Self::update) at the start of every
+/// frame. An event stays readable during the frame it was sent and the next
+/// one, so readers running before the sender in a frame still see it.
+pub struct Events<T> {
+    /// Events sent before the last update.
+    previous: Vec<T>,
+    /// Events sent since the last update.
+    current: Vec<T>,
+    /// Id of the first event in `previous`.
+    previous_start: usize,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            previous_start: 0,
+        }
+    }
+}
+
+impl<T> Events<T> {
+    /// Create an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue an event.
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    /// Queue every event of `events`.
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.current.extend(events);
+    }
+
+    /// Drop the events of the previous frame and start a new one.
+    pub fn update(&mut self) {
+        self.previous_start += self.previous.len();
+        self.previous.clear();
+        std::mem::swap(&mut self.previous, &mut self.current);
+    }
+
+    /// Number of readable events.
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Whether no events are readable.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every queued event.
+    pub fn clear(&mut self) {
+        self.previous_start += self.len();
+        self.previous.clear();
+        self.current.clear();
+    }
+
+    /// A reader that only sees events sent from now on.
+    pub fn reader_from_now(&self) -> EventReader<T> {
+        EventReader {
+            cursor: self.end(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Id one past the newest event.
+    fn end(&self) -> usize {
+        self.previous_start + self.len()
+    }
+
+    /// Readable events with an id of at least `cursor`, oldest first.
+    fn since(&self, cursor: usize) -> impl Iterator<Item = &T> {
+        let skip = cursor.saturating_sub(self.previous_start);
+        self.previous.iter().chain(&self.current).skip(skip)
+    }
+}
+
+/// Per-system cursor into an [`Events`] queue.
+///
+/// Each reader sees every event once, as long as it reads at least every
+/// other frame; older events have expired.
+pub struct EventReader<T> {
+    cursor: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for EventReader<T> {
+    fn default() -> Self {
+        Self {
+            cursor: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> EventReader<T> {
+    /// Create a reader that sees every event still readable in the queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events not yet read by this reader, oldest first.
+    pub fn read<'a>(&mut self, events: &'a Events<T>) -> impl Iterator<Item = &'a T> {
+        let unread = events.since(self.cursor);
+        self.cursor = events.end();
+        unread
+    }
+
+    /// Number of events not yet read by this reader.
+    pub fn unread(&self, events: &Events<T>) -> usize {
+        events.end() - self.cursor.clamp(events.previous_start, events.end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_expire_after_two_frames() {
+        let mut events = Events::new();
+        let mut early = EventReader::new();
+        let mut late = EventReader::new();
+
+        events.send(1);
+        assert_eq!(early.read(&events).copied().collect::<Vec<_>>(), [1]);
+        assert_eq!(early.read(&events).count(), 0);
+
+        events.update();
+        events.send_batch([2, 3]);
+        assert_eq!(early.unread(&events), 2);
+        assert_eq!(early.read(&events).copied().collect::<Vec<_>>(), [2, 3]);
+
+        events.update();
+        events.update();
+        events.send(4);
+        // Events 1-3 expired before the late reader looked
+        assert_eq!(late.read(&events).copied().collect::<Vec<_>>(), [4]);
+
+        let mut fresh = events.reader_from_now();
+        events.send(5);
+        assert_eq!(fresh.read(&events).copied().collect::<Vec<_>>(), [5]);
+        assert_eq!(events.len(), 2);
+        events.clear();
+        assert!(events.is_empty());
+        assert_eq!(early.read(&events).count(), 0);
+    }
+}
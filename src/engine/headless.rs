@@ -0,0 +1,348 @@
+//! Windowless engine runner for tests, simulations and batch jobs.
+
+use anyhow::bail;
+
+use super::{App, Engine, GameLoopConfig, Resources};
+use crate::context::WgpuContext;
+use crate::core::texture::{DepthTexture, Texture2D};
+use crate::core::RenderTarget;
+use crate::window::frame_io::{FrameInput, Viewport};
+
+/// Settings for [`run_app_headless`].
+pub struct HeadlessConfig {
+    /// Game loop settings, as for [`run_app`](super::run_app).
+    pub game_loop: GameLoopConfig,
+    /// Simulated time between frames (seconds). Frames run as fast as the
+    /// machine allows, independent of wall-clock time. Default: 1/60.
+    pub frame_time: f64,
+    /// Offscreen render target size. Default: 1280x720.
+    pub size: (u32, u32),
+    /// Offscreen render target format, reported as the surface format.
+    /// Default: `Rgba8UnormSrgb`.
+    pub format: wgpu::TextureFormat,
+    /// Whether to render each frame. When false, the render passes are
+    /// skipped but [`Stage::Render`](super::Stage::Render) systems and
+    /// `App::post_render` still run. Default: true.
+    pub render: bool,
+}
+
+impl Default for HeadlessConfig {
+    fn default() -> Self {
+        Self {
+            game_loop: GameLoopConfig::default(),
+            frame_time: 1.0 / 60.0,
+            size: (1280, 720),
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            render: true,
+        }
+    }
+}
+
+/// Stop condition checked by [`HeadlessRun::Until`] after each frame.
+pub type HeadlessDoneFn = Box<dyn FnMut(&hecs::World, &Resources) -> bool>;
+
+/// How long [`run_app_headless`] runs.
+pub enum HeadlessRun {
+    /// Run exactly this many frames.
+    Frames(u64),
+    /// Run until `done` returns true after a frame, failing after
+    /// `max_frames` frames.
+    Until {
+        max_frames: u64,
+        done: HeadlessDoneFn,
+    },
+}
+
+impl HeadlessRun {
+    /// Run until `done` returns true, for at most `max_frames` frames.
+    pub fn until(
+        max_frames: u64,
+        done: impl FnMut(&hecs::World, &Resources) -> bool + 'static,
+    ) -> Self {
+        Self::Until {
+            max_frames,
+            done: Box::new(done),
+        }
+    }
+}
+
+/// Final engine state returned by [`run_app_headless`].
+pub struct HeadlessOutput<A> {
+    pub app: A,
+    pub world: hecs::World,
+    pub resources: Resources,
+    /// Number of frames run.
+    pub frames: u64,
+    /// Simulated time at the end of the last frame (seconds).
+    pub elapsed_time: f64,
+    /// The offscreen target holding the last rendered frame, readable with
+    /// [`Texture2D::read_pixels`].
+    pub color: Texture2D,
+}
+
+/// Run a game application without a window.
+///
+/// Creates a [`WgpuContext`] without a surface and runs the same frame as
+/// [`run_app`](super::run_app), rendering into an offscreen target and
+/// advancing a simulated clock by `frame_time` per frame, so runs are
+/// deterministic and can go faster than real time.
+pub fn run_app_headless<A: App>(
+    config: HeadlessConfig,
+    app: A,
+    run: HeadlessRun,
+) -> anyhow::Result<HeadlessOutput<A>> {
+    let ctx = WgpuContext::new_blocking(None)?;
+    run_app_headless_with(&ctx, config, app, run)
+}
+
+/// [`run_app_headless`] on an existing context.
+pub fn run_app_headless_with<A: App>(
+    ctx: &WgpuContext,
+    config: HeadlessConfig,
+    app: A,
+    mut run: HeadlessRun,
+) -> anyhow::Result<HeadlessOutput<A>> {
+    let (width, height) = config.size;
+    let color = Texture2D::new(
+        ctx,
+        width,
+        height,
+        config.format,
+        wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC,
+        Some("headless color"),
+    );
+    let depth = DepthTexture::new(ctx, width, height, Some("headless depth"));
+    let target = RenderTarget::new(
+        ctx,
+        color.view(),
+        Some(depth.view()),
+        width,
+        height,
+        config.format,
+    );
+
+    let mut engine = Engine::new(config.game_loop, app);
+    let mut frames = 0;
+    let mut elapsed_time = 0.0;
+    loop {
+        match &mut run {
+            HeadlessRun::Frames(count) if frames >= *count => break,
+            HeadlessRun::Until { max_frames, .. } if frames >= *max_frames => {
+                bail!("Headless run did not finish within {max_frames} frames");
+            }
+            _ => {}
+        }
+
+        elapsed_time += config.frame_time;
+        let frame = FrameInput {
+            events: Vec::new(),
+            elapsed_time,
+            delta_time: config.frame_time,
+            viewport: Viewport {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            },
+            ctx,
+            surface_view: color.view(),
+            depth_texture: &depth,
+            surface_format: config.format,
+        };
+        engine.frame(&frame, config.render.then_some(&target));
+        frames += 1;
+
+        if let HeadlessRun::Until { done, .. } = &mut run {
+            if done(&engine.world, &engine.resources) {
+                break;
+            }
+        }
+    }
+
+    Ok(HeadlessOutput {
+        app: engine.app,
+        world: engine.world,
+        resources: engine.resources,
+        frames,
+        elapsed_time,
+        color,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{AppBuilder, Stage, SystemContext};
+    use crate::time::Time;
+
+    fn try_create_ctx() -> Option<WgpuContext> {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            return None;
+        }
+        WgpuContext::new_blocking(None).ok()
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        fixed_steps: u32,
+        time_scale: Option<f64>,
+    }
+
+    impl App for Counter {
+        fn build(&mut self, app: &mut AppBuilder) {
+            if let Some(scale) = self.time_scale {
+                app.resources.get_mut::<Time>().unwrap().set_scale(scale);
+            }
+            app.insert_resource(0u32)
+                .add_system(Stage::Update, "count", |_, ctx| {
+                    *ctx.resources.get_mut::<u32>().unwrap() += 1;
+                });
+        }
+
+        fn init(&mut self, _ctx: &WgpuContext, _world: &mut hecs::World) {}
+
+        fn update(&mut self, _world: &mut hecs::World, _ctx: &SystemContext) {}
+
+        fn fixed_update(&mut self, _world: &mut hecs::World, _dt: f32) {
+            self.fixed_steps += 1;
+        }
+    }
+
+    fn config_30hz() -> HeadlessConfig {
+        HeadlessConfig {
+            frame_time: 1.0 / 30.0,
+            size: (32, 32),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_headless_run_uses_simulated_clock() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let output = run_app_headless_with(
+            &ctx,
+            config_30hz(),
+            Counter::default(),
+            HeadlessRun::Frames(10),
+        )
+        .unwrap();
+        assert_eq!(output.frames, 10);
+        assert_eq!(output.app.fixed_steps, 20);
+        assert!((output.elapsed_time - 10.0 / 30.0).abs() < 1e-9);
+
+        // The empty world still gets the engine clear color
+        let pixels = output.color.read_pixels(&ctx).unwrap();
+        let center = (16 * 32 + 16) * 4;
+        assert!(
+            (pixels[center] as i32 - 89).abs() <= 2,
+            "{:?}",
+            &pixels[center..center + 4]
+        );
+
+        let config = HeadlessConfig {
+            render: false,
+            ..Default::default()
+        };
+        let run = HeadlessRun::until(100, |_, resources| *resources.get::<u32>().unwrap() == 5);
+        let output = run_app_headless_with(&ctx, config, Counter::default(), run).unwrap();
+        assert_eq!(output.frames, 5);
+
+        // Game time drives the fixed loop; real time stays unscaled
+        let app = Counter {
+            time_scale: Some(0.5),
+            ..Default::default()
+        };
+        let output =
+            run_app_headless_with(&ctx, config_30hz(), app, HeadlessRun::Frames(10)).unwrap();
+        assert_eq!(output.app.fixed_steps, 10);
+        let time = output.resources.get::<Time>().unwrap();
+        assert!((time.elapsed() - 10.0 / 60.0).abs() < 1e-9);
+        assert!((time.unscaled_elapsed() - 10.0 / 30.0).abs() < 1e-9);
+
+        let run = HeadlessRun::until(3, |_, _| false);
+        assert!(
+            run_app_headless_with(&ctx, HeadlessConfig::default(), Counter::default(), run)
+                .is_err()
+        );
+    }
+
+    /// Drops one body under engine-managed physics and records the physics
+    /// clock after each frame.
+    #[cfg(feature = "physics")]
+    struct Falling {
+        paused: bool,
+        body: Option<hecs::Entity>,
+    }
+
+    #[cfg(feature = "physics")]
+    impl App for Falling {
+        fn build(&mut self, app: &mut AppBuilder) {
+            let time = app.resources.get_mut::<Time>().unwrap();
+            time.set_scale(0.5);
+            if self.paused {
+                time.pause();
+            }
+            app.insert_resource(0.0f64)
+                .add_system(Stage::Render, "physics_clock", |_, ctx| {
+                    let elapsed = ctx.physics.as_ref().unwrap().time().elapsed();
+                    *ctx.resources.get_mut::<f64>().unwrap() = elapsed;
+                });
+        }
+
+        fn init(&mut self, _ctx: &WgpuContext, world: &mut hecs::World) {
+            use crate::ecs::components::physics::RigidBody;
+            use crate::ecs::components::transform::{GlobalTransform, Transform};
+            self.body = Some(world.spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                RigidBody::new_dynamic(1.0),
+            )));
+        }
+
+        fn update(&mut self, _world: &mut hecs::World, _ctx: &SystemContext) {}
+
+        fn fixed_update(&mut self, _world: &mut hecs::World, _dt: f32) {}
+    }
+
+    #[cfg(feature = "physics")]
+    #[test]
+    fn test_engine_physics_follows_engine_clock() {
+        use crate::ecs::components::transform::Transform;
+        use crate::physics::PhysicsConfig;
+
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let config = || HeadlessConfig {
+            game_loop: GameLoopConfig::default().with_physics(PhysicsConfig::default()),
+            render: false,
+            ..config_30hz()
+        };
+        let run = |paused| {
+            let app = Falling { paused, body: None };
+            run_app_headless_with(&ctx, config(), app, HeadlessRun::Frames(10)).unwrap()
+        };
+
+        let output = run(false);
+        let elapsed = output.resources.get::<Time>().unwrap().elapsed();
+        assert!((elapsed - 10.0 / 60.0).abs() < 1e-9);
+        assert_eq!(*output.resources.get::<f64>().unwrap(), elapsed);
+        let body = output.app.body.unwrap();
+        assert!(output.world.get::<&Transform>(body).unwrap().position.y < 0.0);
+
+        // Pausing the engine clock pauses physics
+        let output = run(true);
+        assert_eq!(*output.resources.get::<f64>().unwrap(), 0.0);
+        let body = output.app.body.unwrap();
+        assert_eq!(
+            output.world.get::<&Transform>(body).unwrap().position.y,
+            0.0
+        );
+    }
+}
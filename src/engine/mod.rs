@@ -1,11 +1,32 @@
 //! Game engine module with App trait and game loop.
 //!
 //! Provides a high-level API for ECS-based applications with automatic
-//! system scheduling (transform propagation, culling, rendering).
+//! system scheduling (transform propagation, culling, rendering). Apps and
+//! [`Plugin`]s add their own systems to a staged [`Schedule`], share state
+//! through typed [`Resources`] and communicate through [`Events`] queues.
+//! [`run_app_headless`] drives the same loop without a window.
+
+mod events;
+mod headless;
+mod resources;
+mod schedule;
+
+pub use events::{EventReader, Events};
+pub use headless::{
+    run_app_headless, run_app_headless_with, HeadlessConfig, HeadlessDoneFn, HeadlessOutput,
+    HeadlessRun,
+};
+pub use resources::Resources;
+pub use schedule::{Schedule, Stage, System, SystemFn};
 
 use crate::context::WgpuContext;
+use crate::core::RenderTarget;
+use crate::time::Time;
 use crate::window::event::Event;
-use crate::window::frame_io::Viewport;
+use crate::window::frame_io::{FrameInput, Viewport};
+
+#[cfg(feature = "physics")]
+use crate::physics::{PhysicsConfig, PhysicsWorld};
 
 /// Game loop configuration.
 pub struct GameLoopConfig {
@@ -13,6 +34,19 @@ pub struct GameLoopConfig {
     pub fixed_timestep: f64,
     /// Maximum physics substeps per frame. Default: 4.
     pub max_substeps: u32,
+    /// Engine-managed physics. When set, [`run_app`] owns a [`PhysicsWorld`]
+    /// and steps it on every fixed step, before `App::fixed_update`. The
+    /// engine's `fixed_timestep`/`max_substeps` take precedence over the ones
+    /// in [`PhysicsConfig`]; `use_gpu` selects `step_gpu` with the
+    /// `gpu-physics` feature. Physics runs on the [`Time`] resource's game
+    /// time, so pausing, scaling or single-stepping it applies to physics.
+    /// Default: `None`.
+    #[cfg(feature = "physics")]
+    pub physics: Option<PhysicsConfig>,
+    /// Bodies the engine-managed GPU physics buffers hold. Fixed steps with
+    /// more bodies run on the CPU path. Default: 1024.
+    #[cfg(feature = "gpu-physics")]
+    pub gpu_physics_capacity: usize,
 }
 
 impl Default for GameLoopConfig {
@@ -20,26 +54,125 @@ impl Default for GameLoopConfig {
         Self {
             fixed_timestep: 1.0 / 60.0,
             max_substeps: 4,
+            #[cfg(feature = "physics")]
+            physics: None,
+            #[cfg(feature = "gpu-physics")]
+            gpu_physics_capacity: 1024,
         }
     }
 }
 
-/// System execution context passed to App callbacks.
+impl GameLoopConfig {
+    /// Enable engine-managed physics with the given configuration.
+    #[cfg(feature = "physics")]
+    pub fn with_physics(mut self, physics: PhysicsConfig) -> Self {
+        self.physics = Some(physics);
+        self
+    }
+
+    /// Set the body capacity of engine-managed GPU physics.
+    #[cfg(feature = "gpu-physics")]
+    pub fn with_gpu_physics_capacity(mut self, capacity: usize) -> Self {
+        self.gpu_physics_capacity = capacity;
+        self
+    }
+}
+
+/// Name of the engine transform propagation system in [`Stage::PostUpdate`].
+pub const TRANSFORM_SYSTEM: &str = "transform";
+
+/// Name of the engine frustum culling system in [`Stage::PostUpdate`], which
+/// runs after [`TRANSFORM_SYSTEM`].
+pub const CULLING_SYSTEM: &str = "culling";
+
+/// Name of the [`Stage::PreUpdate`] systems that advance each [`Events`]
+/// queue registered with [`AppBuilder::add_event`].
+pub const EVENT_UPDATE_SYSTEM: &str = "events";
+
+/// System execution context passed to App callbacks and scheduled systems.
 pub struct SystemContext<'a> {
     /// The wgpu context.
     pub ctx: &'a WgpuContext,
-    /// Time since last frame (seconds).
+    /// Game time since last frame (seconds), after the [`Time`] resource's
+    /// pause, scale and clamp. In [`Stage::FixedUpdate`] systems, the fixed
+    /// timestep.
     pub delta_time: f64,
+    /// Real time since last frame (seconds), unaffected by [`Time`].
+    pub unscaled_delta_time: f64,
     /// Fixed timestep interval (seconds).
     pub fixed_delta_time: f64,
-    /// Time since application start (seconds).
+    /// Game time since application start (seconds).
     pub elapsed_time: f64,
+    /// Real time since application start (seconds).
+    pub unscaled_elapsed_time: f64,
     /// Current viewport dimensions.
     pub viewport: Viewport,
     /// Events that occurred this frame.
     pub events: &'a [Event],
     /// The surface texture format.
     pub surface_format: wgpu::TextureFormat,
+    /// Engine resources shared by the app, plugins and systems.
+    pub resources: &'a mut Resources,
+    /// Engine-managed physics world, if enabled in [`GameLoopConfig`].
+    /// Its clock mirrors the [`Time`] resource; systems may re-initialize GPU
+    /// physics with a larger capacity.
+    #[cfg(feature = "physics")]
+    pub physics: Option<&'a mut PhysicsWorld>,
+}
+
+/// Registration access given to [`App::build`] and [`Plugin::build`].
+pub struct AppBuilder<'a> {
+    /// The wgpu context.
+    pub ctx: &'a WgpuContext,
+    /// The engine schedule.
+    pub schedule: &'a mut Schedule,
+    /// The engine resources.
+    pub resources: &'a mut Resources,
+}
+
+impl AppBuilder<'_> {
+    /// Install a plugin's systems and resources.
+    pub fn add_plugin(&mut self, plugin: impl Plugin) -> &mut Self {
+        plugin.build(self);
+        self
+    }
+
+    /// Register a system; see [`Schedule::add_system`].
+    pub fn add_system(
+        &mut self,
+        stage: Stage,
+        name: &'static str,
+        system: impl FnMut(&mut hecs::World, &mut SystemContext) + 'static,
+    ) -> &mut System {
+        self.schedule.add_system(stage, name, system)
+    }
+
+    /// Insert a resource, replacing any previous one of the same type.
+    pub fn insert_resource<T: 'static>(&mut self, value: T) -> &mut Self {
+        self.resources.insert(value);
+        self
+    }
+
+    /// Insert an [`Events<T>`] resource and advance it at the start of
+    /// every frame. Registering the same event type twice has no effect.
+    pub fn add_event<T: 'static>(&mut self) -> &mut Self {
+        if !self.resources.contains::<Events<T>>() {
+            self.resources.insert(Events::<T>::new());
+            self.schedule
+                .add_system(Stage::PreUpdate, EVENT_UPDATE_SYSTEM, |_, ctx| {
+                    if let Some(events) = ctx.resources.get_mut::<Events<T>>() {
+                        events.update();
+                    }
+                });
+        }
+        self
+    }
+}
+
+/// A reusable bundle of systems and resources.
+pub trait Plugin {
+    /// Register the plugin's systems and resources.
+    fn build(&self, app: &mut AppBuilder);
 }
 
 /// Trait for ECS-based game applications.
@@ -47,6 +180,10 @@ pub struct SystemContext<'a> {
 /// Implement this trait and pass it to [`run_app`] to run a game
 /// with automatic ECS system scheduling.
 pub trait App {
+    /// Called once on the first frame, before [`init`](Self::init). Register
+    /// systems, plugins and resources. Optional.
+    fn build(&mut self, _app: &mut AppBuilder) {}
+
     /// Called once on the first frame. Set up ECS world and load resources.
     fn init(&mut self, ctx: &WgpuContext, world: &mut hecs::World);
 
@@ -56,97 +193,308 @@ pub trait App {
     /// Called at fixed timestep intervals. Use for physics logic. Optional.
     fn fixed_update(&mut self, _world: &mut hecs::World, _dt: f32) {}
 
-    /// Called after rendering. Use for GUI and debug overlays. Optional.
+    /// Called after rendering and the [`Stage::Render`] systems. Use for GUI
+    /// and debug overlays. Optional.
     fn post_render(&mut self, _world: &mut hecs::World, _ctx: &SystemContext) {}
 }
 
 /// Run a game application with automatic ECS system scheduling.
 ///
 /// This wraps [`Window::render_loop`] and automatically runs:
-/// 1. `App::fixed_update` at fixed timestep intervals
-/// 2. `App::update` each frame
-/// 3. `transform_system` (hierarchy propagation)
-/// 4. `culling_system` (frustum culling)
-/// 5. `render_system` (draw visible entities)
-/// 6. `App::post_render`
+/// 1. [`Stage::PreUpdate`] systems
+/// 2. At fixed timestep intervals of game time: the engine-managed `PhysicsWorld` step and
+///    `transform_system` (if physics is enabled), `App::fixed_update`, then
+///    [`Stage::FixedUpdate`] systems
+/// 3. `App::update`, then [`Stage::Update`] systems
+/// 4. [`Stage::PostUpdate`] systems, including `transform_system` (hierarchy
+///    propagation, [`TRANSFORM_SYSTEM`]) and `culling_system` (frustum
+///    culling, [`CULLING_SYSTEM`])
+/// 5. `render_shadow_maps` (shadow casters of a `ShadowCaster` light),
+///    `render_offscreen_cameras` (cameras with a `RenderTexture`), then
+///    `render_system` (draw visible entities to the window), and
+///    `render_id_buffer` if an `IdBuffer` resource was inserted for GPU picking,
+///    and `render_selection_mask` if an `OutlineMask` resource was inserted
+///    for an `OutlineEffect`
+/// 6. [`Stage::Render`] systems, then `App::post_render`
+///
+/// Game time comes from the [`Time`] resource, which systems can use to
+/// pause, slow down or single-step the game. `App::build` runs on the first
+/// frame, before `App::init`. See [`run_app_headless`] to run the same loop without a window.
+///
+/// [`Window::render_loop`]: crate::window::Window::render_loop
 pub fn run_app<A: App + 'static>(
     settings: crate::window::WindowSettings,
     config: GameLoopConfig,
     app: A,
 ) -> anyhow::Result<()> {
-    use crate::core::ClearState;
-    use crate::ecs::systems::{culling_system, render_system, transform_system};
     use crate::window::{screen_target, FrameOutput, Window};
 
     let window = Window::new(settings)?;
+    window.render_loop(Engine::new(config, app), |engine, frame| {
+        let target = screen_target(&frame);
+        engine.frame(&frame, Some(&target));
+        FrameOutput::default()
+    })
+}
+
+/// Engine state shared by [`run_app`] and [`run_app_headless`].
+struct Engine<A: App> {
+    app: A,
+    world: hecs::World,
+    config: GameLoopConfig,
+    initialized: bool,
+    accumulator: f64,
+    render_buffers: Option<crate::ecs::systems::RenderBuffers>,
+    schedule: Schedule,
+    resources: Resources,
+    #[cfg(feature = "physics")]
+    physics: Option<PhysicsWorld>,
+}
+
+impl<A: App> Engine<A> {
+    fn new(config: GameLoopConfig, app: A) -> Self {
+        use crate::ecs::systems::{culling_system, transform_system};
+
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::PostUpdate, TRANSFORM_SYSTEM, |world, _| {
+            transform_system(world)
+        });
+        schedule
+            .add_system(Stage::PostUpdate, CULLING_SYSTEM, |world, _| {
+                culling_system(world)
+            })
+            .after(TRANSFORM_SYSTEM);
+
+        let mut resources = Resources::new();
+        resources.insert(Time::new());
 
-    struct EngineState<A: App> {
-        app: A,
-        world: hecs::World,
-        config: GameLoopConfig,
-        initialized: bool,
-        accumulator: f64,
+        Self {
+            #[cfg(feature = "physics")]
+            physics: config.physics.clone().map(PhysicsWorld::new),
+            app,
+            world: hecs::World::new(),
+            config,
+            initialized: false,
+            accumulator: 0.0,
+            render_buffers: None,
+            schedule,
+            resources,
+        }
     }
 
-    let state = EngineState {
-        app,
-        world: hecs::World::new(),
-        config,
-        initialized: false,
-        accumulator: 0.0,
-    };
+    /// Run one frame, drawing into `target` if given.
+    fn frame(&mut self, frame: &FrameInput, target: Option<&RenderTarget>) {
+        use crate::core::ClearState;
+        #[cfg(feature = "physics")]
+        use crate::ecs::systems::transform_system;
+        use crate::ecs::systems::{
+            render_id_buffer, render_offscreen_cameras, render_selection_mask, render_shadow_maps,
+            render_system, IdBuffer, RenderBuffers,
+        };
+        use crate::effect::OutlineMask;
 
-    window.render_loop(state, |state, frame| {
         let ctx = frame.ctx;
 
         // Initialize on first frame (GPU context is now available)
-        if !state.initialized {
-            state.app.init(ctx, &mut state.world);
-            state.initialized = true;
+        if !self.initialized {
+            #[cfg(feature = "gpu-physics")]
+            if let Some(physics) = self.physics.as_mut().filter(|p| p.config().use_gpu) {
+                if let Err(e) = physics.init_gpu(ctx, self.config.gpu_physics_capacity) {
+                    tracing::warn!("GPU physics unavailable, using CPU path: {e:#}");
+                }
+            }
+            self.app.build(&mut AppBuilder {
+                ctx,
+                schedule: &mut self.schedule,
+                resources: &mut self.resources,
+            });
+            self.app.init(ctx, &mut self.world);
+            self.initialized = true;
+        }
+
+        // Game time for this frame; changes to the Time resource made by
+        // systems apply from the next frame
+        let time = {
+            let time = self.resources.get_or_insert_with(Time::new);
+            time.advance(frame.delta_time, self.config.fixed_timestep);
+            *time
+        };
+        // Engine-managed physics follows the engine clock
+        #[cfg(feature = "physics")]
+        if let Some(physics) = &mut self.physics {
+            *physics.time_mut() = time;
         }
 
+        let mut sys_ctx = system_context(
+            frame,
+            &time,
+            time.delta(),
+            self.config.fixed_timestep,
+            &mut self.resources,
+            #[cfg(feature = "physics")]
+            self.physics.as_mut(),
+        );
+        self.schedule
+            .run(Stage::PreUpdate, &mut self.world, &mut sys_ctx);
+
         // Fixed timestep loop
-        state.accumulator += frame.delta_time;
+        self.accumulator += time.delta();
         let mut substeps = 0u32;
-        while state.accumulator >= state.config.fixed_timestep
-            && substeps < state.config.max_substeps
+        while self.accumulator >= self.config.fixed_timestep && substeps < self.config.max_substeps
         {
-            state
-                .app
-                .fixed_update(&mut state.world, state.config.fixed_timestep as f32);
-            state.accumulator -= state.config.fixed_timestep;
+            let dt = self.config.fixed_timestep as f32;
+            #[cfg(feature = "physics")]
+            if let Some(physics) = &mut self.physics {
+                step_physics(physics, &mut self.world, dt, ctx);
+                transform_system(&mut self.world);
+            }
+            self.app.fixed_update(&mut self.world, dt);
+            let mut fixed_ctx = system_context(
+                frame,
+                &time,
+                self.config.fixed_timestep,
+                self.config.fixed_timestep,
+                &mut self.resources,
+                #[cfg(feature = "physics")]
+                self.physics.as_mut(),
+            );
+            self.schedule
+                .run(Stage::FixedUpdate, &mut self.world, &mut fixed_ctx);
+            self.accumulator -= self.config.fixed_timestep;
             substeps += 1;
         }
 
         // Variable timestep update
-        let sys_ctx = SystemContext {
-            ctx,
-            delta_time: frame.delta_time,
-            fixed_delta_time: state.config.fixed_timestep,
-            elapsed_time: frame.elapsed_time,
-            viewport: frame.viewport,
-            events: &frame.events,
-            surface_format: frame.surface_format,
-        };
-        state.app.update(&mut state.world, &sys_ctx);
+        let mut sys_ctx = system_context(
+            frame,
+            &time,
+            time.delta(),
+            self.config.fixed_timestep,
+            &mut self.resources,
+            #[cfg(feature = "physics")]
+            self.physics.as_mut(),
+        );
+        self.app.update(&mut self.world, &sys_ctx);
+        self.schedule
+            .run(Stage::Update, &mut self.world, &mut sys_ctx);
 
         // ECS systems
-        transform_system(&mut state.world);
-        culling_system(&mut state.world);
+        self.schedule
+            .run(Stage::PostUpdate, &mut self.world, &mut sys_ctx);
 
-        // Rendering
-        let target = screen_target(&frame);
-        let mut encoder = ctx.create_encoder(Some("engine frame"));
-        {
-            let clear = ClearState::color_and_depth([0.1, 0.1, 0.1, 1.0], 1.0);
-            let mut pass = target.begin_render_pass(&mut encoder, clear);
-            render_system(&state.world, ctx, &mut pass);
+        if crate::core::hot_reload::hot_reload_enabled() {
+            reload_shaders(&self.world, sys_ctx.resources, self.render_buffers.as_ref());
+            #[cfg(feature = "gpu-physics")]
+            if let Some(gpu) = sys_ctx.physics.as_deref().and_then(|p| p.gpu_physics_ref()) {
+                gpu.reload_shaders();
+            }
+        }
+
+        // Rendering: shadow maps and offscreen cameras first so later passes
+        // can sample them
+        if let Some(target) = target {
+            let buffers = self
+                .render_buffers
+                .get_or_insert_with(|| RenderBuffers::new(ctx));
+            render_shadow_maps(&self.world, ctx, buffers);
+            render_offscreen_cameras(&self.world, ctx, buffers);
+
+            let mut encoder = ctx.create_encoder(Some("engine frame"));
+            {
+                let clear = ClearState::color_and_depth([0.1, 0.1, 0.1, 1.0], 1.0);
+                let mut pass = target.begin_render_pass(&mut encoder, clear);
+                let size = (target.width(), target.height());
+                render_system(&self.world, ctx, buffers, size, &mut pass);
+            }
+            ctx.submit([encoder.finish()]);
+
+            if let Some(id_buffer) = sys_ctx.resources.get_mut::<IdBuffer>() {
+                let size = (target.width(), target.height());
+                render_id_buffer(&self.world, ctx, id_buffer, size);
+            }
+            if let Some(mask) = sys_ctx.resources.get::<OutlineMask>() {
+                let size = (target.width(), target.height());
+                let depth = target.depth_view();
+                render_selection_mask(&self.world, ctx, buffers, mask, depth, size);
+            }
         }
-        ctx.submit([encoder.finish()]);
 
         // Post-render
-        state.app.post_render(&mut state.world, &sys_ctx);
+        self.schedule
+            .run(Stage::Render, &mut self.world, &mut sys_ctx);
+        self.app.post_render(&mut self.world, &sys_ctx);
+    }
+}
 
-        FrameOutput::default()
-    })
+/// Rebuild the pipelines whose shader files changed: those of every material
+/// drawn by a [`MeshRenderer`], of the [`EffectChain`] and [`IdBuffer`]
+/// resources, and of the engine's own shadow caster and selection mask passes.
+/// GPU physics pipelines are reloaded separately by the caller.
+///
+/// [`MeshRenderer`]: crate::ecs::components::rendering::MeshRenderer
+/// [`EffectChain`]: crate::effect::EffectChain
+/// [`IdBuffer`]: crate::ecs::systems::IdBuffer
+fn reload_shaders(
+    world: &hecs::World,
+    resources: &Resources,
+    buffers: Option<&crate::ecs::systems::RenderBuffers>,
+) {
+    use crate::ecs::components::rendering::MeshRenderer;
+    use crate::ecs::systems::IdBuffer;
+    use crate::effect::EffectChain;
+
+    // Shared materials are checked once
+    let mut seen = std::collections::HashSet::new();
+    for (_, renderer) in world.query::<&MeshRenderer>().iter() {
+        let material = &renderer.material.0;
+        if seen.insert(std::sync::Arc::as_ptr(material) as *const ()) {
+            material.reload_shaders();
+        }
+    }
+    if let Some(effects) = resources.get::<EffectChain>() {
+        effects.reload_shaders();
+    }
+    if let Some(id_buffer) = resources.get::<IdBuffer>() {
+        id_buffer.reload_shaders();
+    }
+    if let Some(buffers) = buffers {
+        buffers.reload_shaders();
+    }
+}
+
+/// Build the [`SystemContext`] for one frame or fixed step.
+fn system_context<'a>(
+    frame: &'a FrameInput,
+    time: &Time,
+    delta_time: f64,
+    fixed_delta_time: f64,
+    resources: &'a mut Resources,
+    #[cfg(feature = "physics")] physics: Option<&'a mut PhysicsWorld>,
+) -> SystemContext<'a> {
+    SystemContext {
+        ctx: frame.ctx,
+        delta_time,
+        unscaled_delta_time: time.unscaled_delta(),
+        fixed_delta_time,
+        elapsed_time: time.elapsed(),
+        unscaled_elapsed_time: time.unscaled_elapsed(),
+        viewport: frame.viewport,
+        events: &frame.events,
+        surface_format: frame.surface_format,
+        resources,
+        #[cfg(feature = "physics")]
+        physics,
+    }
+}
+
+/// Run one engine-managed physics step on the CPU or GPU path.
+#[cfg(feature = "physics")]
+fn step_physics(physics: &mut PhysicsWorld, world: &mut hecs::World, dt: f32, ctx: &WgpuContext) {
+    #[cfg(feature = "gpu-physics")]
+    if physics.config().use_gpu {
+        physics.step_fixed_gpu(world, dt, ctx);
+        return;
+    }
+    let _ = ctx;
+    physics.step_fixed(world, dt);
 }
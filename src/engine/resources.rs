@@ -0,0 +1,106 @@
+//! Typed resource map shared by engine systems.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Type-keyed storage for engine-wide state such as input maps, asset
+/// caches or game settings.
+///
+/// Holds at most one value per type. Systems reach it through
+/// [`SystemContext::resources`](super::SystemContext::resources).
+#[derive(Default)]
+pub struct Resources {
+    values: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Resources {
+    /// Create an empty resource map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert `value`, returning the previous resource of the same type.
+    pub fn insert<T: 'static>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .map(|old| {
+                *old.downcast::<T>()
+                    .expect("resource stored under its own type")
+            })
+    }
+
+    /// Remove and return the resource of type `T`.
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        self.values.remove(&TypeId::of::<T>()).map(|old| {
+            *old.downcast::<T>()
+                .expect("resource stored under its own type")
+        })
+    }
+
+    /// Whether a resource of type `T` is present.
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Borrow the resource of type `T`.
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.values
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Mutably borrow the resource of type `T`.
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.values
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Mutably borrow the resource of type `T`, inserting `init()` first if
+    /// it is missing.
+    pub fn get_or_insert_with<T: 'static>(&mut self, init: impl FnOnce() -> T) -> &mut T {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(init()))
+            .downcast_mut()
+            .expect("resource stored under its own type")
+    }
+
+    /// Number of stored resources.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether no resources are stored.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Score(u32);
+
+    #[test]
+    fn test_resources_are_keyed_by_type() {
+        let mut resources = Resources::new();
+        assert!(resources.insert(Score(1)).is_none());
+        assert_eq!(resources.insert(Score(2)), Some(Score(1)));
+        resources.insert(String::from("level"));
+        assert_eq!(resources.len(), 2);
+
+        resources.get_mut::<Score>().unwrap().0 += 1;
+        assert_eq!(resources.get::<Score>(), Some(&Score(3)));
+        assert_eq!(resources.get::<String>().map(String::as_str), Some("level"));
+
+        *resources.get_or_insert_with(|| 10u64) += 1;
+        assert_eq!(resources.get::<u64>(), Some(&11));
+
+        assert_eq!(resources.remove::<Score>(), Some(Score(3)));
+        assert!(!resources.contains::<Score>());
+        assert!(resources.get::<f32>().is_none());
+    }
+}
@@ -0,0 +1,249 @@
+//! System schedule with stages and ordering constraints.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use anyhow::bail;
+
+use super::SystemContext;
+
+/// Point in the frame at which a system runs.
+///
+/// Stages run in declaration order. [`FixedUpdate`](Stage::FixedUpdate) runs
+/// zero or more times per frame, once per fixed step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Before the fixed steps, e.g. input and event collection.
+    PreUpdate,
+    /// Each fixed step, after the engine physics step and `App::fixed_update`.
+    FixedUpdate,
+    /// Each frame, after `App::update`.
+    Update,
+    /// After gameplay; holds the engine transform and culling systems.
+    PostUpdate,
+    /// After the frame is drawn, before `App::post_render`.
+    Render,
+}
+
+impl Stage {
+    /// All stages in execution order.
+    pub const ALL: [Stage; 5] = [
+        Stage::PreUpdate,
+        Stage::FixedUpdate,
+        Stage::Update,
+        Stage::PostUpdate,
+        Stage::Render,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Boxed system function.
+pub type SystemFn = Box<dyn FnMut(&mut hecs::World, &mut SystemContext)>;
+
+/// A named system registered in a [`Schedule`].
+///
+/// Returned by [`Schedule::add_system`] to declare ordering constraints
+/// against other systems of the same stage. Constraints naming systems that
+/// are not registered are ignored, so plugins can order themselves against
+/// optional systems.
+pub struct System {
+    name: &'static str,
+    before: Vec<&'static str>,
+    after: Vec<&'static str>,
+    run: SystemFn,
+}
+
+impl System {
+    /// The system name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Run this system before every system called `name`.
+    pub fn before(&mut self, name: &'static str) -> &mut Self {
+        self.before.push(name);
+        self
+    }
+
+    /// Run this system after every system called `name`.
+    pub fn after(&mut self, name: &'static str) -> &mut Self {
+        self.after.push(name);
+        self
+    }
+}
+
+#[derive(Default)]
+struct StageSystems {
+    systems: Vec<System>,
+    /// Execution order, recomputed after systems are added.
+    order: Option<Vec<usize>>,
+}
+
+/// Systems grouped by [`Stage`] and ordered by their before/after
+/// constraints.
+///
+/// Unconstrained systems keep their registration order.
+#[derive(Default)]
+pub struct Schedule {
+    stages: [StageSystems; 5],
+}
+
+impl Schedule {
+    /// Create an empty schedule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `system` under `name` in `stage`.
+    pub fn add_system(
+        &mut self,
+        stage: Stage,
+        name: &'static str,
+        system: impl FnMut(&mut hecs::World, &mut SystemContext) + 'static,
+    ) -> &mut System {
+        let stage = &mut self.stages[stage.index()];
+        stage.order = None;
+        stage.systems.push(System {
+            name,
+            before: Vec::new(),
+            after: Vec::new(),
+            run: Box::new(system),
+        });
+        stage.systems.last_mut().expect("system was just pushed")
+    }
+
+    /// Whether a system called `name` is registered in any stage.
+    pub fn contains(&self, name: &str) -> bool {
+        self.stages
+            .iter()
+            .any(|stage| stage.systems.iter().any(|system| system.name == name))
+    }
+
+    /// Number of systems in `stage`.
+    pub fn len(&self, stage: Stage) -> usize {
+        self.stages[stage.index()].systems.len()
+    }
+
+    /// Whether no systems are registered.
+    pub fn is_empty(&self) -> bool {
+        self.stages.iter().all(|stage| stage.systems.is_empty())
+    }
+
+    /// Names of the systems in `stage` in execution order.
+    ///
+    /// Fails if the ordering constraints form a cycle.
+    pub fn order(&self, stage: Stage) -> anyhow::Result<Vec<&'static str>> {
+        let systems = &self.stages[stage.index()].systems;
+        Ok(sort_systems(systems)?
+            .into_iter()
+            .map(|i| systems[i].name)
+            .collect())
+    }
+
+    /// Run every system of `stage` in order.
+    ///
+    /// A cycle in the constraints is logged once and the stage falls back to
+    /// registration order.
+    pub fn run(&mut self, stage: Stage, world: &mut hecs::World, ctx: &mut SystemContext) {
+        let stage_systems = &mut self.stages[stage.index()];
+        let order = stage_systems.order.get_or_insert_with(|| {
+            sort_systems(&stage_systems.systems).unwrap_or_else(|e| {
+                tracing::error!("{stage:?} schedule: {e:#}, using registration order");
+                (0..stage_systems.systems.len()).collect()
+            })
+        });
+        for &i in order.iter() {
+            (stage_systems.systems[i].run)(world, ctx);
+        }
+    }
+}
+
+/// Topologically sort `systems` by their constraints, preferring lower
+/// registration indices among systems that are ready to run.
+fn sort_systems(systems: &[System]) -> anyhow::Result<Vec<usize>> {
+    let n = systems.len();
+    let mut successors = vec![Vec::new(); n];
+    let mut in_degree = vec![0usize; n];
+    let mut add_edge = |from: usize, to: usize| {
+        if from != to {
+            successors[from].push(to);
+            in_degree[to] += 1;
+        }
+    };
+    for (i, system) in systems.iter().enumerate() {
+        for (j, other) in systems.iter().enumerate() {
+            if system.before.contains(&other.name) {
+                add_edge(i, j);
+            }
+            if system.after.contains(&other.name) {
+                add_edge(j, i);
+            }
+        }
+    }
+
+    let mut ready: BinaryHeap<_> = (0..n).filter(|&i| in_degree[i] == 0).map(Reverse).collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(i)) = ready.pop() {
+        order.push(i);
+        for &j in &successors[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                ready.push(Reverse(j));
+            }
+        }
+    }
+
+    if order.len() < n {
+        let cycle: Vec<_> = (0..n)
+            .filter(|&i| in_degree[i] > 0)
+            .map(|i| systems[i].name)
+            .collect();
+        bail!("ordering cycle involving systems {cycle:?}");
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop(_: &mut hecs::World, _: &mut SystemContext) {}
+
+    #[test]
+    fn test_schedule_orders_by_constraints() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::Update, "render_prep", noop);
+        schedule
+            .add_system(Stage::Update, "ai", noop)
+            .before("movement");
+        schedule.add_system(Stage::Update, "movement", noop);
+        schedule
+            .add_system(Stage::Update, "input", noop)
+            .before("ai")
+            .after("missing");
+        schedule.add_system(Stage::PostUpdate, "late", noop);
+
+        assert_eq!(
+            schedule.order(Stage::Update).unwrap(),
+            ["render_prep", "input", "ai", "movement"]
+        );
+        assert_eq!(schedule.order(Stage::PostUpdate).unwrap(), ["late"]);
+        assert_eq!(schedule.len(Stage::FixedUpdate), 0);
+        assert!(schedule.contains("late"));
+    }
+
+    #[test]
+    fn test_schedule_rejects_cycles() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(Stage::Update, "a", noop).after("b");
+        schedule.add_system(Stage::Update, "b", noop).after("a");
+        schedule.add_system(Stage::Update, "c", noop);
+
+        let err = schedule.order(Stage::Update).unwrap_err().to_string();
+        assert!(err.contains("\"a\"") && err.contains("\"b\""), "{err}");
+        assert!(!err.contains("\"c\""), "{err}");
+    }
+}
@@ -11,16 +11,21 @@
 //! 3. **compute** - Compute shader utilities
 //! 4. **renderer** - High-level rendering (cameras, materials, geometry, lights)
 //! 5. **physics** - Rigid body simulation, collision detection (feature = "physics")
-//! 6. **ecs** - hecs ECS integration (feature = "ecs")
+//! 6. **ecs** - hecs ECS integration (feature = "ecs"), RON scene files
+//!    (feature = "scene")
 //! 7. **engine** - Game loop with App trait (feature = "engine")
 //! 8. **window** - Window management with winit (feature = "window")
 //! 9. **gui** - Text rendering with glyphon (feature = "gui")
 //! 10. **urdf** - URDF robot model loading
+//! 11. **time** - Game clock with pause, time scale and single-step
+//! 12. **assets** - Asset store with handles, path dedup and background loading
 
+pub mod assets;
 pub mod context;
 pub mod core;
 pub mod effect;
 pub mod renderer;
+pub mod time;
 pub mod urdf;
 
 #[cfg(feature = "window")]
@@ -41,13 +46,14 @@ pub mod engine;
 pub mod physics;
 
 // Re-export commonly used types
+pub use assets::{AssetId, AssetState, Assets, Handle};
 pub use context::WgpuContext;
 
 pub use core::{
-    BlendState, ClearState, ComputePipelineBuilder, CullState, DepthState, DepthTexture,
+    BlendState, ClearState, ComputePipelineBuilder, CullState, DepthState, DepthTexture, HotShader,
     IndexBuffer, InstanceBuffer, InstanceData, PipelineBuilder, RawUniformBuffer, RenderTarget,
-    StorageBuffer, Texture2D, Texture2DArray, TextureCubeMap, UniformBuffer, VertexBuffer, VertexP,
-    VertexPC, VertexPN, VertexPNUC,
+    ShaderFile, StorageBuffer, Texture2D, Texture2DArray, TextureCubeMap, UniformBuffer,
+    VertexBuffer, VertexP, VertexPC, VertexPN, VertexPNUC,
 };
 
 #[cfg(feature = "window")]
@@ -56,18 +62,24 @@ pub use renderer::{
 };
 
 pub use renderer::{
-    Aabb, AmbientLight, Attenuation, Axes, BoundingBoxMesh, Camera, Circle, ColorMaterial,
-    DepthMaterial, DirectionalLight, DirectionalShadow, Frustum, FrustumCuller, Geometry, Gm,
-    GridMaterial, InstancedMesh, Intersection, Light, LineMaterial, LineStrip, Lines, Material,
-    Mesh, ModelUniform, NormalMaterial, Object, ParticleData, ParticleSystem, PbrMaterial,
-    PhongMaterial, Plane, PointLight, PositionMaterial, Projection, Rectangle, ShadowConfig,
-    ShadowMap, ShadowUniform, Skybox, SpotLight, SpriteMaterial, Sprites, Terrain, TerrainLod,
-    TerrainMaterial, TerrainUniform, UVMaterial, UnlitMaterial,
+    Aabb, AmbientLight, Attenuation, Axes, BoundingBoxMesh, Camera, CameraUniformRing, Circle,
+    ColorMaterial, DepthMaterial, DirectionalLight, DirectionalShadow, Frustum, FrustumCuller,
+    Geometry, Gm, GpuLight, GridMaterial, InstancedMesh, Intersection, Light, LineMaterial,
+    LineStrip, Lines, Material, Mesh, ModelUniform, ModelUniformRing, NormalMaterial, Object,
+    ParticleData, ParticleSystem, PbrMaterial, PhongMaterial, Plane, PointLight, PositionMaterial,
+    Projection, Ray, Rectangle, SceneLighting, ShadowConfig, ShadowMap, ShadowUniform, Skybox,
+    SpotLight, SpriteMaterial, Sprites, Terrain, TerrainLod, TerrainMaterial, TerrainUniform,
+    UVMaterial, UniformRing, UnlitMaterial,
 };
 
+pub use time::Time;
+
 pub use urdf::{RobotModel, UrdfLoader};
 
-pub use effect::{CopyEffect, Effect, EffectChain, FogEffect, FogMode, FullscreenQuad, FxaaEffect};
+pub use effect::{
+    CopyEffect, Effect, EffectChain, FogEffect, FogMode, FullscreenQuad, FxaaEffect, OutlineEffect,
+    OutlineMask,
+};
 
 #[cfg(feature = "window")]
 pub use window::{
@@ -84,10 +96,13 @@ pub use compute::{compute_workgroup_count, read_back, ComputeDispatcher};
 pub use ecs::prelude::*;
 
 #[cfg(feature = "engine")]
-pub use engine::{run_app, App, GameLoopConfig, SystemContext};
+pub use engine::{
+    run_app, run_app_headless, App, AppBuilder, EventReader, Events, GameLoopConfig,
+    HeadlessConfig, HeadlessOutput, HeadlessRun, Plugin, Resources, Schedule, Stage, SystemContext,
+};
 
 #[cfg(feature = "physics")]
-pub use physics::{PhysicsConfig, PhysicsWorld};
+pub use physics::{PhysicsConfig, PhysicsStats, PhysicsWorld};
 
 // Re-export glam for convenience
 pub use glam;
@@ -5,12 +5,13 @@ use std::collections::HashMap;
 use glam::Vec3;
 
 use crate::ecs::components::physics::{Collider, RigidBody, RigidBodyType};
-use crate::ecs::components::transform::GlobalTransform;
+use crate::ecs::components::transform::{GlobalTransform, Parent};
 
-use super::collider::PhysicsAabb;
+use super::collider::{collider_transform, PhysicsAabb};
 
 type CellKey = (i32, i32, i32);
-type CellEntry = (hecs::Entity, PhysicsAabb, RigidBodyType);
+/// Collider entity, its AABB, and the type and entity of its body.
+type CellEntry = (hecs::Entity, PhysicsAabb, RigidBodyType, hecs::Entity);
 
 /// Spatial hash grid broadphase for O(n) average-case pair detection.
 pub struct SpatialHashGrid {
@@ -43,42 +44,57 @@ impl SpatialHashGrid {
         )
     }
 
-    /// Find all pairs of entities whose AABBs overlap.
+    /// Find all pairs of collider entities whose AABBs overlap.
     ///
-    /// Only returns pairs where at least one entity is dynamic.
+    /// Only returns pairs where at least one body is dynamic, and never two
+    /// parts of the same compound body (see
+    /// [`body_of`](super::collider::body_of)).
     pub fn find_pairs(&mut self, world: &hecs::World) -> Vec<(hecs::Entity, hecs::Entity)> {
         self.cells.clear();
 
         // Collect all entries and determine max AABB size for cell sizing
-        let mut entries: Vec<(hecs::Entity, PhysicsAabb, RigidBodyType)> = Vec::new();
+        let mut entries: Vec<CellEntry> = Vec::new();
         let mut max_extent: f32 = 0.0;
+        let mut push =
+            |entity, body, collider: &Collider, transform: GlobalTransform, body_type| {
+                let mut adjusted_transform = transform;
+                if collider.offset != Vec3::ZERO {
+                    adjusted_transform.0 *= glam::Mat4::from_translation(collider.offset);
+                }
+                let aabb = collider.shape.compute_aabb(&adjusted_transform);
+                max_extent = max_extent.max((aabb.max - aabb.min).max_element());
+                entries.push((entity, aabb, body_type, body));
+            };
 
         for (entity, (collider, transform, rb)) in world
             .query::<(&Collider, &GlobalTransform, &RigidBody)>()
             .iter()
         {
+            if !collider.is_sensor {
+                push(entity, entity, collider, *transform, rb.body_type);
+            }
+        }
+        // Parts of compound bodies
+        for (entity, (collider, parent)) in world
+            .query::<hecs::Without<(&Collider, &Parent), &RigidBody>>()
+            .iter()
+        {
+            let Ok(rb) = world.get::<&RigidBody>(parent.0) else {
+                continue;
+            };
             if collider.is_sensor {
                 continue;
             }
-            let mut adjusted_transform = *transform;
-            if collider.offset != Vec3::ZERO {
-                adjusted_transform.0 *= glam::Mat4::from_translation(collider.offset);
+            if let Some((body, transform)) = collider_transform(world, entity) {
+                push(entity, body, collider, transform, rb.body_type);
             }
-            let aabb = collider.shape.compute_aabb(&adjusted_transform);
-
-            let extent = (aabb.max - aabb.min).max_element();
-            if extent > max_extent {
-                max_extent = extent;
-            }
-
-            entries.push((entity, aabb, rb.body_type));
         }
 
         // Set cell size to 2x the max AABB extent (minimum 1.0)
         self.cell_size = (max_extent * 2.0).max(1.0);
 
         // Insert entries into cells
-        for &(entity, ref aabb, body_type) in &entries {
+        for &(entity, ref aabb, body_type, body) in &entries {
             let min_cell = self.cell_coords(aabb.min);
             let max_cell = self.cell_coords(aabb.max);
 
@@ -88,7 +104,7 @@ impl SpatialHashGrid {
                         self.cells
                             .entry((cx, cy, cz))
                             .or_default()
-                            .push((entity, *aabb, body_type));
+                            .push((entity, *aabb, body_type, body));
                     }
                 }
             }
@@ -101,11 +117,13 @@ impl SpatialHashGrid {
         for cell in self.cells.values() {
             for i in 0..cell.len() {
                 for j in (i + 1)..cell.len() {
-                    let (entity_a, ref aabb_a, type_a) = cell[i];
-                    let (entity_b, ref aabb_b, type_b) = cell[j];
+                    let (entity_a, ref aabb_a, type_a, body_a) = cell[i];
+                    let (entity_b, ref aabb_b, type_b, body_b) = cell[j];
 
-                    // Skip static-static pairs
-                    if type_a == RigidBodyType::Static && type_b == RigidBodyType::Static {
+                    // Skip static-static pairs and parts of the same body
+                    if type_a == RigidBodyType::Static && type_b == RigidBodyType::Static
+                        || body_a == body_b
+                    {
                         continue;
                     }
 
@@ -140,99 +158,33 @@ mod tests {
     use super::*;
     use crate::ecs::components::physics::{ColliderShape, RigidBody};
     use crate::ecs::components::transform::{GlobalTransform, Transform};
+    #[cfg(feature = "scene")]
+    use crate::ecs::scene::spawn_fixture;
     use glam::Mat4;
 
     #[test]
+    #[cfg(feature = "scene")]
     fn test_broadphase_overlapping() {
-        let mut world = hecs::World::new();
-
-        // Two overlapping spheres
-        let _a = world.spawn((
-            Transform::from_position(Vec3::new(0.0, 0.0, 0.0)),
-            GlobalTransform(Mat4::IDENTITY),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 1.0 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
-        let _b = world.spawn((
-            Transform::from_position(Vec3::new(1.0, 0.0, 0.0)),
-            GlobalTransform(Mat4::from_translation(Vec3::new(1.0, 0.0, 0.0))),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 1.0 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
+        let (world, _) =
+            spawn_fixture(include_str!("../../tests/fixtures/overlapping_spheres.ron"));
         let mut broadphase = SpatialHashGrid::new();
         let pairs = broadphase.find_pairs(&world);
         assert_eq!(pairs.len(), 1);
     }
 
     #[test]
+    #[cfg(feature = "scene")]
     fn test_broadphase_no_overlap() {
-        let mut world = hecs::World::new();
-
-        // Two far apart spheres
-        let _a = world.spawn((
-            Transform::from_position(Vec3::ZERO),
-            GlobalTransform(Mat4::IDENTITY),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 0.5 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
-        let _b = world.spawn((
-            Transform::from_position(Vec3::new(10.0, 0.0, 0.0)),
-            GlobalTransform(Mat4::from_translation(Vec3::new(10.0, 0.0, 0.0))),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 0.5 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
+        let (world, _) = spawn_fixture(include_str!("../../tests/fixtures/separated_spheres.ron"));
         let mut broadphase = SpatialHashGrid::new();
         let pairs = broadphase.find_pairs(&world);
         assert!(pairs.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "scene")]
     fn test_broadphase_static_static_skipped() {
-        let mut world = hecs::World::new();
-
-        // Two overlapping static bodies - should NOT be returned
-        world.spawn((
-            Transform::identity(),
-            GlobalTransform(Mat4::IDENTITY),
-            RigidBody::new_static(),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 1.0 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
-        world.spawn((
-            Transform::identity(),
-            GlobalTransform(Mat4::IDENTITY),
-            RigidBody::new_static(),
-            Collider {
-                shape: ColliderShape::Sphere { radius: 1.0 },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
+        let (world, _) = spawn_fixture(include_str!("../../tests/fixtures/static_spheres.ron"));
         let mut broadphase = SpatialHashGrid::new();
         let pairs = broadphase.find_pairs(&world);
         assert!(pairs.is_empty());
@@ -254,6 +206,7 @@ mod tests {
                         shape: ColliderShape::Sphere { radius: 1.0 },
                         offset: Vec3::ZERO,
                         is_sensor: false,
+                        material: None,
                     },
                 ));
             }
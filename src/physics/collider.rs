@@ -2,8 +2,8 @@
 
 use glam::{Mat4, Vec3};
 
-use crate::ecs::components::physics::ColliderShape;
-use crate::ecs::components::transform::GlobalTransform;
+use crate::ecs::components::physics::{Collider, ColliderShape, RigidBody};
+use crate::ecs::components::transform::{GlobalTransform, Parent, Transform};
 
 /// Axis-aligned bounding box for broadphase collision detection.
 #[derive(Debug, Clone, Copy)]
@@ -172,6 +172,79 @@ impl ColliderShape {
     }
 }
 
+impl ColliderShape {
+    /// Volume of the shape in local space (unscaled).
+    ///
+    /// Convex hulls are approximated by half their bounding box volume.
+    pub fn volume(&self) -> f32 {
+        use std::f32::consts::PI;
+        match self {
+            ColliderShape::Sphere { radius } => 4.0 / 3.0 * PI * radius.powi(3),
+            ColliderShape::Box { half_extents } => {
+                8.0 * half_extents.x * half_extents.y * half_extents.z
+            }
+            ColliderShape::Capsule {
+                radius,
+                half_height,
+            } => PI * radius * radius * (2.0 * half_height + 4.0 / 3.0 * radius),
+            ColliderShape::Cylinder {
+                radius,
+                half_height,
+            } => PI * radius * radius * 2.0 * half_height,
+            ColliderShape::ConvexHull { points } => {
+                if points.is_empty() {
+                    return 0.0;
+                }
+                let mut min = Vec3::splat(f32::MAX);
+                let mut max = Vec3::splat(f32::MIN);
+                for p in points {
+                    min = min.min(*p);
+                    max = max.max(*p);
+                }
+                let size = max - min;
+                0.5 * size.x * size.y * size.z
+            }
+        }
+    }
+
+    /// Test whether a world-space point lies inside the shape.
+    ///
+    /// Convex hulls are tested against their local bounding box.
+    pub fn contains_point(&self, point: Vec3, transform: &GlobalTransform) -> bool {
+        let local = transform.0.inverse().transform_point3(point);
+        match self {
+            ColliderShape::Sphere { radius } => local.length_squared() <= radius * radius,
+            ColliderShape::Box { half_extents } => local.abs().cmple(*half_extents).all(),
+            ColliderShape::Capsule {
+                radius,
+                half_height,
+            } => {
+                let y = local.y.clamp(-*half_height, *half_height);
+                (local - Vec3::new(0.0, y, 0.0)).length_squared() <= radius * radius
+            }
+            ColliderShape::Cylinder {
+                radius,
+                half_height,
+            } => {
+                local.y.abs() <= *half_height
+                    && local.x * local.x + local.z * local.z <= radius * radius
+            }
+            ColliderShape::ConvexHull { points } => {
+                if points.is_empty() {
+                    return false;
+                }
+                let mut min = Vec3::splat(f32::MAX);
+                let mut max = Vec3::splat(f32::MIN);
+                for p in points {
+                    min = min.min(*p);
+                    max = max.max(*p);
+                }
+                local.cmpge(min).all() && local.cmple(max).all()
+            }
+        }
+    }
+}
+
 /// Compute world-space AABB from local half-extents and a transform matrix.
 #[inline]
 fn aabb_from_extents(half_extents: Vec3, mat: Mat4) -> PhysicsAabb {
@@ -190,6 +263,51 @@ fn aabb_from_extents(half_extents: Vec3, mat: Mat4) -> PhysicsAabb {
     }
 }
 
+/// The rigid body a collider entity moves with.
+///
+/// A collider on an entity without a `RigidBody` of its own whose `Parent`
+/// has one is a part of the parent's compound body. Anything else is its own
+/// body.
+pub fn body_of(world: &hecs::World, collider: hecs::Entity) -> hecs::Entity {
+    if world.satisfies::<&RigidBody>(collider).unwrap_or(false) {
+        return collider;
+    }
+    match world.get::<&Parent>(collider) {
+        Ok(parent) if world.satisfies::<&RigidBody>(parent.0).unwrap_or(false) => parent.0,
+        _ => collider,
+    }
+}
+
+/// The body of a collider entity and the collider's world transform, before
+/// its `offset`.
+///
+/// Parts of a compound body are placed by their local `Transform` relative to
+/// the body's `GlobalTransform`, so they follow the body within a step,
+/// before `transform_system` propagates the hierarchy.
+pub fn collider_transform(
+    world: &hecs::World,
+    collider: hecs::Entity,
+) -> Option<(hecs::Entity, GlobalTransform)> {
+    let body = body_of(world, collider);
+    if body == collider {
+        let transform = *world.get::<&GlobalTransform>(collider).ok()?;
+        return Some((body, transform));
+    }
+    let body_transform = world.get::<&GlobalTransform>(body).ok()?.0;
+    let local = world
+        .get::<&Transform>(collider)
+        .map_or(Mat4::IDENTITY, |t| t.to_matrix());
+    Some((body, GlobalTransform(body_transform * local)))
+}
+
+/// Whether any rigid body has colliders on child entities.
+pub fn has_compound_parts(world: &hecs::World) -> bool {
+    world
+        .query::<hecs::Without<(&Collider, &Parent), &RigidBody>>()
+        .iter()
+        .any(|(_, (_, parent))| world.satisfies::<&RigidBody>(parent.0).unwrap_or(false))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +355,20 @@ mod tests {
         assert!(!a.overlaps(&c));
     }
 
+    #[test]
+    fn test_contains_point() {
+        let shape = ColliderShape::Box {
+            half_extents: Vec3::new(1.0, 2.0, 3.0),
+        };
+        let transform = GlobalTransform(Mat4::from_translation(Vec3::new(10.0, 0.0, 0.0)));
+        assert!(shape.contains_point(Vec3::new(10.5, 1.5, -2.5), &transform));
+        assert!(!shape.contains_point(Vec3::new(8.5, 0.0, 0.0), &transform));
+
+        let sphere = ColliderShape::Sphere { radius: 1.0 };
+        assert!(sphere.contains_point(Vec3::new(0.5, 0.5, 0.5), &GlobalTransform::default()));
+        assert!(!sphere.contains_point(Vec3::new(1.0, 1.0, 0.0), &GlobalTransform::default()));
+    }
+
     #[test]
     fn test_sphere_support() {
         let shape = ColliderShape::Sphere { radius: 2.0 };
@@ -4,6 +4,14 @@ use std::collections::HashMap;
 
 use glam::Vec3;
 
+use crate::ecs::components::physics::{
+    AnisotropicFriction, Collider, CombineMode, PhysicsMaterial, RigidBody,
+};
+use crate::ecs::components::transform::GlobalTransform;
+
+use super::collider::body_of;
+use super::solver::tangent_basis;
+
 /// Information about a single contact between two shapes.
 #[derive(Debug, Clone, Copy)]
 pub struct ContactInfo {
@@ -28,7 +36,90 @@ pub struct ContactPoint {
     pub tangent_impulse: [f32; 2],
 }
 
-/// A collection of contact points between two entities.
+/// Combined surface properties for a pair of colliders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContactMaterial {
+    pub static_friction: f32,
+    pub dynamic_friction: f32,
+    pub restitution: f32,
+    pub rolling_friction: f32,
+    pub spinning_friction: f32,
+    /// World-space primary friction direction for anisotropic friction.
+    pub friction_direction: Option<Vec3>,
+    /// Friction multipliers along and across `friction_direction`.
+    pub friction_scale: [f32; 2],
+}
+
+impl Default for ContactMaterial {
+    fn default() -> Self {
+        Self::combine(&PhysicsMaterial::default(), &PhysicsMaterial::default())
+    }
+}
+
+impl ContactMaterial {
+    /// Combine two materials using their combine modes.
+    pub fn combine(a: &PhysicsMaterial, b: &PhysicsMaterial) -> Self {
+        let friction = CombineMode::resolve(a.friction_combine, b.friction_combine);
+        let restitution = CombineMode::resolve(a.restitution_combine, b.restitution_combine);
+        Self {
+            static_friction: friction.combine(a.static_friction, b.static_friction),
+            dynamic_friction: friction.combine(a.dynamic_friction, b.dynamic_friction),
+            restitution: restitution.combine(a.restitution, b.restitution),
+            rolling_friction: friction.combine(a.rolling_friction, b.rolling_friction),
+            spinning_friction: friction.combine(a.spinning_friction, b.spinning_friction),
+            friction_direction: None,
+            friction_scale: [1.0, 1.0],
+        }
+    }
+
+    /// Resolve the combined material for two collider entities.
+    ///
+    /// Each side uses the `material` of its `Collider` if set, otherwise the
+    /// `friction`/`restitution` of its body's `RigidBody` (the parent's for a
+    /// part of a compound body). An `AnisotropicFriction` on either side (A
+    /// first), on the collider or else its body, sets the friction directions.
+    pub fn between(world: &hecs::World, a: hecs::Entity, b: hecs::Entity) -> Self {
+        let mut material =
+            Self::combine(&Self::material_of(world, a), &Self::material_of(world, b));
+        if let Some((direction, scale)) =
+            Self::anisotropy_of(world, a).or_else(|| Self::anisotropy_of(world, b))
+        {
+            material.friction_direction = Some(direction);
+            material.friction_scale = scale;
+        }
+        material
+    }
+
+    fn anisotropy_of(world: &hecs::World, entity: hecs::Entity) -> Option<(Vec3, [f32; 2])> {
+        let entity = [entity, body_of(world, entity)]
+            .into_iter()
+            .find(|e| world.satisfies::<&AnisotropicFriction>(*e).unwrap_or(false))?;
+        let aniso = world.get::<&AnisotropicFriction>(entity).ok()?;
+        let direction = world
+            .get::<&GlobalTransform>(entity)
+            .map(|t| t.0.transform_vector3(aniso.direction))
+            .unwrap_or(aniso.direction)
+            .try_normalize()?;
+        Some((direction, [aniso.primary_scale, aniso.secondary_scale]))
+    }
+
+    fn material_of(world: &hecs::World, entity: hecs::Entity) -> PhysicsMaterial {
+        if let Some(material) = world
+            .get::<&Collider>(entity)
+            .ok()
+            .and_then(|collider| collider.material)
+        {
+            return material;
+        }
+        world
+            .get::<&RigidBody>(body_of(world, entity))
+            .map(|rb| PhysicsMaterial::from_rigid_body(&rb))
+            .unwrap_or_default()
+    }
+}
+
+/// A collection of contact points between two colliders. `entity_a` and
+/// `entity_b` are the rigid bodies the colliders belong to.
 #[derive(Debug, Clone)]
 pub struct ContactManifold {
     pub entity_a: hecs::Entity,
@@ -36,6 +127,8 @@ pub struct ContactManifold {
     /// Contact normal (from A to B).
     pub normal: Vec3,
     pub contacts: Vec<ContactPoint>,
+    /// Combined surface material of both colliders.
+    pub material: ContactMaterial,
 }
 
 /// Cached contact data for warm-starting the solver.
@@ -45,8 +138,10 @@ struct CachedContact {
     position: Vec3,
     /// Accumulated normal impulse from previous frame.
     normal_impulse: f32,
-    /// Accumulated tangent impulses from previous frame.
-    tangent_impulse: [f32; 2],
+    /// Accumulated friction impulse from previous frame in world space, as
+    /// applied to the second entity of the pair key. The tangent basis and
+    /// the pair order of a manifold may change between frames.
+    friction_impulse: Vec3,
 }
 
 /// Maximum distance squared for matching contacts across frames.
@@ -54,7 +149,8 @@ const CONTACT_MATCH_THRESHOLD_SQ: f32 = 0.02 * 0.02;
 
 /// Cache of contact impulses for warm-starting the constraint solver.
 ///
-/// Stores accumulated impulses from the previous frame keyed by entity pair.
+/// Stores accumulated impulses from the previous frame keyed by body pair;
+/// the manifolds of several parts of a compound body share one entry.
 /// On each new frame, current contacts are matched against cached contacts
 /// by position proximity, and matching impulses are applied before the solver
 /// iterates, greatly improving convergence.
@@ -75,6 +171,8 @@ impl ContactCache {
         for manifold in manifolds.iter_mut() {
             let key = Self::pair_key(manifold.entity_a, manifold.entity_b);
             if let Some(cached) = self.cache.get(&key) {
+                let tangents = tangent_basis(manifold.normal, manifold.material.friction_direction);
+                let sign = Self::key_sign(manifold, key);
                 for contact in &mut manifold.contacts {
                     // Find matching cached contact by position proximity
                     if let Some(cc) = cached.iter().min_by(|a, b| {
@@ -84,8 +182,10 @@ impl ContactCache {
                     }) {
                         let dist_sq = (cc.position - contact.position).length_squared();
                         if dist_sq < CONTACT_MATCH_THRESHOLD_SQ {
+                            let friction = cc.friction_impulse * sign;
                             contact.normal_impulse = cc.normal_impulse;
-                            contact.tangent_impulse = cc.tangent_impulse;
+                            contact.tangent_impulse =
+                                [friction.dot(tangents[0]), friction.dot(tangents[1])];
                         }
                     }
                 }
@@ -98,16 +198,29 @@ impl ContactCache {
         self.cache.clear();
         for manifold in manifolds {
             let key = Self::pair_key(manifold.entity_a, manifold.entity_b);
+            let tangents = tangent_basis(manifold.normal, manifold.material.friction_direction);
+            let sign = Self::key_sign(manifold, key);
             let contacts: Vec<CachedContact> = manifold
                 .contacts
                 .iter()
                 .map(|c| CachedContact {
                     position: c.position,
                     normal_impulse: c.normal_impulse,
-                    tangent_impulse: c.tangent_impulse,
+                    friction_impulse: (tangents[0] * c.tangent_impulse[0]
+                        + tangents[1] * c.tangent_impulse[1])
+                        * sign,
                 })
                 .collect();
-            self.cache.insert(key, contacts);
+            self.cache.entry(key).or_default().extend(contacts);
+        }
+    }
+
+    /// 1 if the manifold's B is the second entity of `key`, else -1.
+    fn key_sign(manifold: &ContactManifold, key: (hecs::Entity, hecs::Entity)) -> f32 {
+        if manifold.entity_b == key.1 {
+            1.0
+        } else {
+            -1.0
         }
     }
 
@@ -120,3 +233,112 @@ impl ContactCache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_modes() {
+        assert_eq!(CombineMode::Average.combine(0.2, 0.6), 0.4);
+        assert_eq!(CombineMode::Min.combine(0.2, 0.6), 0.2);
+        assert_eq!(CombineMode::Max.combine(0.2, 0.6), 0.6);
+        assert!((CombineMode::Multiply.combine(0.2, 0.6) - 0.12).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_combine_mode_priority() {
+        // Ice (Min) against rubber (Average) stays slippery.
+        let m = ContactMaterial::combine(&PhysicsMaterial::ice(), &PhysicsMaterial::rubber());
+        assert_eq!(m.dynamic_friction, 0.03);
+        // Rubber restitution uses Max.
+        assert_eq!(m.restitution, 0.8);
+    }
+
+    #[test]
+    fn test_material_falls_back_to_rigid_body() {
+        let mut world = hecs::World::new();
+        let mut rb = RigidBody::new_dynamic(1.0);
+        rb.friction = 0.2;
+        rb.restitution = 0.1;
+        let a = world.spawn((rb,));
+        let b = world.spawn((Collider {
+            material: Some(PhysicsMaterial::new(0.6, 0.5)),
+            ..Default::default()
+        },));
+
+        let m = ContactMaterial::between(&world, a, b);
+        assert!((m.static_friction - 0.4).abs() < 1e-6);
+        assert!((m.dynamic_friction - 0.4).abs() < 1e-6);
+        assert!((m.restitution - 0.3).abs() < 1e-6);
+        assert!(m.friction_direction.is_none());
+    }
+
+    #[test]
+    fn test_warm_start_survives_normal_sign_flip() {
+        use crate::physics::solver::solve_contacts;
+
+        // A box resting on the ground, pushed sideways below its static
+        // friction limit every frame.
+        let mut world = hecs::World::new();
+        let ground = world.spawn((GlobalTransform::default(), RigidBody::new_static()));
+        let body = world.spawn((
+            GlobalTransform(glam::Mat4::from_translation(Vec3::new(0.0, 0.5, 0.0))),
+            RigidBody::new_dynamic(1.0),
+        ));
+        let push = Vec3::new(2.0, -9.81, 1.0) / 60.0;
+        let manifold = |a, b, normal| ContactManifold {
+            entity_a: a,
+            entity_b: b,
+            normal,
+            contacts: vec![ContactPoint {
+                position: Vec3::ZERO,
+                penetration: 0.0,
+                normal_impulse: 0.0,
+                tangent_impulse: [0.0; 2],
+            }],
+            material: ContactMaterial::default(),
+        };
+
+        let mut cache = ContactCache::new();
+        for _ in 0..10 {
+            world.get::<&mut RigidBody>(body).unwrap().linear_velocity += push;
+            let mut manifolds = [manifold(ground, body, Vec3::Y)];
+            cache.warm_start(&mut manifolds);
+            solve_contacts(&mut manifolds, &mut world, 8);
+            cache.update(&manifolds);
+        }
+        world.get::<&mut RigidBody>(body).unwrap().angular_velocity = Vec3::ZERO;
+        world.get::<&mut RigidBody>(body).unwrap().linear_velocity = push;
+
+        // Swapped pair: the normal flips sign, and with it glam's tangent
+        // basis. Warm starting alone must cancel the push again.
+        let mut manifolds = [manifold(body, ground, -Vec3::Y)];
+        cache.warm_start(&mut manifolds);
+        solve_contacts(&mut manifolds, &mut world, 0);
+        let velocity = world.get::<&RigidBody>(body).unwrap().linear_velocity;
+        assert!(
+            velocity.length() < 0.1 * push.length(),
+            "velocity = {velocity}"
+        );
+    }
+
+    #[test]
+    fn test_anisotropy_uses_world_direction() {
+        let mut world = hecs::World::new();
+        let rotation = glam::Mat4::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let a = world.spawn((
+            GlobalTransform(rotation),
+            AnisotropicFriction::new(Vec3::X, 1.0, 0.1),
+        ));
+        let b = world.spawn((Collider {
+            material: Some(PhysicsMaterial::default()),
+            ..Default::default()
+        },));
+
+        let m = ContactMaterial::between(&world, b, a);
+        let dir = m.friction_direction.unwrap();
+        assert!((dir - Vec3::NEG_Z).length() < 1e-5, "dir = {dir}");
+        assert_eq!(m.friction_scale, [1.0, 0.1]);
+    }
+}
@@ -0,0 +1,434 @@
+//! Force field application (regional gravity, attractors, vortices, drag, buoyancy).
+
+use glam::Vec3;
+
+use crate::ecs::components::physics::{
+    Collider, ColliderShape, ForceField, ForceFieldKind, RigidBody, RigidBodyType, SleepInfo,
+    SleepState,
+};
+use crate::ecs::components::transform::GlobalTransform;
+
+use super::collider::PhysicsAabb;
+
+/// A force field's world-space volume, collected before iterating bodies.
+struct FieldVolume {
+    entity: hecs::Entity,
+    kind: ForceFieldKind,
+    transform: GlobalTransform,
+    /// `None` for unbounded fields.
+    shape: Option<(ColliderShape, PhysicsAabb)>,
+}
+
+impl FieldVolume {
+    fn origin(&self) -> Vec3 {
+        self.transform.0.transform_point3(Vec3::ZERO)
+    }
+
+    fn contains(&self, point: Vec3) -> bool {
+        match &self.shape {
+            Some((shape, _)) => shape.contains_point(point, &self.transform),
+            None => true,
+        }
+    }
+}
+
+/// Collider transform including the collider offset.
+fn collider_transform(transform: &GlobalTransform, collider: &Collider) -> GlobalTransform {
+    if collider.offset != Vec3::ZERO {
+        GlobalTransform(transform.0 * glam::Mat4::from_translation(collider.offset))
+    } else {
+        *transform
+    }
+}
+
+/// Extent of an AABB projected onto `axis`, as (min, max).
+fn project_aabb(aabb: &PhysicsAabb, axis: Vec3) -> (f32, f32) {
+    let center = (aabb.min + aabb.max) * 0.5;
+    let half = (aabb.max - aabb.min) * 0.5;
+    let c = center.dot(axis);
+    let r = half.dot(axis.abs());
+    (c - r, c + r)
+}
+
+/// Submerged volume and its centroid offset along `up` for a body below `surface`.
+///
+/// Spheres use the exact spherical cap volume; other shapes scale their
+/// volume by the submerged fraction of their world AABB height.
+fn submerged_volume(
+    shape: &ColliderShape,
+    transform: &GlobalTransform,
+    body_aabb: &PhysicsAabb,
+    up: Vec3,
+    surface: f32,
+) -> (f32, f32) {
+    let (lo, hi) = project_aabb(body_aabb, up);
+    if surface <= lo {
+        return (0.0, 0.0);
+    }
+    let top = surface.min(hi);
+    let centroid = (lo + top) * 0.5;
+
+    let mat = transform.0;
+    let scale = Vec3::new(
+        mat.x_axis.truncate().length(),
+        mat.y_axis.truncate().length(),
+        mat.z_axis.truncate().length(),
+    );
+
+    let volume = match shape {
+        ColliderShape::Sphere { radius } => {
+            let r = radius * scale.max_element();
+            let depth = (top - lo).clamp(0.0, 2.0 * r);
+            std::f32::consts::PI * depth * depth * (3.0 * r - depth) / 3.0
+        }
+        _ => {
+            let height = hi - lo;
+            let fraction = if height > 0.0 {
+                ((top - lo) / height).clamp(0.0, 1.0)
+            } else {
+                1.0
+            };
+            shape.volume() * scale.x * scale.y * scale.z * fraction
+        }
+    };
+    (volume, centroid)
+}
+
+/// Apply all [`ForceField`]s to the dynamic rigid bodies inside their volumes.
+///
+/// Runs in the force-accumulation step alongside gravity; `gravity` defines
+/// the "up" direction and weight used for buoyancy. Sleeping bodies are
+/// skipped, matching gravity.
+pub fn apply_force_fields(world: &mut hecs::World, gravity: Vec3) {
+    let fields: Vec<FieldVolume> = world
+        .query::<(&ForceField, &GlobalTransform, Option<&Collider>)>()
+        .iter()
+        .map(|(entity, (field, transform, collider))| {
+            let (transform, shape) = match collider {
+                Some(c) => {
+                    let t = collider_transform(transform, c);
+                    let aabb = c.shape.compute_aabb(&t);
+                    (t, Some((c.shape.clone(), aabb)))
+                }
+                None => (*transform, None),
+            };
+            FieldVolume {
+                entity,
+                kind: field.kind,
+                transform,
+                shape,
+            }
+        })
+        .collect();
+
+    if fields.is_empty() {
+        return;
+    }
+
+    let up = (-gravity).normalize_or(Vec3::Y);
+
+    for (entity, (rb, transform, collider, sleep)) in world.query_mut::<(
+        &mut RigidBody,
+        &GlobalTransform,
+        Option<&Collider>,
+        Option<&SleepInfo>,
+    )>() {
+        let is_sleeping = sleep.is_some_and(|s| s.state == SleepState::Sleeping);
+        if rb.body_type != RigidBodyType::Dynamic || rb.mass <= 0.0 || is_sleeping {
+            continue;
+        }
+
+        let body_transform = match collider {
+            Some(c) => collider_transform(transform, c),
+            None => *transform,
+        };
+        let center = body_transform.0.transform_point3(Vec3::ZERO);
+
+        for field in &fields {
+            if field.entity == entity {
+                continue;
+            }
+
+            match field.kind {
+                ForceFieldKind::Directional { acceleration } => {
+                    if field.contains(center) {
+                        rb.force_accumulator += acceleration * rb.mass;
+                    }
+                }
+                ForceFieldKind::PointAttractor {
+                    strength,
+                    min_distance,
+                } => {
+                    if field.contains(center) {
+                        let to_origin = field.origin() - center;
+                        let distance = to_origin.length().max(min_distance.max(1e-4));
+                        let dir = to_origin.normalize_or_zero();
+                        rb.force_accumulator += dir * (strength / (distance * distance)) * rb.mass;
+                    }
+                }
+                ForceFieldKind::Vortex {
+                    axis,
+                    strength,
+                    inward,
+                } => {
+                    if field.contains(center) {
+                        let axis = field
+                            .transform
+                            .0
+                            .transform_vector3(axis)
+                            .normalize_or_zero();
+                        let offset = center - field.origin();
+                        let radial = offset - axis * offset.dot(axis);
+                        let tangent = axis.cross(radial).normalize_or_zero();
+                        let accel = tangent * strength - radial.normalize_or_zero() * inward;
+                        rb.force_accumulator += accel * rb.mass;
+                    }
+                }
+                ForceFieldKind::Drag {
+                    linear,
+                    quadratic,
+                    flow_velocity,
+                } => {
+                    if field.contains(center) {
+                        let v = rb.linear_velocity - flow_velocity;
+                        rb.force_accumulator -= v * linear + v * v.length() * quadratic;
+                    }
+                }
+                ForceFieldKind::Buoyancy {
+                    fluid_density,
+                    linear_drag,
+                    angular_drag,
+                } => {
+                    let Some(c) = collider else {
+                        continue;
+                    };
+                    let body_aabb = c.shape.compute_aabb(&body_transform);
+                    let surface = match &field.shape {
+                        Some((_, field_aabb)) => {
+                            if !field_aabb.overlaps(&body_aabb) {
+                                continue;
+                            }
+                            project_aabb(field_aabb, up).1
+                        }
+                        None => field.origin().dot(up),
+                    };
+
+                    let (volume, centroid) =
+                        submerged_volume(&c.shape, &body_transform, &body_aabb, up, surface);
+                    if volume <= 0.0 {
+                        continue;
+                    }
+
+                    let buoyant = -gravity * fluid_density * volume;
+                    let center_of_buoyancy = center + up * (centroid - center.dot(up));
+                    rb.force_accumulator += buoyant;
+                    rb.torque_accumulator += (center_of_buoyancy - center).cross(buoyant);
+
+                    // Full volume with the same scaling as the submerged part
+                    let (full_volume, _) =
+                        submerged_volume(&c.shape, &body_transform, &body_aabb, up, f32::INFINITY);
+                    let fraction = (volume / full_volume.max(1e-6)).min(1.0);
+                    rb.force_accumulator -= rb.linear_velocity * linear_drag * fraction * rb.mass;
+                    let inertia = Vec3::new(
+                        rb.inertia_tensor[0],
+                        rb.inertia_tensor[4],
+                        rb.inertia_tensor[8],
+                    );
+                    rb.torque_accumulator -=
+                        rb.angular_velocity * inertia * angular_drag * fraction;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::components::transform::Transform;
+    use crate::physics::{PhysicsConfig, PhysicsWorld};
+    use glam::Mat4;
+
+    fn spawn_body(
+        world: &mut hecs::World,
+        position: Vec3,
+        shape: ColliderShape,
+        mass: f32,
+    ) -> hecs::Entity {
+        world.spawn((
+            Transform::from_position(position),
+            GlobalTransform(Mat4::from_translation(position)),
+            RigidBody::new_dynamic(mass),
+            Collider {
+                shape,
+                offset: Vec3::ZERO,
+                is_sensor: false,
+                material: None,
+            },
+        ))
+    }
+
+    fn spawn_field(world: &mut hecs::World, position: Vec3, half_extents: Vec3, field: ForceField) {
+        world.spawn((
+            Transform::from_position(position),
+            GlobalTransform(Mat4::from_translation(position)),
+            field,
+            Collider {
+                shape: ColliderShape::Box { half_extents },
+                offset: Vec3::ZERO,
+                is_sensor: true,
+                material: None,
+            },
+        ));
+    }
+
+    #[test]
+    fn test_directional_field_only_inside_volume() {
+        let mut world = hecs::World::new();
+        spawn_field(
+            &mut world,
+            Vec3::ZERO,
+            Vec3::splat(1.0),
+            ForceField::directional(Vec3::new(5.0, 0.0, 0.0)),
+        );
+        let inside = spawn_body(
+            &mut world,
+            Vec3::ZERO,
+            ColliderShape::Sphere { radius: 0.1 },
+            2.0,
+        );
+        let outside = spawn_body(
+            &mut world,
+            Vec3::new(3.0, 0.0, 0.0),
+            ColliderShape::Sphere { radius: 0.1 },
+            2.0,
+        );
+
+        apply_force_fields(&mut world, Vec3::new(0.0, -9.81, 0.0));
+
+        let rb = world.get::<&RigidBody>(inside).unwrap();
+        assert!((rb.force_accumulator - Vec3::new(10.0, 0.0, 0.0)).length() < 1e-5);
+        let rb = world.get::<&RigidBody>(outside).unwrap();
+        assert_eq!(rb.force_accumulator, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_wind_pushes_toward_flow_velocity() {
+        let mut world = hecs::World::new();
+        world.spawn((
+            GlobalTransform::default(),
+            ForceField::wind(Vec3::new(0.0, 0.0, 4.0), 0.5),
+        ));
+        let body = spawn_body(
+            &mut world,
+            Vec3::ZERO,
+            ColliderShape::Sphere { radius: 0.5 },
+            1.0,
+        );
+
+        apply_force_fields(&mut world, Vec3::new(0.0, -9.81, 0.0));
+
+        let rb = world.get::<&RigidBody>(body).unwrap();
+        // v_rel = (0,0,-4): F = -0.5 * 4 * (0,0,-4) = (0,0,8)
+        assert!((rb.force_accumulator - Vec3::new(0.0, 0.0, 8.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_submerged_sphere_volume() {
+        let shape = ColliderShape::Sphere { radius: 1.0 };
+        let transform = GlobalTransform::default();
+        let aabb = shape.compute_aabb(&transform);
+
+        let (full, _) = submerged_volume(&shape, &transform, &aabb, Vec3::Y, 5.0);
+        assert!((full - shape.volume()).abs() < 1e-4);
+
+        let (half, centroid) = submerged_volume(&shape, &transform, &aabb, Vec3::Y, 0.0);
+        assert!((half - shape.volume() * 0.5).abs() < 1e-4);
+        assert!(centroid < 0.0);
+
+        let (none, _) = submerged_volume(&shape, &transform, &aabb, Vec3::Y, -2.0);
+        assert_eq!(none, 0.0);
+    }
+
+    #[test]
+    fn test_drag_uses_scaled_submerged_fraction() {
+        let mut world = hecs::World::new();
+        spawn_field(
+            &mut world,
+            Vec3::new(0.0, -5.0, 0.0),
+            Vec3::new(20.0, 5.0, 20.0),
+            ForceField::buoyancy(1000.0),
+        );
+        // Unit cube scaled to 2x2x2, half below the surface at y = 0
+        let body = world.spawn((
+            GlobalTransform(Mat4::from_scale(Vec3::splat(2.0))),
+            RigidBody {
+                linear_velocity: Vec3::X,
+                ..RigidBody::new_dynamic(10.0)
+            },
+            Collider {
+                shape: ColliderShape::Box {
+                    half_extents: Vec3::splat(0.5),
+                },
+                offset: Vec3::ZERO,
+                is_sensor: false,
+                material: None,
+            },
+        ));
+
+        apply_force_fields(&mut world, Vec3::new(0.0, -9.81, 0.0));
+
+        // Linear drag 1.0 * half submerged * mass 10
+        let rb = world.get::<&RigidBody>(body).unwrap();
+        assert!(
+            (rb.force_accumulator.x + 5.0).abs() < 1e-3,
+            "drag = {}",
+            rb.force_accumulator.x
+        );
+    }
+
+    #[test]
+    fn test_light_box_floats_heavy_box_sinks() {
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+
+        // Water volume with its surface at y = 0
+        spawn_field(
+            &mut world,
+            Vec3::new(0.0, -5.0, 0.0),
+            Vec3::new(20.0, 5.0, 20.0),
+            ForceField::buoyancy(1000.0),
+        );
+
+        // Unit cubes: density 500 floats half-submerged, density 2000 sinks.
+        let light = spawn_body(
+            &mut world,
+            Vec3::new(-3.0, 1.0, 0.0),
+            ColliderShape::Box {
+                half_extents: Vec3::splat(0.5),
+            },
+            500.0,
+        );
+        let heavy = spawn_body(
+            &mut world,
+            Vec3::new(3.0, 1.0, 0.0),
+            ColliderShape::Box {
+                half_extents: Vec3::splat(0.5),
+            },
+            2000.0,
+        );
+
+        for _ in 0..600 {
+            physics.step(&mut world, 1.0 / 60.0);
+        }
+
+        let light_y = world.get::<&Transform>(light).unwrap().position.y;
+        let heavy_y = world.get::<&Transform>(heavy).unwrap().position.y;
+        assert!(
+            light_y.abs() < 0.15,
+            "light box should float half-submerged: y = {}",
+            light_y
+        );
+        assert!(heavy_y < -2.0, "heavy box should sink: y = {}", heavy_y);
+    }
+}
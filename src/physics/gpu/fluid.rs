@@ -0,0 +1,773 @@
+//! GPU SPH (smoothed-particle hydrodynamics) fluid simulation.
+//!
+//! Particles live entirely on the GPU. Each substep runs five compute passes
+//! recorded into a single command encoder:
+//!
+//! | Pass | Work |
+//! |------|------|
+//! | Clear grid | Reset spatial hash cell heads |
+//! | Build grid | Insert particles into per-cell linked lists |
+//! | Density | Density + pressure from neighbors (poly6 kernel) |
+//! | Forces | Pressure (spiky), viscosity (laplacian) and gravity |
+//! | Integrate | Symplectic Euler, static collider and domain boundaries |
+//!
+//! Neighbor search uses a hashed uniform grid with cell size equal to the
+//! smoothing radius, so each particle only visits the 27 surrounding cells.
+//! Static colliders (spheres and boxes) are uploaded from the ECS world with
+//! [`SphFluid::upload_static_colliders`] and act as boundaries.
+//!
+//! [`FluidRenderer`] draws the particles as point sprites reading directly
+//! from the particle storage buffer, so no CPU readback is needed per frame.
+
+use glam::Vec3;
+
+use crate::compute::{compute_workgroup_count, read_buffer_sync};
+use crate::context::WgpuContext;
+use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
+use crate::core::render_states::{BlendState, CullState, DepthState};
+use crate::core::{ComputePipelineBuilder, PipelineBuilder, StorageBuffer};
+use crate::ecs::components::physics::{Collider, ColliderShape, RigidBody, RigidBodyType};
+use crate::ecs::components::transform::GlobalTransform;
+use crate::renderer::viewer::{CameraUniform, Viewer};
+
+use super::{gpu_shape_data, GpuShapeData};
+
+/// Maximum number of static colliders used as fluid boundaries.
+pub const MAX_FLUID_COLLIDERS: usize = 256;
+
+/// Workgroup size matching the SPH shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// GPU particle layout matching the SPH shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct FluidParticle {
+    pub position: [f32; 3],
+    pub density: f32,
+    pub velocity: [f32; 3],
+    pub pressure: f32,
+}
+
+/// GPU SPH parameters.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SphParams {
+    num_particles: u32,
+    table_size: u32,
+    num_colliders: u32,
+    _pad0: u32,
+    gravity: [f32; 3],
+    dt: f32,
+    bounds_min: [f32; 3],
+    smoothing_radius: f32,
+    bounds_max: [f32; 3],
+    particle_mass: f32,
+    rest_density: f32,
+    stiffness: f32,
+    viscosity: f32,
+    boundary_restitution: f32,
+}
+
+/// Configuration for the SPH fluid solver.
+#[derive(Debug, Clone)]
+pub struct SphConfig {
+    /// Kernel support radius `h`, also the hash grid cell size. Default: 0.1.
+    pub smoothing_radius: f32,
+    /// Mass of each particle. Default: 0.125 (water at 0.05 spacing).
+    pub particle_mass: f32,
+    /// Rest density of the fluid. Default: 1000.
+    pub rest_density: f32,
+    /// Pressure stiffness (gas constant). Default: 50.
+    pub stiffness: f32,
+    /// Dynamic viscosity coefficient. Default: 1.0.
+    pub viscosity: f32,
+    /// Gravity vector. Default: (0, -9.81, 0).
+    pub gravity: Vec3,
+    /// Minimum corner of the simulation domain.
+    pub bounds_min: Vec3,
+    /// Maximum corner of the simulation domain.
+    pub bounds_max: Vec3,
+    /// Velocity restitution when bouncing off boundaries (0.0 - 1.0). Default: 0.3.
+    pub boundary_restitution: f32,
+    /// Number of solver substeps per [`SphFluid::step`]. Default: 4.
+    pub substeps: u32,
+}
+
+impl Default for SphConfig {
+    fn default() -> Self {
+        Self {
+            smoothing_radius: 0.1,
+            particle_mass: 0.125,
+            rest_density: 1000.0,
+            stiffness: 50.0,
+            viscosity: 1.0,
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            bounds_min: Vec3::splat(-5.0),
+            bounds_max: Vec3::splat(5.0),
+            boundary_restitution: 0.3,
+            substeps: 4,
+        }
+    }
+}
+
+impl SphConfig {
+    /// Particle mass that yields `rest_density` for particles laid out on a
+    /// cubic lattice with the given spacing.
+    pub fn mass_for_spacing(rest_density: f32, spacing: f32) -> f32 {
+        rest_density * spacing * spacing * spacing
+    }
+}
+
+/// The five SPH compute pipelines, rebuilt together on shader reload.
+struct SphPipelines {
+    clear_grid: wgpu::ComputePipeline,
+    build_grid: wgpu::ComputePipeline,
+    density: wgpu::ComputePipeline,
+    forces: wgpu::ComputePipeline,
+    integrate: wgpu::ComputePipeline,
+}
+
+/// GPU SPH fluid solver.
+///
+/// Owns the particle, acceleration and spatial hash buffers plus the five
+/// compute pipelines. The particle count is fixed at construction.
+pub struct SphFluid {
+    config: SphConfig,
+    num_particles: u32,
+    table_size: u32,
+    num_colliders: u32,
+
+    pipelines: HotShader<SphPipelines>,
+
+    particle_buffer: StorageBuffer,
+    _acceleration_buffer: StorageBuffer,
+    _cell_head_buffer: StorageBuffer,
+    _next_buffer: StorageBuffer,
+    collider_buffer: StorageBuffer,
+    params_buffer: RawUniformBuffer,
+
+    data_bind_group: wgpu::BindGroup,
+    params_bind_group: wgpu::BindGroup,
+}
+
+impl SphFluid {
+    /// Create a fluid with particles at the given positions (initially at rest).
+    pub fn new(ctx: &WgpuContext, config: SphConfig, positions: &[Vec3]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            !positions.is_empty(),
+            "SPH fluid needs at least one particle"
+        );
+
+        let particles: Vec<FluidParticle> = positions
+            .iter()
+            .map(|p| FluidParticle {
+                position: p.to_array(),
+                density: config.rest_density,
+                velocity: [0.0; 3],
+                pressure: 0.0,
+            })
+            .collect();
+        let num_particles = particles.len() as u32;
+        // Keep the hash table at most half full to limit collisions.
+        let table_size = (num_particles * 2).next_power_of_two();
+
+        let particle_buffer = StorageBuffer::from_data(ctx, &particles, Some("sph particles"));
+        let acceleration_buffer = StorageBuffer::new(
+            ctx,
+            num_particles as u64 * std::mem::size_of::<[f32; 4]>() as u64,
+            Some("sph accelerations"),
+        );
+        let cell_head_buffer = StorageBuffer::new(
+            ctx,
+            table_size as u64 * std::mem::size_of::<i32>() as u64,
+            Some("sph cell heads"),
+        );
+        let next_buffer = StorageBuffer::new(
+            ctx,
+            num_particles as u64 * std::mem::size_of::<i32>() as u64,
+            Some("sph next particle"),
+        );
+        let collider_buffer = StorageBuffer::new(
+            ctx,
+            (MAX_FLUID_COLLIDERS * std::mem::size_of::<GpuShapeData>()) as u64,
+            Some("sph colliders"),
+        );
+        let params_buffer = RawUniformBuffer::new(
+            ctx,
+            std::mem::size_of::<SphParams>() as u64,
+            Some("sph params"),
+        );
+
+        let storage_entry = |binding: u32, read_only: bool| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+
+        let data_layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("sph data layout"),
+                entries: &[
+                    // Particles (read_write)
+                    storage_entry(0, false),
+                    // Accelerations (read_write)
+                    storage_entry(1, false),
+                    // Cell heads (atomic)
+                    storage_entry(2, false),
+                    // Next particle links (read_write)
+                    storage_entry(3, false),
+                    // Static colliders (read)
+                    storage_entry(4, true),
+                ],
+            });
+
+        let params_layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("sph params layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+
+        let shader = ShaderFile::embedded(
+            "compute/sph.wgsl",
+            include_str!("../../shaders/compute/sph.wgsl"),
+        );
+        let (pipeline_ctx, layouts) = (ctx.clone(), [data_layout.clone(), params_layout.clone()]);
+        let watched = shader.is_watched();
+        let pipelines = HotShader::new(shader, move |shader| {
+            let build = |label: &str, entry: &str| {
+                layouts
+                    .iter()
+                    .fold(
+                        ComputePipelineBuilder::new(&pipeline_ctx)
+                            .label(label)
+                            .shader(shader)
+                            .catch_errors(watched)
+                            .entry_point(entry),
+                        |builder, layout| builder.bind_group_layout(layout),
+                    )
+                    .build()
+            };
+            Ok(SphPipelines {
+                clear_grid: build("sph clear grid", "cs_clear_grid")?,
+                build_grid: build("sph build grid", "cs_build_grid")?,
+                density: build("sph density", "cs_density")?,
+                forces: build("sph forces", "cs_forces")?,
+                integrate: build("sph integrate", "cs_integrate")?,
+            })
+        })?;
+
+        let data_bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("sph data"),
+            layout: &data_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: particle_buffer.buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: acceleration_buffer.buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: cell_head_buffer.buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: next_buffer.buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 4,
+                    resource: collider_buffer.buffer().as_entire_binding(),
+                },
+            ],
+        });
+
+        let params_bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("sph params"),
+            layout: &params_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: params_buffer.buffer().as_entire_binding(),
+            }],
+        });
+
+        Ok(Self {
+            config,
+            num_particles,
+            table_size,
+            num_colliders: 0,
+            pipelines,
+            particle_buffer,
+            _acceleration_buffer: acceleration_buffer,
+            _cell_head_buffer: cell_head_buffer,
+            _next_buffer: next_buffer,
+            collider_buffer,
+            params_buffer,
+            data_bind_group,
+            params_bind_group,
+        })
+    }
+
+    /// Create a fluid filling an axis-aligned block with particles on a cubic lattice.
+    pub fn new_block(
+        ctx: &WgpuContext,
+        config: SphConfig,
+        min: Vec3,
+        max: Vec3,
+        spacing: f32,
+    ) -> anyhow::Result<Self> {
+        let counts = ((max - min) / spacing).floor().as_uvec3() + glam::UVec3::ONE;
+        let mut positions = Vec::with_capacity((counts.x * counts.y * counts.z) as usize);
+        for x in 0..counts.x {
+            for y in 0..counts.y {
+                for z in 0..counts.z {
+                    positions.push(min + Vec3::new(x as f32, y as f32, z as f32) * spacing);
+                }
+            }
+        }
+        Self::new(ctx, config, &positions)
+    }
+
+    /// Upload static, non-sensor sphere and box colliders from the ECS world
+    /// as fluid boundaries. Call again whenever the static geometry changes.
+    ///
+    /// Returns the number of colliders uploaded (capped at [`MAX_FLUID_COLLIDERS`]).
+    pub fn upload_static_colliders(&mut self, ctx: &WgpuContext, world: &hecs::World) -> usize {
+        let mut shapes = Vec::new();
+        for (_, (collider, transform, rb)) in world
+            .query::<(&Collider, &GlobalTransform, &RigidBody)>()
+            .iter()
+        {
+            if collider.is_sensor || rb.body_type != RigidBodyType::Static {
+                continue;
+            }
+            if !matches!(
+                collider.shape,
+                ColliderShape::Sphere { .. } | ColliderShape::Box { .. }
+            ) {
+                continue;
+            }
+            if shapes.len() == MAX_FLUID_COLLIDERS {
+                tracing::warn!(
+                    "SPH fluid supports at most {} static colliders; ignoring the rest",
+                    MAX_FLUID_COLLIDERS
+                );
+                break;
+            }
+            shapes.push(gpu_shape_data(collider, transform));
+        }
+
+        if !shapes.is_empty() {
+            self.collider_buffer.write(ctx, &shapes);
+        }
+        self.num_colliders = shapes.len() as u32;
+        shapes.len()
+    }
+
+    /// Advance the simulation by `delta_time` seconds, split into
+    /// `config.substeps` equal substeps.
+    pub fn step(&self, ctx: &WgpuContext, delta_time: f32) {
+        let substeps = self.config.substeps.max(1);
+        self.write_params(ctx, delta_time / substeps as f32);
+
+        let particle_groups = compute_workgroup_count(self.num_particles, WORKGROUP_SIZE);
+        let table_groups = compute_workgroup_count(self.table_size, WORKGROUP_SIZE);
+
+        let pipelines = self.pipelines.get();
+        let mut encoder = ctx.create_encoder(Some("sph step"));
+        for _ in 0..substeps {
+            for (pipeline, groups, label) in [
+                (&pipelines.clear_grid, table_groups, "sph clear grid"),
+                (&pipelines.build_grid, particle_groups, "sph build grid"),
+                (&pipelines.density, particle_groups, "sph density"),
+                (&pipelines.forces, particle_groups, "sph forces"),
+                (&pipelines.integrate, particle_groups, "sph integrate"),
+            ] {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some(label),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &self.data_bind_group, &[]);
+                pass.set_bind_group(1, &self.params_bind_group, &[]);
+                pass.dispatch_workgroups(groups, 1, 1);
+            }
+        }
+        ctx.submit([encoder.finish()]);
+    }
+
+    fn write_params(&self, ctx: &WgpuContext, dt: f32) {
+        let c = &self.config;
+        let params = SphParams {
+            num_particles: self.num_particles,
+            table_size: self.table_size,
+            num_colliders: self.num_colliders,
+            _pad0: 0,
+            gravity: c.gravity.to_array(),
+            dt,
+            bounds_min: c.bounds_min.to_array(),
+            smoothing_radius: c.smoothing_radius,
+            bounds_max: c.bounds_max.to_array(),
+            particle_mass: c.particle_mass,
+            rest_density: c.rest_density,
+            stiffness: c.stiffness,
+            viscosity: c.viscosity,
+            boundary_restitution: c.boundary_restitution,
+        };
+        self.params_buffer.write(ctx, &params);
+    }
+
+    /// Rebuild the compute pipelines if their shader file changed, when hot
+    /// reload is enabled (see [`hot_reload`](crate::core::hot_reload)).
+    /// Returns whether they were rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipelines.reload_if_changed()
+    }
+
+    /// Read all particles back to the CPU. Blocks until the GPU is done.
+    pub fn read_particles(&self, ctx: &WgpuContext) -> Vec<FluidParticle> {
+        read_buffer_sync(
+            ctx,
+            self.particle_buffer.buffer(),
+            self.particle_buffer.size(),
+        )
+    }
+
+    /// Get the particle storage buffer (array of [`FluidParticle`]).
+    pub fn particle_buffer(&self) -> &wgpu::Buffer {
+        self.particle_buffer.buffer()
+    }
+
+    /// Number of simulated particles.
+    pub fn particle_count(&self) -> u32 {
+        self.num_particles
+    }
+
+    /// Get the solver configuration.
+    pub fn config(&self) -> &SphConfig {
+        &self.config
+    }
+
+    /// Get a mutable reference to the solver configuration.
+    ///
+    /// Changes take effect on the next [`step`](Self::step).
+    pub fn config_mut(&mut self) -> &mut SphConfig {
+        &mut self.config
+    }
+}
+
+/// Render parameters for [`FluidRenderer`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct FluidRenderParams {
+    color: [f32; 4],
+    fast_color: [f32; 4],
+    radius: f32,
+    max_speed: f32,
+    _pad0: f32,
+    _pad1: f32,
+}
+
+/// Point sprite renderer for [`SphFluid`].
+///
+/// Reads particle positions and velocities straight from the solver's
+/// storage buffer, coloring each sprite by speed from `color` (at rest) to
+/// `fast_color` (at `max_speed` and above).
+pub struct FluidRenderer {
+    pipeline: HotShader<wgpu::RenderPipeline>,
+    camera_buffer: RawUniformBuffer,
+    camera_bind_group: wgpu::BindGroup,
+    params_buffer: RawUniformBuffer,
+    particle_bind_group: wgpu::BindGroup,
+    particle_count: u32,
+    /// Base particle color (RGBA).
+    pub color: [f32; 4],
+    /// Particle color at `max_speed` (RGBA).
+    pub fast_color: [f32; 4],
+    /// Sprite radius in world units.
+    pub radius: f32,
+    /// Speed at which particles are drawn fully in `fast_color`.
+    pub max_speed: f32,
+}
+
+impl FluidRenderer {
+    /// Create a renderer bound to the given fluid's particle buffer.
+    pub fn new(
+        ctx: &WgpuContext,
+        format: wgpu::TextureFormat,
+        fluid: &SphFluid,
+    ) -> anyhow::Result<Self> {
+        let shader = ShaderFile::embedded("fluid.wgsl", include_str!("../../shaders/fluid.wgsl"));
+
+        let camera_layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("fluid camera bind group layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+            });
+
+        let particle_layout =
+            ctx.device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: Some("fluid particle bind group layout"),
+                    entries: &[
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 0,
+                            visibility: wgpu::ShaderStages::VERTEX,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                        wgpu::BindGroupLayoutEntry {
+                            binding: 1,
+                            visibility: wgpu::ShaderStages::VERTEX,
+                            ty: wgpu::BindingType::Buffer {
+                                ty: wgpu::BufferBindingType::Uniform,
+                                has_dynamic_offset: false,
+                                min_binding_size: None,
+                            },
+                            count: None,
+                        },
+                    ],
+                });
+
+        let layouts = [camera_layout.clone(), particle_layout.clone()];
+        let pipeline_ctx = ctx.clone();
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("fluid sprite pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .bind_group_layout(&layouts[0])
+                .bind_group_layout(&layouts[1])
+                .color_format(format)
+                .depth(DepthState::read_write())
+                .blend(BlendState::Opaque)
+                .cull(CullState::None)
+                .build()
+        })?;
+
+        let camera_buffer = RawUniformBuffer::new(
+            ctx,
+            std::mem::size_of::<CameraUniform>() as u64,
+            Some("fluid camera uniform"),
+        );
+        let camera_bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("fluid camera bind group"),
+            layout: &camera_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.buffer().as_entire_binding(),
+            }],
+        });
+
+        let params_buffer = RawUniformBuffer::new(
+            ctx,
+            std::mem::size_of::<FluidRenderParams>() as u64,
+            Some("fluid render params"),
+        );
+        let particle_bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("fluid particle bind group"),
+            layout: &particle_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: fluid.particle_buffer().as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: params_buffer.buffer().as_entire_binding(),
+                },
+            ],
+        });
+
+        Ok(Self {
+            pipeline,
+            camera_buffer,
+            camera_bind_group,
+            params_buffer,
+            particle_bind_group,
+            particle_count: fluid.particle_count(),
+            color: [0.15, 0.45, 0.9, 1.0],
+            fast_color: [0.85, 0.95, 1.0, 1.0],
+            radius: fluid.config().smoothing_radius * 0.3,
+            max_speed: 3.0,
+        })
+    }
+
+    /// Update camera and render parameters before rendering.
+    pub fn update_uniforms(&self, ctx: &WgpuContext, viewer: &dyn Viewer) {
+        self.camera_buffer
+            .write(ctx, &CameraUniform::from_viewer(viewer));
+        self.params_buffer.write(
+            ctx,
+            &FluidRenderParams {
+                color: self.color,
+                fast_color: self.fast_color,
+                radius: self.radius,
+                max_speed: self.max_speed,
+                _pad0: 0.0,
+                _pad1: 0.0,
+            },
+        );
+    }
+
+    /// Rebuild the pipeline if its shader file changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// it was rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
+
+    /// Draw all particles as camera-facing sprites.
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass<'_>) {
+        render_pass.set_pipeline(&self.pipeline.get());
+        render_pass.set_bind_group(0, &self.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.particle_bind_group, &[]);
+        render_pass.draw(0..6, 0..self.particle_count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn try_create_ctx() -> Option<WgpuContext> {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            return None;
+        }
+        WgpuContext::new_blocking(None).ok()
+    }
+
+    #[test]
+    fn test_fluid_particle_layout() {
+        assert_eq!(std::mem::size_of::<FluidParticle>(), 32);
+    }
+
+    #[test]
+    fn test_sph_params_layout() {
+        // Must match WGSL SphParams (vec3 + f32 rows, 16-byte aligned)
+        assert_eq!(std::mem::size_of::<SphParams>(), 80);
+    }
+
+    #[test]
+    fn test_mass_for_spacing() {
+        let mass = SphConfig::mass_for_spacing(1000.0, 0.05);
+        assert!((mass - 0.125).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_colliding_cells_are_counted_once() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+
+        // One particle gets a 2-slot table, so most of its 27 cells collide.
+        let config = SphConfig {
+            gravity: Vec3::ZERO,
+            substeps: 1,
+            ..Default::default()
+        };
+        let fluid = SphFluid::new(&ctx, config.clone(), &[Vec3::ZERO]).unwrap();
+        fluid.step(&ctx, 1e-4);
+
+        let h = config.smoothing_radius;
+        let own = config.particle_mass * 315.0 / (64.0 * std::f32::consts::PI * h.powi(3));
+        let density = fluid.read_particles(&ctx)[0].density;
+        assert!(
+            (density - own).abs() < own * 1e-3,
+            "density = {density}, expected {own}"
+        );
+    }
+
+    #[test]
+    fn test_fluid_settles_inside_container() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+
+        let config = SphConfig {
+            bounds_min: Vec3::new(-0.5, -1.0, -0.5),
+            bounds_max: Vec3::new(0.5, 2.0, 0.5),
+            ..Default::default()
+        };
+        let mut fluid = SphFluid::new_block(
+            &ctx,
+            config,
+            Vec3::new(-0.2, 0.2, -0.2),
+            Vec3::new(0.2, 0.6, 0.2),
+            0.05,
+        )
+        .unwrap();
+
+        // Static floor at y = 0
+        let mut world = hecs::World::new();
+        world.spawn((
+            GlobalTransform(glam::Mat4::from_translation(Vec3::new(0.0, -0.5, 0.0))),
+            RigidBody::new_static(),
+            Collider {
+                shape: ColliderShape::Box {
+                    half_extents: Vec3::new(1.0, 0.5, 1.0),
+                },
+                offset: Vec3::ZERO,
+                is_sensor: false,
+                material: None,
+            },
+        ));
+        assert_eq!(fluid.upload_static_colliders(&ctx, &world), 1);
+
+        for _ in 0..60 {
+            fluid.step(&ctx, 1.0 / 60.0);
+        }
+
+        let particles = fluid.read_particles(&ctx);
+        assert_eq!(particles.len(), fluid.particle_count() as usize);
+        let mut mean_y = 0.0;
+        for p in &particles {
+            let pos = Vec3::from(p.position);
+            assert!(pos.is_finite(), "particle diverged: {:?}", p);
+            assert!(pos.y > -0.01, "particle fell through floor: {:?}", pos);
+            assert!(pos.x.abs() <= 0.5 + 1e-4 && pos.z.abs() <= 0.5 + 1e-4);
+            mean_y += pos.y;
+        }
+        mean_y /= particles.len() as f32;
+        assert!(
+            mean_y < 0.4,
+            "fluid should have fallen: mean y = {}",
+            mean_y
+        );
+    }
+}
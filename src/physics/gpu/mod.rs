@@ -14,15 +14,23 @@
 //! | Contact solver | CPU | Sequential impulse is inherently serial |
 //!
 //! GPU offload is used when body count >= [`GPU_BODY_THRESHOLD`].
+//!
+//! The [`fluid`] submodule provides an SPH fluid solver that runs entirely
+//! on the GPU and uses static colliders as boundaries.
+
+pub mod fluid;
 
 use glam::Vec3;
 
 use crate::compute::{compute_workgroup_count, read_buffer_sync, ComputeDispatcher};
 use crate::context::WgpuContext;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::{ComputePipelineBuilder, StorageBuffer};
 use crate::ecs::components::physics::{Collider, ColliderShape, RigidBody, RigidBodyType};
 use crate::ecs::components::transform::GlobalTransform;
 
+pub use fluid::{FluidParticle, FluidRenderer, SphConfig, SphFluid};
+
 /// Minimum number of bodies before GPU offload is used.
 pub const GPU_BODY_THRESHOLD: usize = 256;
 
@@ -125,16 +133,48 @@ struct IntegrateParams {
     _pad2: f32,
 }
 
+/// Broadphase compute pipelines, rebuilt together on shader reload.
+struct BroadphasePipelines {
+    // Legacy O(n^2) fallback, kept for potential direct use
+    _brute_force: wgpu::ComputePipeline,
+    // Spatial hash broadphase (2-pass)
+    assign_cells: wgpu::ComputePipeline,
+    spatial: wgpu::ComputePipeline,
+}
+
+/// Integration compute pipelines, rebuilt together on shader reload.
+struct IntegratePipelines {
+    velocities: wgpu::ComputePipeline,
+    positions: wgpu::ComputePipeline,
+}
+
+/// Build compute pipelines from `shader` with the given bind group layouts,
+/// capturing validation errors when the shader is watched.
+fn compute_builder<'a>(
+    ctx: &'a WgpuContext,
+    shader: &'a str,
+    watched: bool,
+    layouts: &'a [wgpu::BindGroupLayout],
+    label: &'a str,
+    entry: &'a str,
+) -> ComputePipelineBuilder<'a> {
+    layouts.iter().fold(
+        ComputePipelineBuilder::new(ctx)
+            .label(label)
+            .shader(shader)
+            .catch_errors(watched)
+            .entry_point(entry),
+        |builder, layout| builder.bind_group_layout(layout),
+    )
+}
+
 /// GPU-accelerated physics engine.
 ///
 /// Manages GPU buffers and compute pipelines for broadphase collision
 /// detection and rigid body integration.
 pub struct GpuPhysics {
-    // Broadphase resources (legacy O(n^2) fallback, kept for potential direct use)
-    _broadphase_pipeline: wgpu::ComputePipeline,
-    // Spatial hash broadphase resources (2-pass)
-    assign_cells_pipeline: wgpu::ComputePipeline,
-    broadphase_spatial_pipeline: wgpu::ComputePipeline,
+    // Broadphase resources
+    broadphase_pipelines: HotShader<BroadphasePipelines>,
     aabb_buffer: StorageBuffer,
     pair_buffer: StorageBuffer,
     pair_count_buffer: StorageBuffer,
@@ -143,7 +183,7 @@ pub struct GpuPhysics {
     broadphase_params_layout: wgpu::BindGroupLayout,
 
     // Narrowphase resources
-    narrowphase_pipeline: wgpu::ComputePipeline,
+    narrowphase_pipeline: HotShader<wgpu::ComputePipeline>,
     narrowphase_pair_buffer: StorageBuffer,
     shape_buffer: StorageBuffer,
     narrowphase_result_buffer: StorageBuffer,
@@ -151,8 +191,7 @@ pub struct GpuPhysics {
     narrowphase_params_layout: wgpu::BindGroupLayout,
 
     // Integration resources
-    integrate_vel_pipeline: wgpu::ComputePipeline,
-    integrate_pos_pipeline: wgpu::ComputePipeline,
+    integrate_pipelines: HotShader<IntegratePipelines>,
     body_buffer: StorageBuffer,
     integrate_data_layout: wgpu::BindGroupLayout,
     integrate_params_layout: wgpu::BindGroupLayout,
@@ -238,30 +277,26 @@ impl GpuPhysics {
                     }],
                 });
 
-        let broadphase_shader = include_str!("../../shaders/compute/broadphase.wgsl");
-        let broadphase_pipeline = ComputePipelineBuilder::new(ctx)
-            .label("broadphase compute (legacy)")
-            .shader(broadphase_shader)
-            .entry_point("cs_broadphase")
-            .bind_group_layout(&broadphase_data_layout)
-            .bind_group_layout(&broadphase_params_layout)
-            .build()?;
-
-        let assign_cells_pipeline = ComputePipelineBuilder::new(ctx)
-            .label("assign cells compute")
-            .shader(broadphase_shader)
-            .entry_point("cs_assign_cells")
-            .bind_group_layout(&broadphase_data_layout)
-            .bind_group_layout(&broadphase_params_layout)
-            .build()?;
-
-        let broadphase_spatial_pipeline = ComputePipelineBuilder::new(ctx)
-            .label("broadphase spatial compute")
-            .shader(broadphase_shader)
-            .entry_point("cs_broadphase_spatial")
-            .bind_group_layout(&broadphase_data_layout)
-            .bind_group_layout(&broadphase_params_layout)
-            .build()?;
+        let shader = ShaderFile::embedded(
+            "compute/broadphase.wgsl",
+            include_str!("../../shaders/compute/broadphase.wgsl"),
+        );
+        let pipeline_ctx = ctx.clone();
+        let layouts = [
+            broadphase_data_layout.clone(),
+            broadphase_params_layout.clone(),
+        ];
+        let watched = shader.is_watched();
+        let broadphase_pipelines = HotShader::new(shader, move |shader| {
+            let build = |label, entry| {
+                compute_builder(&pipeline_ctx, shader, watched, &layouts, label, entry).build()
+            };
+            Ok(BroadphasePipelines {
+                _brute_force: build("broadphase compute (legacy)", "cs_broadphase")?,
+                assign_cells: build("assign cells compute", "cs_assign_cells")?,
+                spatial: build("broadphase spatial compute", "cs_broadphase_spatial")?,
+            })
+        })?;
 
         // Broadphase buffers
         let aabb_size = (max_bodies * std::mem::size_of::<GpuAabb>()) as u64;
@@ -310,22 +345,25 @@ impl GpuPhysics {
                     }],
                 });
 
-        let integrate_shader = include_str!("../../shaders/compute/integrate.wgsl");
-        let integrate_vel_pipeline = ComputePipelineBuilder::new(ctx)
-            .label("integrate velocities compute")
-            .shader(integrate_shader)
-            .entry_point("cs_integrate_velocities")
-            .bind_group_layout(&integrate_data_layout)
-            .bind_group_layout(&integrate_params_layout)
-            .build()?;
-
-        let integrate_pos_pipeline = ComputePipelineBuilder::new(ctx)
-            .label("integrate positions compute")
-            .shader(integrate_shader)
-            .entry_point("cs_integrate_positions")
-            .bind_group_layout(&integrate_data_layout)
-            .bind_group_layout(&integrate_params_layout)
-            .build()?;
+        let shader = ShaderFile::embedded(
+            "compute/integrate.wgsl",
+            include_str!("../../shaders/compute/integrate.wgsl"),
+        );
+        let pipeline_ctx = ctx.clone();
+        let layouts = [
+            integrate_data_layout.clone(),
+            integrate_params_layout.clone(),
+        ];
+        let watched = shader.is_watched();
+        let integrate_pipelines = HotShader::new(shader, move |shader| {
+            let build = |label, entry| {
+                compute_builder(&pipeline_ctx, shader, watched, &layouts, label, entry).build()
+            };
+            Ok(IntegratePipelines {
+                velocities: build("integrate velocities compute", "cs_integrate_velocities")?,
+                positions: build("integrate positions compute", "cs_integrate_positions")?,
+            })
+        })?;
 
         // Body buffer
         let body_size = (max_bodies * std::mem::size_of::<GpuBody>()) as u64;
@@ -389,14 +427,27 @@ impl GpuPhysics {
                     }],
                 });
 
-        let narrowphase_shader = include_str!("../../shaders/compute/narrowphase.wgsl");
-        let narrowphase_pipeline = ComputePipelineBuilder::new(ctx)
-            .label("narrowphase compute")
-            .shader(narrowphase_shader)
-            .entry_point("cs_narrowphase")
-            .bind_group_layout(&narrowphase_data_layout)
-            .bind_group_layout(&narrowphase_params_layout)
-            .build()?;
+        let shader = ShaderFile::embedded(
+            "compute/narrowphase.wgsl",
+            include_str!("../../shaders/compute/narrowphase.wgsl"),
+        );
+        let pipeline_ctx = ctx.clone();
+        let layouts = [
+            narrowphase_data_layout.clone(),
+            narrowphase_params_layout.clone(),
+        ];
+        let watched = shader.is_watched();
+        let narrowphase_pipeline = HotShader::new(shader, move |shader| {
+            compute_builder(
+                &pipeline_ctx,
+                shader,
+                watched,
+                &layouts,
+                "narrowphase compute",
+                "cs_narrowphase",
+            )
+            .build()
+        })?;
 
         let max_narrowphase_pairs = MAX_PAIRS as usize;
         let narrowphase_pair_size =
@@ -412,9 +463,7 @@ impl GpuPhysics {
             StorageBuffer::new(ctx, result_size, Some("narrowphase result buffer"));
 
         Ok(Self {
-            _broadphase_pipeline: broadphase_pipeline,
-            assign_cells_pipeline,
-            broadphase_spatial_pipeline,
+            broadphase_pipelines,
             aabb_buffer,
             pair_buffer,
             pair_count_buffer,
@@ -427,8 +476,7 @@ impl GpuPhysics {
             narrowphase_result_buffer,
             narrowphase_data_layout,
             narrowphase_params_layout,
-            integrate_vel_pipeline,
-            integrate_pos_pipeline,
+            integrate_pipelines,
             body_buffer,
             integrate_data_layout,
             integrate_params_layout,
@@ -555,12 +603,13 @@ impl GpuPhysics {
             }],
         });
 
+        let pipelines = self.broadphase_pipelines.get();
         let dispatcher = ComputeDispatcher::new(ctx);
         let workgroups = compute_workgroup_count(body_count, WORKGROUP_SIZE);
 
         // Pass 1: Assign cells
         dispatcher.dispatch(
-            &self.assign_cells_pipeline,
+            &pipelines.assign_cells,
             &[&data_bind_group, &params_bind_group],
             [workgroups, 1, 1],
             Some("assign cells"),
@@ -568,7 +617,7 @@ impl GpuPhysics {
 
         // Pass 2: Spatial broadphase
         dispatcher.dispatch(
-            &self.broadphase_spatial_pipeline,
+            &pipelines.spatial,
             &[&data_bind_group, &params_bind_group],
             [workgroups, 1, 1],
             Some("broadphase spatial"),
@@ -609,54 +658,7 @@ impl GpuPhysics {
             let transform = world.get::<&GlobalTransform>(*entity).ok();
 
             let shape_data = if let (Some(collider), Some(transform)) = (collider, transform) {
-                let mut adjusted = *transform;
-                if collider.offset != Vec3::ZERO {
-                    adjusted.0 *= glam::Mat4::from_translation(collider.offset);
-                }
-
-                let position = adjusted.0.transform_point3(Vec3::ZERO);
-                let axis_x = adjusted.0.x_axis.truncate().normalize_or_zero();
-                let axis_y = adjusted.0.y_axis.truncate().normalize_or_zero();
-                let axis_z = adjusted.0.z_axis.truncate().normalize_or_zero();
-                let scale_x = adjusted.0.x_axis.truncate().length();
-                let scale_y = adjusted.0.y_axis.truncate().length();
-                let scale_z = adjusted.0.z_axis.truncate().length();
-
-                match &collider.shape {
-                    ColliderShape::Sphere { radius } => GpuShapeData {
-                        position: position.into(),
-                        shape_type: 0,
-                        data: [*radius, 0.0, 0.0, 0.0],
-                        axis_x: axis_x.into(),
-                        scale_x,
-                        axis_y: axis_y.into(),
-                        scale_y,
-                        axis_z: axis_z.into(),
-                        scale_z,
-                    },
-                    ColliderShape::Box { half_extents } => GpuShapeData {
-                        position: position.into(),
-                        shape_type: 1,
-                        data: [half_extents.x, half_extents.y, half_extents.z, 0.0],
-                        axis_x: axis_x.into(),
-                        scale_x,
-                        axis_y: axis_y.into(),
-                        scale_y,
-                        axis_z: axis_z.into(),
-                        scale_z,
-                    },
-                    _ => GpuShapeData {
-                        position: position.into(),
-                        shape_type: 255,
-                        data: [0.0; 4],
-                        axis_x: axis_x.into(),
-                        scale_x,
-                        axis_y: axis_y.into(),
-                        scale_y,
-                        axis_z: axis_z.into(),
-                        scale_z,
-                    },
-                }
+                gpu_shape_data(&collider, &transform)
             } else {
                 GpuShapeData {
                     position: [0.0; 3],
@@ -784,7 +786,7 @@ impl GpuPhysics {
             let dispatcher = ComputeDispatcher::new(ctx);
             let workgroups = compute_workgroup_count(gpu_pair_count, WORKGROUP_SIZE);
             dispatcher.dispatch(
-                &self.narrowphase_pipeline,
+                &self.narrowphase_pipeline.get(),
                 &[&data_bind_group, &params_bind_group],
                 [workgroups, 1, 1],
                 Some("narrowphase"),
@@ -845,7 +847,7 @@ impl GpuPhysics {
         let dispatcher = ComputeDispatcher::new(ctx);
         let workgroups = compute_workgroup_count(pair_count, WORKGROUP_SIZE);
         dispatcher.dispatch(
-            &self.narrowphase_pipeline,
+            &self.narrowphase_pipeline.get(),
             &[&data_bind_group, &params_bind_group],
             [workgroups, 1, 1],
             Some("narrowphase direct"),
@@ -951,7 +953,8 @@ impl GpuPhysics {
             _pad2: 0.0,
         };
 
-        self.dispatch_integrate(ctx, &self.integrate_vel_pipeline, body_count, &params);
+        let pipelines = self.integrate_pipelines.get();
+        self.dispatch_integrate(ctx, &pipelines.velocities, body_count, &params);
     }
 
     /// Dispatch GPU position integration.
@@ -971,7 +974,8 @@ impl GpuPhysics {
             _pad2: 0.0,
         };
 
-        self.dispatch_integrate(ctx, &self.integrate_pos_pipeline, body_count, &params);
+        let pipelines = self.integrate_pipelines.get();
+        self.dispatch_integrate(ctx, &pipelines.positions, body_count, &params);
     }
 
     fn dispatch_integrate(
@@ -1062,12 +1066,66 @@ impl GpuPhysics {
         self.pair_count_buffer.buffer()
     }
 
+    /// Number of bodies the GPU buffers hold. Larger worlds are not uploaded.
+    pub fn max_bodies(&self) -> usize {
+        self.max_bodies
+    }
+
+    /// Rebuild the compute pipelines whose shader files changed, when hot
+    /// reload is enabled (see [`hot_reload`](crate::core::hot_reload)).
+    /// Returns whether any was rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        let broadphase = self.broadphase_pipelines.reload_if_changed();
+        let narrowphase = self.narrowphase_pipeline.reload_if_changed();
+        let integrate = self.integrate_pipelines.reload_if_changed();
+        broadphase || narrowphase || integrate
+    }
+
     /// Check if GPU offload should be used based on body count.
     pub fn should_use_gpu(body_count: usize) -> bool {
         body_count >= GPU_BODY_THRESHOLD
     }
 }
 
+/// Pack a collider and its world transform into the GPU shape layout.
+///
+/// Shapes other than spheres and boxes are tagged with type 255 and ignored
+/// by the GPU kernels.
+pub(crate) fn gpu_shape_data(collider: &Collider, transform: &GlobalTransform) -> GpuShapeData {
+    let mut adjusted = *transform;
+    if collider.offset != Vec3::ZERO {
+        adjusted.0 *= glam::Mat4::from_translation(collider.offset);
+    }
+
+    let position = adjusted.0.transform_point3(Vec3::ZERO);
+    let axis_x = adjusted.0.x_axis.truncate().normalize_or_zero();
+    let axis_y = adjusted.0.y_axis.truncate().normalize_or_zero();
+    let axis_z = adjusted.0.z_axis.truncate().normalize_or_zero();
+    let scale_x = adjusted.0.x_axis.truncate().length();
+    let scale_y = adjusted.0.y_axis.truncate().length();
+    let scale_z = adjusted.0.z_axis.truncate().length();
+
+    let (shape_type, data) = match &collider.shape {
+        ColliderShape::Sphere { radius } => (0, [*radius, 0.0, 0.0, 0.0]),
+        ColliderShape::Box { half_extents } => {
+            (1, [half_extents.x, half_extents.y, half_extents.z, 0.0])
+        }
+        _ => (255, [0.0; 4]),
+    };
+
+    GpuShapeData {
+        position: position.into(),
+        shape_type,
+        data,
+        axis_x: axis_x.into(),
+        scale_x,
+        axis_y: axis_y.into(),
+        scale_y,
+        axis_z: axis_z.into(),
+        scale_z,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
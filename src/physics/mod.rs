@@ -4,7 +4,7 @@
 //!
 //! The physics pipeline runs in a fixed timestep loop:
 //!
-//! 1. Apply forces (gravity)
+//! 1. Apply forces (gravity, force fields)
 //! 2. Integrate velocities
 //! 3. Broadphase collision detection (AABB overlap)
 //! 4. Narrowphase collision detection (GJK/EPA, SAT, specialized tests)
@@ -12,24 +12,42 @@
 //! 6. Integrate positions
 //! 7. Synchronize transforms
 //! 8. Clear force accumulators
+//!
+//! # Clocks
+//!
+//! [`PhysicsWorld::step`] and [`PhysicsWorld::step_gpu`] pass their delta
+//! through the world's own [`Time`] clock, which pauses, scales and
+//! single-steps the simulation. Engine-managed physics (see
+//! `engine::GameLoopConfig::physics`) instead runs one fixed step per engine
+//! fixed step and follows the engine's `Time` resource: the engine copies it
+//! into [`PhysicsWorld::time`] every frame, so pause or scale the resource.
+//!
+//! Counters and per-stage timings of the latest fixed step are available from
+//! [`PhysicsWorld::stats`]; each stage also runs inside a `physics_stage`
+//! `tracing` span.
 
 pub mod broadphase;
 pub mod collider;
 pub mod contact;
+pub mod force_field;
 #[cfg(feature = "gpu-physics")]
 pub mod gpu;
 pub mod narrowphase;
 pub mod rigid_body;
 pub mod solver;
+pub mod stats;
 
 use glam::Vec3;
 
 use crate::ecs::components::physics::Collider;
 use crate::ecs::components::transform::GlobalTransform;
+use crate::time::Time;
 
 use self::broadphase::SpatialHashGrid;
-use self::contact::{ContactCache, ContactManifold, ContactPoint};
-use self::narrowphase::detect_collision;
+use self::contact::{ContactCache, ContactManifold, ContactMaterial, ContactPoint};
+use self::narrowphase::detect_contacts;
+
+pub use self::stats::{PhysicsPath, PhysicsStats, StageTimings};
 
 /// Configuration for the physics simulation.
 #[derive(Debug, Clone)]
@@ -62,12 +80,16 @@ impl Default for PhysicsConfig {
 /// The main physics world managing simulation state.
 pub struct PhysicsWorld {
     config: PhysicsConfig,
+    time: Time,
     accumulator: f64,
     broadphase: SpatialHashGrid,
     contacts: Vec<ContactManifold>,
     contact_cache: ContactCache,
+    stats: PhysicsStats,
     #[cfg(feature = "gpu-physics")]
     gpu_physics: Option<gpu::GpuPhysics>,
+    #[cfg(feature = "gpu-physics")]
+    gpu_capacity_warned: bool,
 }
 
 impl PhysicsWorld {
@@ -75,18 +97,24 @@ impl PhysicsWorld {
     pub fn new(config: PhysicsConfig) -> Self {
         Self {
             config,
+            time: Time::new(),
             accumulator: 0.0,
             broadphase: SpatialHashGrid::new(),
             contacts: Vec::new(),
             contact_cache: ContactCache::new(),
+            stats: PhysicsStats::default(),
             #[cfg(feature = "gpu-physics")]
             gpu_physics: None,
+            #[cfg(feature = "gpu-physics")]
+            gpu_capacity_warned: false,
         }
     }
 
     /// Initialize GPU physics resources. Only available with the `gpu-physics` feature.
     ///
     /// Call this once after creating the physics world to enable GPU acceleration.
+    /// Steps with more bodies than `initial_capacity` (at least 256) run on the
+    /// CPU path; call again with a larger capacity to re-enable the GPU.
     #[cfg(feature = "gpu-physics")]
     pub fn init_gpu(
         &mut self,
@@ -94,6 +122,7 @@ impl PhysicsWorld {
         initial_capacity: usize,
     ) -> anyhow::Result<()> {
         self.gpu_physics = Some(gpu::GpuPhysics::new(ctx, initial_capacity)?);
+        self.gpu_capacity_warned = false;
         Ok(())
     }
 
@@ -103,11 +132,39 @@ impl PhysicsWorld {
         self.gpu_physics.as_ref()
     }
 
+    /// The simulation configuration.
+    pub fn config(&self) -> &PhysicsConfig {
+        &self.config
+    }
+
+    /// The clock applied by [`step`](Self::step) and [`step_gpu`](Self::step_gpu).
+    ///
+    /// With engine-managed physics, a copy of the engine's `Time` resource.
+    pub fn time(&self) -> &Time {
+        &self.time
+    }
+
+    /// Mutable access to the clock, to pause, scale or single-step the
+    /// simulation. A single step runs exactly one fixed step.
+    ///
+    /// With engine-managed physics the engine overwrites this clock every
+    /// frame; change its `Time` resource instead.
+    pub fn time_mut(&mut self) -> &mut Time {
+        &mut self.time
+    }
+
+    /// Counters and stage timings for the most recent fixed step.
+    pub fn stats(&self) -> &PhysicsStats {
+        &self.stats
+    }
+
     /// Step the physics simulation forward by `delta_time` seconds.
     ///
     /// Uses a fixed timestep accumulator to ensure deterministic simulation.
+    /// `delta_time` passes through the [`time`](Self::time) clock first, so
+    /// pausing, time scale, single-stepping and the max-delta clamp apply.
     pub fn step(&mut self, world: &mut hecs::World, delta_time: f64) {
-        self.accumulator += delta_time;
+        self.accumulator += self.time.advance(delta_time, self.config.fixed_timestep);
 
         let mut substeps = 0u32;
         while self.accumulator >= self.config.fixed_timestep && substeps < self.config.max_substeps
@@ -116,6 +173,7 @@ impl PhysicsWorld {
             self.accumulator -= self.config.fixed_timestep;
             substeps += 1;
         }
+        self.stats.substeps = substeps;
 
         // Clamp accumulator to avoid spiral of death
         if self.accumulator > self.config.fixed_timestep * self.config.max_substeps as f64 {
@@ -123,10 +181,33 @@ impl PhysicsWorld {
         }
     }
 
+    /// Run exactly one fixed step of `dt` seconds, bypassing the accumulator
+    /// and the clock.
+    ///
+    /// For callers that own the fixed-timestep loop and clock, such as
+    /// `engine::run_app`.
+    pub fn step_fixed(&mut self, world: &mut hecs::World, dt: f32) {
+        self.fixed_step(world, dt);
+        self.stats.substeps = 1;
+    }
+
+    /// GPU variant of [`step_fixed`](Self::step_fixed).
+    #[cfg(feature = "gpu-physics")]
+    pub fn step_fixed_gpu(
+        &mut self,
+        world: &mut hecs::World,
+        dt: f32,
+        ctx: &crate::context::WgpuContext,
+    ) {
+        self.fixed_step_gpu(world, dt, ctx);
+        self.stats.substeps = 1;
+    }
+
     /// Step the physics simulation with GPU-accelerated broadphase.
     ///
     /// Uses GPU compute for AABB broadphase when body count exceeds the threshold,
-    /// falling back to CPU otherwise. Requires `gpu-physics` feature and prior
+    /// falling back to CPU otherwise, and always for worlds with compound
+    /// bodies (colliders on child entities). Requires `gpu-physics` feature and prior
     /// call to [`init_gpu`].
     #[cfg(feature = "gpu-physics")]
     pub fn step_gpu(
@@ -135,7 +216,7 @@ impl PhysicsWorld {
         delta_time: f64,
         ctx: &crate::context::WgpuContext,
     ) {
-        self.accumulator += delta_time;
+        self.accumulator += self.time.advance(delta_time, self.config.fixed_timestep);
 
         let mut substeps = 0u32;
         while self.accumulator >= self.config.fixed_timestep && substeps < self.config.max_substeps
@@ -144,6 +225,7 @@ impl PhysicsWorld {
             self.accumulator -= self.config.fixed_timestep;
             substeps += 1;
         }
+        self.stats.substeps = substeps;
 
         if self.accumulator > self.config.fixed_timestep * self.config.max_substeps as f64 {
             self.accumulator = 0.0;
@@ -157,23 +239,49 @@ impl PhysicsWorld {
         dt: f32,
         ctx: &crate::context::WgpuContext,
     ) {
-        rigid_body::apply_gravity(world, self.config.gravity);
-        rigid_body::integrate_velocities(world, dt);
+        let _span = tracing::debug_span!("physics_step", gpu = true).entered();
+        let mut stats = PhysicsStats {
+            path: PhysicsPath::GpuFallback,
+            ..Default::default()
+        };
 
-        // Sync transforms so GPU broadphase sees current positions
-        rigid_body::sync_transforms(world);
+        stats::timed("forces", &mut stats.timings.forces, || {
+            rigid_body::apply_gravity(world, self.config.gravity);
+            force_field::apply_force_fields(world, self.config.gravity);
+        });
+        stats::timed(
+            "integrate_velocities",
+            &mut stats.timings.integrate_velocities,
+            || {
+                rigid_body::integrate_velocities(world, dt);
+                // Sync transforms so GPU broadphase sees current positions
+                rigid_body::sync_transforms(world);
+            },
+        );
 
         self.contacts.clear();
 
-        if let Some(gpu) = &self.gpu_physics {
-            let (body_count, entity_map, max_extent) = gpu.upload_aabbs(ctx, world);
-            if gpu::GpuPhysics::should_use_gpu(body_count as usize) {
-                // GPU broadphase
+        // The GPU path only sees colliders on body entities
+        let gpu = self
+            .gpu_physics
+            .as_ref()
+            .filter(|_| !collider::has_compound_parts(world));
+        if let Some(gpu) = gpu {
+            let (body_count, entity_map, max_extent) =
+                stats::timed("broadphase", &mut stats.timings.broadphase, || {
+                    gpu.upload_aabbs(ctx, world)
+                });
+            let fits = body_count as usize <= gpu.max_bodies();
+            if !fits && !self.gpu_capacity_warned {
+                tracing::warn!(
+                    body_count,
+                    capacity = gpu.max_bodies(),
+                    "GPU physics capacity exceeded, falling back to CPU"
+                );
+                self.gpu_capacity_warned = true;
+            }
+            if fits && gpu::GpuPhysics::should_use_gpu(body_count as usize) {
                 let cell_size = (max_extent * 2.0).max(1.0);
-                gpu.dispatch_broadphase_with_cell_size(ctx, body_count, cell_size);
-
-                // Upload shape data for GPU narrowphase
-                gpu.upload_shapes(ctx, world, &entity_map);
 
                 // Check if all shapes are spheres (fast path: no box-box pairs possible).
                 // GPU narrowphase handles sphere-sphere, sphere-box, box-sphere but NOT box-box.
@@ -193,93 +301,154 @@ impl PhysicsWorld {
 
                 if all_spheres {
                     // Fast path: all sphere-sphere, skip broadphase readback
-                    let pair_count_data: Vec<u32> =
-                        crate::compute::read_buffer_sync(ctx, gpu.pair_count_buffer(), 4);
-                    let pair_count = pair_count_data
-                        .first()
-                        .copied()
-                        .unwrap_or(0)
-                        .min(gpu::MAX_PAIRS);
-
-                    gpu.dispatch_narrowphase_direct(ctx, pair_count);
-                    let gpu_results = gpu.readback_narrowphase(ctx, pair_count);
-                    Self::collect_gpu_narrowphase_results(
-                        world,
-                        &gpu_results,
-                        &entity_map,
-                        &mut self.contacts,
-                    );
-                } else {
-                    // Mixed path: readback pairs, classify per-pair, split GPU/CPU
-                    let broadphase_pairs = gpu.readback_pairs(ctx);
-                    let (gpu_np_count, cpu_pairs) =
-                        gpu.dispatch_narrowphase(ctx, &broadphase_pairs, &entity_map, world);
+                    stats.path = PhysicsPath::GpuDirect;
+                    let pair_count =
+                        stats::timed("broadphase", &mut stats.timings.broadphase, || {
+                            gpu.dispatch_broadphase_with_cell_size(ctx, body_count, cell_size);
+                            let pair_count_data: Vec<u32> =
+                                crate::compute::read_buffer_sync(ctx, gpu.pair_count_buffer(), 4);
+                            pair_count_data
+                                .first()
+                                .copied()
+                                .unwrap_or(0)
+                                .min(gpu::MAX_PAIRS)
+                        });
+                    stats.broadphase_pairs = pair_count as usize;
+                    stats.gpu_narrowphase_pairs = pair_count as usize;
 
-                    if gpu_np_count > 0 {
-                        let gpu_results = gpu.readback_narrowphase(ctx, gpu_np_count);
+                    stats::timed("narrowphase", &mut stats.timings.narrowphase, || {
+                        gpu.upload_shapes(ctx, world, &entity_map);
+                        gpu.dispatch_narrowphase_direct(ctx, pair_count);
+                        let gpu_results = gpu.readback_narrowphase(ctx, pair_count);
                         Self::collect_gpu_narrowphase_results(
                             world,
                             &gpu_results,
                             &entity_map,
                             &mut self.contacts,
                         );
-                    }
+                    });
+                } else {
+                    // Mixed path: readback pairs, classify per-pair, split GPU/CPU
+                    stats.path = PhysicsPath::GpuMixed;
+                    let broadphase_pairs =
+                        stats::timed("broadphase", &mut stats.timings.broadphase, || {
+                            gpu.dispatch_broadphase_with_cell_size(ctx, body_count, cell_size);
+                            gpu.readback_pairs(ctx)
+                        });
+                    stats.broadphase_pairs = broadphase_pairs.len();
+
+                    let gpu_np_count =
+                        stats::timed("narrowphase", &mut stats.timings.narrowphase, || {
+                            gpu.upload_shapes(ctx, world, &entity_map);
+                            let (gpu_np_count, cpu_pairs) = gpu.dispatch_narrowphase(
+                                ctx,
+                                &broadphase_pairs,
+                                &entity_map,
+                                world,
+                            );
 
-                    Self::run_cpu_narrowphase(world, &cpu_pairs, &mut self.contacts);
+                            if gpu_np_count > 0 {
+                                let gpu_results = gpu.readback_narrowphase(ctx, gpu_np_count);
+                                Self::collect_gpu_narrowphase_results(
+                                    world,
+                                    &gpu_results,
+                                    &entity_map,
+                                    &mut self.contacts,
+                                );
+                            }
+
+                            Self::run_cpu_narrowphase(world, &cpu_pairs, &mut self.contacts);
+                            gpu_np_count
+                        });
+                    stats.gpu_narrowphase_pairs = gpu_np_count as usize;
                 }
-            } else {
-                // Fallback to CPU
-                let pairs = self.broadphase.find_pairs(world);
-                Self::run_cpu_narrowphase(world, &pairs, &mut self.contacts);
             }
-        } else {
-            let pairs = self.broadphase.find_pairs(world);
-            Self::run_cpu_narrowphase(world, &pairs, &mut self.contacts);
-        };
+        }
+
+        if !stats.path.used_gpu() {
+            // Fallback to CPU
+            let pairs = stats::timed("broadphase", &mut stats.timings.broadphase, || {
+                self.broadphase.find_pairs(world)
+            });
+            stats.broadphase_pairs = pairs.len();
+            stats::timed("narrowphase", &mut stats.timings.narrowphase, || {
+                Self::run_cpu_narrowphase(world, &pairs, &mut self.contacts);
+            });
+        }
 
-        self.contact_cache.warm_start(&mut self.contacts);
-        solver::solve_contacts(&mut self.contacts, world, self.config.solver_iterations);
-        self.contact_cache.update(&self.contacts);
-        rigid_body::integrate_positions(world, dt);
-        rigid_body::sync_transforms(world);
-        rigid_body::clear_forces(world);
-        rigid_body::update_sleep_states(world, dt);
+        self.finish_step(world, dt, stats);
     }
 
     fn fixed_step(&mut self, world: &mut hecs::World, dt: f32) {
-        // 1. Apply forces (gravity)
-        rigid_body::apply_gravity(world, self.config.gravity);
+        let _span = tracing::debug_span!("physics_step", gpu = false).entered();
+        let mut stats = PhysicsStats::default();
+
+        // 1. Apply forces (gravity, force fields)
+        stats::timed("forces", &mut stats.timings.forces, || {
+            rigid_body::apply_gravity(world, self.config.gravity);
+            force_field::apply_force_fields(world, self.config.gravity);
+        });
 
         // 2. Integrate velocities
-        rigid_body::integrate_velocities(world, dt);
+        stats::timed(
+            "integrate_velocities",
+            &mut stats.timings.integrate_velocities,
+            || rigid_body::integrate_velocities(world, dt),
+        );
 
         // 3. Broadphase collision detection
-        let pairs = self.broadphase.find_pairs(world);
+        let pairs = stats::timed("broadphase", &mut stats.timings.broadphase, || {
+            self.broadphase.find_pairs(world)
+        });
+        stats.broadphase_pairs = pairs.len();
 
         // 4. Narrowphase collision detection
         self.contacts.clear();
-        Self::run_cpu_narrowphase(world, &pairs, &mut self.contacts);
+        stats::timed("narrowphase", &mut stats.timings.narrowphase, || {
+            Self::run_cpu_narrowphase(world, &pairs, &mut self.contacts);
+        });
+
+        // 5.-11. Solve, integrate positions, finalize
+        self.finish_step(world, dt, stats);
+    }
 
-        // 5. Warm-start from cached impulses
-        self.contact_cache.warm_start(&mut self.contacts);
+    /// Shared tail of `fixed_step`/`fixed_step_gpu`: solve contacts, integrate
+    /// positions, finalize and record stats.
+    fn finish_step(&mut self, world: &mut hecs::World, dt: f32, mut stats: PhysicsStats) {
+        stats::timed("solver", &mut stats.timings.solver, || {
+            // 5. Warm-start from cached impulses
+            self.contact_cache.warm_start(&mut self.contacts);
 
-        // 6. Solve contact constraints
-        solver::solve_contacts(&mut self.contacts, world, self.config.solver_iterations);
+            // 6. Solve contact constraints
+            solver::solve_contacts(&mut self.contacts, world, self.config.solver_iterations);
 
-        // 7. Update contact cache for next frame
-        self.contact_cache.update(&self.contacts);
+            // 7. Update contact cache for next frame
+            self.contact_cache.update(&self.contacts);
+        });
 
         // 8. Integrate positions
-        rigid_body::integrate_positions(world, dt);
+        stats::timed(
+            "integrate_positions",
+            &mut stats.timings.integrate_positions,
+            || rigid_body::integrate_positions(world, dt),
+        );
 
-        // 9. Synchronize transforms
-        rigid_body::sync_transforms(world);
+        stats::timed("finalize", &mut stats.timings.finalize, || {
+            // 9. Synchronize transforms
+            rigid_body::sync_transforms(world);
 
-        // 10. Clear force accumulators
-        rigid_body::clear_forces(world);
+            // 10. Clear force accumulators
+            rigid_body::clear_forces(world);
 
-        // 11. Update sleep states
-        rigid_body::update_sleep_states(world, dt);
+            // 11. Update sleep states
+            rigid_body::update_sleep_states(world, dt);
+        });
+
+        stats.manifolds = self.contacts.len();
+        stats.contacts = self.contacts.iter().map(|m| m.contacts.len()).sum();
+        stats.count_bodies(world);
+        stats.substeps = self.stats.substeps;
+        self.stats = stats;
     }
 
     /// Collect GPU narrowphase results into contact manifolds.
@@ -306,6 +475,7 @@ impl PhysicsWorld {
             contacts.push(ContactManifold {
                 entity_a,
                 entity_b,
+                material: ContactMaterial::between(world, entity_a, entity_b),
                 normal: Vec3::from(result.normal),
                 contacts: vec![ContactPoint {
                     position: Vec3::from(result.point),
@@ -317,53 +487,64 @@ impl PhysicsWorld {
         }
     }
 
-    /// Run CPU narrowphase on a set of entity pairs, appending results to contacts.
+    /// Run CPU narrowphase on a set of collider entity pairs, appending
+    /// results to contacts.
+    ///
+    /// Manifolds are between the colliders' bodies, with the material of the
+    /// two colliders, so each part of a compound body keeps its own material.
     fn run_cpu_narrowphase(
         world: &mut hecs::World,
         pairs: &[(hecs::Entity, hecs::Entity)],
         contacts: &mut Vec<ContactManifold>,
     ) {
         for (entity_a, entity_b) in pairs {
-            let contact = {
+            let placed = (
+                collider::collider_transform(world, *entity_a),
+                collider::collider_transform(world, *entity_b),
+            );
+            let (Some((body_a, ta)), Some((body_b, tb))) = placed else {
+                continue;
+            };
+            let points = {
                 let collider_a = world.get::<&Collider>(*entity_a);
                 let collider_b = world.get::<&Collider>(*entity_b);
-                let transform_a = world.get::<&GlobalTransform>(*entity_a);
-                let transform_b = world.get::<&GlobalTransform>(*entity_b);
 
-                if let (Ok(ca), Ok(cb), Ok(ta), Ok(tb)) =
-                    (collider_a, collider_b, transform_a, transform_b)
-                {
+                if let (Ok(ca), Ok(cb)) = (collider_a, collider_b) {
                     let adjusted_a = if ca.offset != Vec3::ZERO {
                         GlobalTransform(ta.0 * glam::Mat4::from_translation(ca.offset))
                     } else {
-                        *ta
+                        ta
                     };
                     let adjusted_b = if cb.offset != Vec3::ZERO {
                         GlobalTransform(tb.0 * glam::Mat4::from_translation(cb.offset))
                     } else {
-                        *tb
+                        tb
                     };
 
-                    detect_collision(&ca.shape, &adjusted_a, &cb.shape, &adjusted_b)
+                    detect_contacts(&ca.shape, &adjusted_a, &cb.shape, &adjusted_b)
                 } else {
-                    None
+                    Vec::new()
                 }
             };
 
-            if let Some(info) = contact {
-                rigid_body::wake_body(world, *entity_a);
-                rigid_body::wake_body(world, *entity_b);
+            if let Some(first) = points.first() {
+                rigid_body::wake_body(world, body_a);
+                rigid_body::wake_body(world, body_b);
 
                 contacts.push(ContactManifold {
-                    entity_a: *entity_a,
-                    entity_b: *entity_b,
-                    normal: info.normal,
-                    contacts: vec![ContactPoint {
-                        position: info.point,
-                        penetration: info.penetration,
-                        normal_impulse: 0.0,
-                        tangent_impulse: [0.0; 2],
-                    }],
+                    entity_a: body_a,
+                    entity_b: body_b,
+                    material: ContactMaterial::between(world, *entity_a, *entity_b),
+                    normal: first.normal,
+                    contacts: points
+                        .iter()
+                        .map(|info| ContactPoint {
+                            position: info.point,
+                            penetration: info.penetration,
+                            normal_impulse: 0.0,
+                            tangent_impulse: [0.0; 2],
+                        })
+                        .collect(),
                 });
             }
         }
@@ -373,10 +554,62 @@ impl PhysicsWorld {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ecs::components::physics::{Collider, ColliderShape, RigidBody};
-    use crate::ecs::components::transform::{GlobalTransform, Transform};
+    use crate::ecs::components::physics::{
+        AnisotropicFriction, Collider, ColliderShape, PhysicsMaterial, RigidBody, SleepInfo,
+    };
+    use crate::ecs::components::transform::{GlobalTransform, Parent, Transform};
+    #[cfg(feature = "scene")]
+    use crate::ecs::scene::spawn_fixture;
     use glam::Mat4;
 
+    /// Drop a ball onto a static ground with the given material on both and
+    /// return the highest point reached after the first bounce.
+    fn bounce_height(material: PhysicsMaterial) -> f32 {
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+
+        let start = Vec3::new(0.0, 3.0, 0.0);
+        let ball = world.spawn((
+            Transform::from_position(start),
+            GlobalTransform(Mat4::from_translation(start)),
+            RigidBody::new_dynamic(1.0),
+            Collider {
+                shape: ColliderShape::Sphere { radius: 0.5 },
+                offset: Vec3::ZERO,
+                is_sensor: false,
+                material: Some(material),
+            },
+        ));
+        world.spawn((
+            Transform::from_position(Vec3::new(0.0, -0.5, 0.0)),
+            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, -0.5, 0.0))),
+            RigidBody::new_static(),
+            Collider {
+                shape: ColliderShape::Box {
+                    half_extents: Vec3::new(10.0, 0.5, 10.0),
+                },
+                offset: Vec3::ZERO,
+                is_sensor: false,
+                material: Some(material),
+            },
+        ));
+
+        let mut bounced = false;
+        let mut peak = f32::MIN;
+        for _ in 0..180 {
+            physics.step(&mut world, 1.0 / 60.0);
+            let vy = world.get::<&RigidBody>(ball).unwrap().linear_velocity.y;
+            let y = world.get::<&Transform>(ball).unwrap().position.y;
+            if vy > 0.0 {
+                bounced = true;
+            }
+            if bounced {
+                peak = peak.max(y);
+            }
+        }
+        peak
+    }
+
     #[test]
     fn test_physics_world_free_fall() {
         let mut world = hecs::World::new();
@@ -390,6 +623,7 @@ mod tests {
                 shape: ColliderShape::Sphere { radius: 0.5 },
                 offset: Vec3::ZERO,
                 is_sensor: false,
+                material: None,
             },
         ));
 
@@ -407,8 +641,12 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "scene")]
     fn test_physics_world_collision() {
-        let mut world = hecs::World::new();
+        // Dynamic box falling onto a static ground slab at y=0
+        let (mut world, entities) =
+            spawn_fixture(include_str!("../../tests/fixtures/box_on_ground.ron"));
+        let dynamic_entity = entities[0];
         let config = PhysicsConfig {
             gravity: Vec3::new(0.0, -9.81, 0.0),
             fixed_timestep: 1.0 / 60.0,
@@ -418,34 +656,6 @@ mod tests {
         };
         let mut physics = PhysicsWorld::new(config);
 
-        // Dynamic box falling
-        let dynamic_entity = world.spawn((
-            Transform::from_position(Vec3::new(0.0, 2.0, 0.0)),
-            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, 2.0, 0.0))),
-            RigidBody::new_dynamic(1.0),
-            Collider {
-                shape: ColliderShape::Box {
-                    half_extents: Vec3::splat(0.5),
-                },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
-        // Static ground plane (large box at y=0)
-        world.spawn((
-            Transform::from_position(Vec3::new(0.0, -0.5, 0.0)),
-            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, -0.5, 0.0))),
-            RigidBody::new_static(),
-            Collider {
-                shape: ColliderShape::Box {
-                    half_extents: Vec3::new(50.0, 0.5, 50.0),
-                },
-                offset: Vec3::ZERO,
-                is_sensor: false,
-            },
-        ));
-
         // Simulate 3 seconds
         for _ in 0..180 {
             physics.step(&mut world, 1.0 / 60.0);
@@ -477,6 +687,347 @@ mod tests {
         );
     }
 
+    /// Place a unit box on a static slope tilted by `angle` about Z and
+    /// simulate two seconds. Returns how far the box moved.
+    fn slide_distance(
+        angle_deg: f32,
+        material: PhysicsMaterial,
+        initial_speed: f32,
+        anisotropy: Option<AnisotropicFriction>,
+    ) -> f32 {
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+
+        let rotation = glam::Quat::from_rotation_z(angle_deg.to_radians());
+        let slope = world.spawn((
+            Transform {
+                rotation,
+                ..Default::default()
+            },
+            GlobalTransform(Mat4::from_quat(rotation)),
+            RigidBody::new_static(),
+            Collider {
+                shape: ColliderShape::Box {
+                    half_extents: Vec3::new(20.0, 0.5, 20.0),
+                },
+                offset: Vec3::ZERO,
+                is_sensor: false,
+                material: Some(material),
+            },
+        ));
+        if let Some(anisotropy) = anisotropy {
+            world.insert_one(slope, anisotropy).unwrap();
+        }
+
+        let start = rotation * Vec3::new(0.0, 0.995, 0.0);
+        let mut body = RigidBody::new_dynamic(1.0);
+        // Downhill direction for a slope tilted about +Z
+        body.linear_velocity = rotation * Vec3::NEG_X * initial_speed;
+        let cube = world.spawn((
+            Transform {
+                position: start,
+                rotation,
+                ..Default::default()
+            },
+            GlobalTransform(Mat4::from_rotation_translation(rotation, start)),
+            body,
+            Collider {
+                shape: ColliderShape::Box {
+                    half_extents: Vec3::splat(0.5),
+                },
+                offset: Vec3::ZERO,
+                is_sensor: false,
+                material: Some(material),
+            },
+        ));
+
+        for _ in 0..120 {
+            physics.step(&mut world, 1.0 / 60.0);
+        }
+
+        let end = world.get::<&Transform>(cube).unwrap().position;
+        (end - start).length()
+    }
+
+    #[test]
+    fn test_box_holds_on_slope_below_friction_angle() {
+        // atan(0.6) is about 31 degrees
+        let moved = slide_distance(20.0, PhysicsMaterial::new(0.6, 0.0), 0.0, None);
+        assert!(
+            moved < 0.05,
+            "Box should hold still on the slope: moved {moved}"
+        );
+    }
+
+    #[test]
+    fn test_box_slides_above_friction_angle() {
+        let moved = slide_distance(45.0, PhysicsMaterial::new(0.6, 0.0), 0.0, None);
+        assert!(
+            moved > 1.0,
+            "Box should slide down the slope: moved {moved}"
+        );
+    }
+
+    #[test]
+    fn test_static_friction_exceeds_dynamic_friction() {
+        // tan(20 deg) = 0.36 lies between the dynamic and static coefficients:
+        // a box at rest stays put, a box already sliding keeps going.
+        let material = PhysicsMaterial {
+            static_friction: 0.6,
+            dynamic_friction: 0.2,
+            restitution: 0.0,
+            ..Default::default()
+        };
+        let resting = slide_distance(20.0, material, 0.0, None);
+        let sliding = slide_distance(20.0, material, 1.0, None);
+        assert!(resting < 0.05, "Resting box should stick: moved {resting}");
+        assert!(
+            sliding > 2.0,
+            "Sliding box should keep sliding: moved {sliding}"
+        );
+    }
+
+    #[test]
+    fn test_anisotropic_friction_direction() {
+        // Grip across X (the downhill direction) is scaled down, so the box
+        // slides even though the slope is below the isotropic friction angle.
+        let material = PhysicsMaterial::new(0.6, 0.0);
+        let grippy = AnisotropicFriction::new(Vec3::Z, 1.0, 1.0);
+        let slippery = AnisotropicFriction::new(Vec3::Z, 1.0, 0.2);
+        let held = slide_distance(20.0, material, 0.0, Some(grippy));
+        let slid = slide_distance(20.0, material, 0.0, Some(slippery));
+        assert!(held < 0.05, "Box should hold with full grip: moved {held}");
+        assert!(
+            slid > 1.0,
+            "Box should slide with reduced grip: moved {slid}"
+        );
+    }
+
+    #[test]
+    fn test_rolling_friction_stops_ball() {
+        let roll = |rolling_friction: f32| {
+            let mut world = hecs::World::new();
+            let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+            let material = PhysicsMaterial {
+                rolling_friction,
+                restitution: 0.0,
+                ..Default::default()
+            };
+            let start = Vec3::new(0.0, 0.499, 0.0);
+            let mut body = RigidBody::new_dynamic(1.0);
+            body.linear_velocity = Vec3::new(2.0, 0.0, 0.0);
+            body.angular_velocity = Vec3::new(0.0, 0.0, -4.0);
+            body.linear_damping = 0.0;
+            body.angular_damping = 0.0;
+            let ball = world.spawn((
+                Transform::from_position(start),
+                GlobalTransform(Mat4::from_translation(start)),
+                body,
+                Collider {
+                    shape: ColliderShape::Sphere { radius: 0.5 },
+                    offset: Vec3::ZERO,
+                    is_sensor: false,
+                    material: Some(material),
+                },
+            ));
+            world.spawn((
+                Transform::from_position(Vec3::new(0.0, -0.5, 0.0)),
+                GlobalTransform(Mat4::from_translation(Vec3::new(0.0, -0.5, 0.0))),
+                RigidBody::new_static(),
+                Collider {
+                    shape: ColliderShape::Box {
+                        half_extents: Vec3::new(50.0, 0.5, 50.0),
+                    },
+                    offset: Vec3::ZERO,
+                    is_sensor: false,
+                    material: Some(material),
+                },
+            ));
+            for _ in 0..180 {
+                physics.step(&mut world, 1.0 / 60.0);
+            }
+            let rb = world.get::<&RigidBody>(ball).unwrap();
+            rb.linear_velocity.length()
+        };
+
+        let free = roll(0.0);
+        let resisted = roll(0.3);
+        assert!(
+            free > 1.0,
+            "Ball without rolling friction keeps rolling: {free}"
+        );
+        assert!(
+            resisted < 0.1,
+            "Rolling friction should stop the ball: {resisted}"
+        );
+    }
+
+    #[test]
+    fn test_material_restitution_affects_bounce() {
+        let rubber = bounce_height(PhysicsMaterial::rubber());
+        let metal = bounce_height(PhysicsMaterial::metal());
+        assert!(
+            rubber > metal + 0.3,
+            "Rubber should bounce higher than metal: rubber = {rubber}, metal = {metal}"
+        );
+    }
+
+    #[test]
+    fn test_compound_body_parts_keep_their_materials() {
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+        world.spawn((
+            Transform::from_position(Vec3::new(0.0, -0.5, 0.0)),
+            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, -0.5, 0.0))),
+            RigidBody::new_static(),
+            Collider {
+                shape: ColliderShape::Box {
+                    half_extents: Vec3::new(10.0, 0.5, 10.0),
+                },
+                material: Some(PhysicsMaterial::metal()),
+                ..Default::default()
+            },
+        ));
+
+        // A body without a collider of its own and two overlapping parts
+        let start = Vec3::new(0.0, 0.49, 0.0);
+        let body = world.spawn((
+            Transform::from_position(start),
+            GlobalTransform(Mat4::from_translation(start)),
+            RigidBody::new_dynamic(1.0),
+        ));
+        for (x, material) in [
+            (-0.4, PhysicsMaterial::ice()),
+            (0.4, PhysicsMaterial::rubber()),
+        ] {
+            let local = Transform::from_position(Vec3::new(x, 0.0, 0.0));
+            world.spawn((
+                local,
+                GlobalTransform(Mat4::from_translation(start) * local.to_matrix()),
+                Parent(body),
+                Collider {
+                    shape: ColliderShape::Sphere { radius: 0.5 },
+                    material: Some(material),
+                    ..Default::default()
+                },
+            ));
+        }
+
+        // One manifold per part against the ground, none between the parts
+        physics.step(&mut world, 1.0 / 60.0);
+        assert_eq!(physics.contacts.len(), 2);
+        let ground = PhysicsMaterial::metal();
+        for material in [PhysicsMaterial::ice(), PhysicsMaterial::rubber()] {
+            let expected = ContactMaterial::combine(&ground, &material);
+            assert!(physics
+                .contacts
+                .iter()
+                .any(|m| (m.entity_a == body || m.entity_b == body) && m.material == expected));
+        }
+
+        for _ in 0..60 {
+            physics.step(&mut world, 1.0 / 60.0);
+        }
+        let y = world.get::<&Transform>(body).unwrap().position.y;
+        assert!(
+            (y - 0.5).abs() < 0.05,
+            "compound body rests on its parts, y = {y}"
+        );
+    }
+
+    #[test]
+    fn test_physics_stats() {
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+
+        let cube = |y: f32, body: RigidBody| {
+            (
+                Transform::from_position(Vec3::new(0.0, y, 0.0)),
+                GlobalTransform(Mat4::from_translation(Vec3::new(0.0, y, 0.0))),
+                body,
+                Collider {
+                    shape: ColliderShape::Box {
+                        half_extents: Vec3::splat(0.5),
+                    },
+                    offset: Vec3::ZERO,
+                    is_sensor: false,
+                    material: None,
+                },
+                SleepInfo::default(),
+            )
+        };
+        world.spawn(cube(-0.5, RigidBody::new_static()));
+        world.spawn(cube(0.495, RigidBody::new_dynamic(1.0)));
+        world.spawn(cube(10.0, RigidBody::new_dynamic(1.0)));
+
+        // Below one fixed timestep: nothing runs
+        physics.step(&mut world, 0.001);
+        assert_eq!(physics.stats().substeps, 0);
+
+        physics.step(&mut world, 2.0 / 60.0 + 1e-4);
+        let stats = *physics.stats();
+        assert_eq!(stats.substeps, 2);
+        assert_eq!(stats.path, PhysicsPath::Cpu);
+        assert_eq!(stats.broadphase_pairs, 1);
+        assert_eq!(stats.manifolds, 1);
+        assert_eq!(stats.contacts, 4);
+        assert_eq!(stats.awake_bodies, 2);
+        assert_eq!(stats.sleeping_bodies, 0);
+        assert!(stats.timings.total() >= stats.timings.narrowphase);
+    }
+
+    #[test]
+    fn test_step_fixed_ignores_accumulator() {
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+        let entity = world.spawn((
+            Transform::from_position(Vec3::new(0.0, 10.0, 0.0)),
+            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, 10.0, 0.0))),
+            RigidBody::new_dynamic(1.0),
+        ));
+
+        // A partial accumulator step does nothing; step_fixed always advances.
+        physics.step(&mut world, 0.001);
+        let before = world.get::<&RigidBody>(entity).unwrap().linear_velocity.y;
+        physics.step_fixed(&mut world, 1.0 / 60.0);
+        let after = world.get::<&RigidBody>(entity).unwrap().linear_velocity.y;
+
+        assert_eq!(before, 0.0);
+        assert!(after < 0.0);
+        assert_eq!(physics.stats().substeps, 1);
+    }
+
+    #[test]
+    fn test_paused_clock_stops_step_until_single_step() {
+        let mut world = hecs::World::new();
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+        let entity = world.spawn((
+            Transform::from_position(Vec3::new(0.0, 10.0, 0.0)),
+            GlobalTransform(Mat4::from_translation(Vec3::new(0.0, 10.0, 0.0))),
+            RigidBody::new_dynamic(1.0),
+        ));
+
+        physics.time_mut().pause();
+        physics.step(&mut world, 1.0 / 30.0);
+        assert_eq!(physics.stats().substeps, 0);
+        assert_eq!(
+            world.get::<&RigidBody>(entity).unwrap().linear_velocity.y,
+            0.0
+        );
+
+        physics.time_mut().step();
+        physics.step(&mut world, 1.0 / 30.0);
+        assert_eq!(physics.stats().substeps, 1);
+        physics.step(&mut world, 1.0 / 30.0);
+        assert_eq!(physics.stats().substeps, 0);
+
+        // Half speed: a 1/30s frame is one 1/60s step
+        physics.time_mut().resume();
+        physics.time_mut().set_scale(0.5);
+        physics.step(&mut world, 1.0 / 30.0);
+        assert_eq!(physics.stats().substeps, 1);
+    }
+
     #[test]
     fn test_physics_config_default() {
         let config = PhysicsConfig::default();
@@ -486,4 +1037,48 @@ mod tests {
         assert_eq!(config.solver_iterations, 8);
         assert!(!config.use_gpu);
     }
+
+    #[cfg(feature = "gpu-physics")]
+    #[test]
+    fn test_gpu_step_falls_back_above_capacity() {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        }
+        let Ok(ctx) = crate::context::WgpuContext::new_blocking(None) else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+
+        let mut physics = PhysicsWorld::new(PhysicsConfig::default());
+        physics.init_gpu(&ctx, 256).unwrap();
+        assert_eq!(physics.gpu_physics_ref().unwrap().max_bodies(), 256);
+
+        let mut world = hecs::World::new();
+        let spawn = |world: &mut hecs::World, i: usize| {
+            let position = Vec3::new((i % 32) as f32 * 2.0, 10.0, (i / 32) as f32 * 2.0);
+            world.spawn((
+                Transform::from_position(position),
+                GlobalTransform(Mat4::from_translation(position)),
+                RigidBody::new_dynamic(1.0),
+                Collider {
+                    shape: ColliderShape::Sphere { radius: 0.5 },
+                    offset: Vec3::ZERO,
+                    is_sensor: false,
+                    material: None,
+                },
+            ));
+        };
+        for i in 0..256 {
+            spawn(&mut world, i);
+        }
+        physics.step_fixed_gpu(&mut world, 1.0 / 60.0, &ctx);
+        assert_eq!(physics.stats().path, PhysicsPath::GpuDirect);
+
+        for i in 256..300 {
+            spawn(&mut world, i);
+        }
+        physics.step_fixed_gpu(&mut world, 1.0 / 60.0, &ctx);
+        assert_eq!(physics.stats().path, PhysicsPath::GpuFallback);
+    }
 }
@@ -553,6 +553,94 @@ pub fn sat_box_box(
     })
 }
 
+/// Contact points for a box-box collision found by [`sat_box_box`].
+///
+/// Returns every vertex of either box that lies inside the other, with its
+/// depth along the SAT normal, so resting face contacts get a full manifold
+/// instead of a single point. Falls back to the SAT point for edge contacts.
+pub fn box_box_contact_points(
+    half_a: Vec3,
+    transform_a: glam::Mat4,
+    half_b: Vec3,
+    transform_b: glam::Mat4,
+    info: &ContactInfo,
+) -> Vec<ContactInfo> {
+    const TOLERANCE: f32 = 1e-3;
+    const DUPLICATE_DISTANCE_SQ: f32 = 1e-4;
+
+    let frame = |transform: glam::Mat4| {
+        (
+            transform.transform_point3(Vec3::ZERO),
+            [
+                transform.x_axis.truncate().normalize_or_zero(),
+                transform.y_axis.truncate().normalize_or_zero(),
+                transform.z_axis.truncate().normalize_or_zero(),
+            ],
+        )
+    };
+    let vertices = |center: Vec3, axes: &[Vec3; 3], half: Vec3| {
+        let mut out = [Vec3::ZERO; 8];
+        for (i, v) in out.iter_mut().enumerate() {
+            let sx = if i & 1 == 0 { -1.0 } else { 1.0 };
+            let sy = if i & 2 == 0 { -1.0 } else { 1.0 };
+            let sz = if i & 4 == 0 { -1.0 } else { 1.0 };
+            *v = center
+                + axes[0] * (half.x * sx)
+                + axes[1] * (half.y * sy)
+                + axes[2] * (half.z * sz);
+        }
+        out
+    };
+    let inside = |point: Vec3, center: Vec3, axes: &[Vec3; 3], half: Vec3| {
+        let d = point - center;
+        d.dot(axes[0]).abs() <= half.x + TOLERANCE
+            && d.dot(axes[1]).abs() <= half.y + TOLERANCE
+            && d.dot(axes[2]).abs() <= half.z + TOLERANCE
+    };
+
+    let (center_a, axes_a) = frame(transform_a);
+    let (center_b, axes_b) = frame(transform_b);
+    let normal = info.normal;
+    let face_a = vertices(center_a, &axes_a, half_a)
+        .iter()
+        .map(|v| v.dot(normal))
+        .fold(f32::MIN, f32::max);
+    let face_b = vertices(center_b, &axes_b, half_b)
+        .iter()
+        .map(|v| v.dot(normal))
+        .fold(f32::MAX, f32::min);
+
+    let mut points = Vec::new();
+    for v in vertices(center_b, &axes_b, half_b) {
+        if inside(v, center_a, &axes_a, half_a) {
+            points.push(ContactInfo {
+                normal,
+                penetration: (face_a - v.dot(normal)).max(0.0),
+                point: v,
+            });
+        }
+    }
+    for v in vertices(center_a, &axes_a, half_a) {
+        // Equal faces put vertices of both boxes at the same spot; keep one
+        let lateral = |p: Vec3| p - normal * p.dot(normal);
+        let duplicate = points
+            .iter()
+            .any(|c| (lateral(c.point) - lateral(v)).length_squared() < DUPLICATE_DISTANCE_SQ);
+        if !duplicate && inside(v, center_b, &axes_b, half_b) {
+            points.push(ContactInfo {
+                normal,
+                penetration: (v.dot(normal) - face_b).max(0.0),
+                point: v,
+            });
+        }
+    }
+
+    if points.is_empty() {
+        points.push(*info);
+    }
+    points
+}
+
 /// Test a single SAT axis. Returns Some(overlap) if overlapping, None if separating.
 #[inline]
 fn sat_test_axis(
@@ -721,6 +809,32 @@ pub fn detect_collision(
     }
 }
 
+/// Detect all contact points between two shapes.
+///
+/// Box-box pairs produce a multi-point manifold; other pairs produce the
+/// single contact from [`detect_collision`].
+pub fn detect_contacts(
+    shape_a: &ColliderShape,
+    transform_a: &GlobalTransform,
+    shape_b: &ColliderShape,
+    transform_b: &GlobalTransform,
+) -> Vec<ContactInfo> {
+    let Some(info) = detect_collision(shape_a, transform_a, shape_b, transform_b) else {
+        return Vec::new();
+    };
+    match (shape_a, shape_b) {
+        (
+            ColliderShape::Box {
+                half_extents: half_a,
+            },
+            ColliderShape::Box {
+                half_extents: half_b,
+            },
+        ) => box_box_contact_points(*half_a, transform_a.0, *half_b, transform_b.0, &info),
+        _ => vec![info],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -820,4 +934,39 @@ mod tests {
         let result = detect_collision(&shape_a, &transform_a, &shape_b, &transform_b);
         assert!(result.is_some());
     }
+
+    #[test]
+    fn test_box_box_face_contact_manifold() {
+        // Unit cube resting on a large slab: four bottom vertices touch.
+        let slab = GlobalTransform(Mat4::from_translation(Vec3::new(0.0, -0.5, 0.0)));
+        let cube = GlobalTransform(Mat4::from_translation(Vec3::new(0.0, 0.49, 0.0)));
+        let contacts = detect_contacts(
+            &ColliderShape::Box {
+                half_extents: Vec3::new(10.0, 0.5, 10.0),
+            },
+            &slab,
+            &ColliderShape::Box {
+                half_extents: Vec3::splat(0.5),
+            },
+            &cube,
+        );
+        assert_eq!(contacts.len(), 4);
+        for c in &contacts {
+            assert!((c.normal - Vec3::Y).length() < 1e-5);
+            assert!((c.penetration - 0.01).abs() < 1e-4);
+            assert!((c.point.y + 0.01).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_box_box_equal_faces_have_no_duplicate_points() {
+        // Equal cubes stacked: both boxes have vertices at the same corners.
+        let shape = ColliderShape::Box {
+            half_extents: Vec3::splat(0.5),
+        };
+        let lower = GlobalTransform(Mat4::IDENTITY);
+        let upper = GlobalTransform(Mat4::from_translation(Vec3::new(0.0, 0.99, 0.0)));
+        let contacts = detect_contacts(&shape, &lower, &shape, &upper);
+        assert_eq!(contacts.len(), 4);
+    }
 }
@@ -3,7 +3,7 @@
 use glam::{Quat, Vec3};
 
 use crate::ecs::components::physics::{RigidBody, RigidBodyType, SleepInfo, SleepState};
-use crate::ecs::components::transform::{GlobalTransform, Transform};
+use crate::ecs::components::transform::{GlobalTransform, Parent, Transform};
 
 /// Linear velocity threshold for sleep eligibility.
 const LINEAR_SLEEP_THRESHOLD: f32 = 0.1;
@@ -93,8 +93,12 @@ pub fn integrate_positions(world: &mut hecs::World, dt: f32) {
 }
 
 /// Synchronize RigidBody positions/rotations to Transform and GlobalTransform.
+///
+/// Only root entities are written; children are left to `transform_system`.
 pub fn sync_transforms(world: &mut hecs::World) {
-    for (_, (transform, global)) in world.query_mut::<(&Transform, &mut GlobalTransform)>() {
+    for (_, (transform, global)) in
+        world.query_mut::<hecs::Without<(&Transform, &mut GlobalTransform), &Parent>>()
+    {
         global.0 = transform.to_matrix();
     }
 }
@@ -11,6 +11,26 @@ use super::contact::ContactManifold;
 const BAUMGARTE_BETA: f32 = 0.2;
 /// Penetration slop (allowed penetration before position correction).
 const PENETRATION_SLOP: f32 = 0.005;
+/// Approach speed below which contacts do not bounce.
+const RESTITUTION_THRESHOLD: f32 = 0.5;
+/// Pre-solve tangential speed above which a contact counts as already sliding.
+/// Must exceed the velocity gravity adds in one step, or resting contacts on
+/// slopes would never get static friction.
+const SLIDING_THRESHOLD: f32 = 0.2;
+
+/// Solver state for one manifold, kept across iterations of a single solve.
+struct ManifoldState {
+    /// Separating velocity each contact should reach from restitution.
+    bounce: Vec<f32>,
+    /// Whether each contact was already sliding before the solve.
+    sliding: Vec<bool>,
+    /// Friction directions in the contact plane.
+    tangents: [Vec3; 2],
+    /// Accumulated rolling resistance angular impulse.
+    rolling_impulse: Vec3,
+    /// Accumulated spinning resistance angular impulse about the normal.
+    spinning_impulse: f32,
+}
 
 /// Solve contact constraints using sequential impulse iteration.
 pub fn solve_contacts(
@@ -18,39 +38,152 @@ pub fn solve_contacts(
     world: &mut hecs::World,
     solver_iterations: u32,
 ) {
+    // Bounce targets and friction regimes come from the pre-solve velocities;
+    // recomputing them per iteration would cancel the restitution impulse.
+    let mut states: Vec<ManifoldState> = manifolds
+        .iter()
+        .map(|manifold| prepare_manifold(manifold, world))
+        .collect();
+
+    for (manifold, state) in manifolds.iter().zip(&states) {
+        warm_start(manifold, state, world);
+    }
+
     for _ in 0..solver_iterations {
-        for manifold in manifolds.iter_mut() {
-            solve_manifold(manifold, world);
+        for (manifold, state) in manifolds.iter_mut().zip(&mut states) {
+            solve_manifold(manifold, world, state);
         }
     }
 }
 
-fn solve_manifold(manifold: &mut ContactManifold, world: &mut hecs::World) {
-    // Read rigid body data and positions for both entities
-    let (rb_a_data, rb_b_data) = {
-        let rb_a = world.get::<&RigidBody>(manifold.entity_a).ok();
-        let rb_b = world.get::<&RigidBody>(manifold.entity_b).ok();
-        let pos_a = world
-            .get::<&GlobalTransform>(manifold.entity_a)
-            .ok()
-            .map(|t| t.0.transform_point3(Vec3::ZERO))
-            .unwrap_or(Vec3::ZERO);
-        let pos_b = world
-            .get::<&GlobalTransform>(manifold.entity_b)
-            .ok()
-            .map(|t| t.0.transform_point3(Vec3::ZERO))
-            .unwrap_or(Vec3::ZERO);
-
-        let a = rb_a.map(|rb| RbData::from_rb(&rb, pos_a));
-        let b = rb_b.map(|rb| RbData::from_rb(&rb, pos_b));
-        (a, b)
+fn prepare_manifold(manifold: &ContactManifold, world: &hecs::World) -> ManifoldState {
+    let normal = manifold.normal;
+    let mut state = ManifoldState {
+        bounce: vec![0.0; manifold.contacts.len()],
+        sliding: vec![false; manifold.contacts.len()],
+        tangents: tangent_basis(normal, manifold.material.friction_direction),
+        rolling_impulse: Vec3::ZERO,
+        spinning_impulse: 0.0,
+    };
+    let (Some(a), Some(b)) = (
+        read_body(world, manifold.entity_a),
+        read_body(world, manifold.entity_b),
+    ) else {
+        return state;
+    };
+
+    for (i, contact) in manifold.contacts.iter().enumerate() {
+        let relative_velocity = b.velocity_at(contact.position) - a.velocity_at(contact.position);
+        let contact_velocity = relative_velocity.dot(normal);
+        if contact_velocity < -RESTITUTION_THRESHOLD {
+            state.bounce[i] = -manifold.material.restitution * contact_velocity;
+        }
+        let tangent_velocity = relative_velocity - normal * contact_velocity;
+        state.sliding[i] = tangent_velocity.length() > SLIDING_THRESHOLD;
+    }
+    state
+}
+
+/// Re-apply last frame's accumulated impulses so the solver starts near the
+/// previous solution.
+fn warm_start(manifold: &ContactManifold, state: &ManifoldState, world: &mut hecs::World) {
+    let (Some(a), Some(b)) = (
+        read_body(world, manifold.entity_a),
+        read_body(world, manifold.entity_b),
+    ) else {
+        return;
     };
+    for contact in &manifold.contacts {
+        let impulse = manifold.normal * contact.normal_impulse
+            + state.tangents[0] * contact.tangent_impulse[0]
+            + state.tangents[1] * contact.tangent_impulse[1];
+        if impulse != Vec3::ZERO {
+            apply_impulse(
+                world,
+                manifold.entity_a,
+                manifold.entity_b,
+                impulse,
+                contact.position - a.position,
+                contact.position - b.position,
+            );
+        }
+    }
+}
+
+/// Orthonormal friction directions in the plane perpendicular to `normal`.
+///
+/// The first direction follows `preferred` (anisotropic friction) when it is
+/// not parallel to the normal.
+pub(super) fn tangent_basis(normal: Vec3, preferred: Option<Vec3>) -> [Vec3; 2] {
+    let t1 = preferred
+        .and_then(|d| (d - normal * d.dot(normal)).try_normalize())
+        .unwrap_or_else(|| normal.any_orthonormal_vector());
+    [t1, normal.cross(t1)]
+}
 
-    let rb_a_data = match rb_a_data {
+/// Clamp an accumulated tangent impulse to the (elliptic) Coulomb cone.
+///
+/// Contacts that were not sliding may hold up to the static limit; once that
+/// is exceeded, or for sliding contacts, the dynamic limit applies.
+fn clamp_friction(
+    impulse: [f32; 2],
+    normal_impulse: f32,
+    static_limit: [f32; 2],
+    dynamic_limit: [f32; 2],
+    sliding: bool,
+) -> [f32; 2] {
+    fn cone_ratio(impulse: [f32; 2], mu: [f32; 2]) -> f32 {
+        let mut sum = 0.0;
+        for k in 0..2 {
+            if mu[k] > 0.0 {
+                sum += (impulse[k] / mu[k]).powi(2);
+            } else if impulse[k] != 0.0 {
+                return f32::INFINITY;
+            }
+        }
+        sum.sqrt()
+    }
+
+    if normal_impulse <= 0.0 {
+        return [0.0; 2];
+    }
+    if !sliding && cone_ratio(impulse, static_limit) <= normal_impulse {
+        return impulse;
+    }
+
+    // Zero out directions without friction before scaling onto the cone
+    let impulse = [
+        if dynamic_limit[0] > 0.0 {
+            impulse[0]
+        } else {
+            0.0
+        },
+        if dynamic_limit[1] > 0.0 {
+            impulse[1]
+        } else {
+            0.0
+        },
+    ];
+    let ratio = cone_ratio(impulse, dynamic_limit);
+    if ratio <= normal_impulse {
+        impulse
+    } else {
+        let scale = normal_impulse / ratio;
+        [impulse[0] * scale, impulse[1] * scale]
+    }
+}
+
+fn solve_manifold(
+    manifold: &mut ContactManifold,
+    world: &mut hecs::World,
+    state: &mut ManifoldState,
+) {
+    // Read rigid body data and positions for both entities
+    let rb_a_data = match read_body(world, manifold.entity_a) {
         Some(d) => d,
         None => return,
     };
-    let rb_b_data = match rb_b_data {
+    let rb_b_data = match read_body(world, manifold.entity_b) {
         Some(d) => d,
         None => return,
     };
@@ -61,29 +194,36 @@ fn solve_manifold(manifold: &mut ContactManifold, world: &mut hecs::World) {
     }
 
     let normal = manifold.normal;
-    let restitution = (rb_a_data.restitution + rb_b_data.restitution) * 0.5;
-    let friction = (rb_a_data.friction + rb_b_data.friction) * 0.5;
+    let material = manifold.material;
+    let tangents = state.tangents;
+    let static_limit = [
+        material.static_friction * material.friction_scale[0],
+        material.static_friction * material.friction_scale[1],
+    ];
+    let dynamic_limit = [
+        material.dynamic_friction * material.friction_scale[0],
+        material.dynamic_friction * material.friction_scale[1],
+    ];
+
+    for (i, contact) in manifold.contacts.iter_mut().enumerate() {
+        // Earlier contacts of this manifold changed the velocities
+        let (Some(rb_a_data), Some(rb_b_data)) = (
+            read_body(world, manifold.entity_a),
+            read_body(world, manifold.entity_b),
+        ) else {
+            return;
+        };
 
-    for contact in &mut manifold.contacts {
         // Compute relative velocity at contact point
         let r_a = contact.position - rb_a_data.position;
         let r_b = contact.position - rb_b_data.position;
 
-        let vel_a = rb_a_data.linear_velocity + rb_a_data.angular_velocity.cross(r_a);
-        let vel_b = rb_b_data.linear_velocity + rb_b_data.angular_velocity.cross(r_b);
-
-        let relative_velocity = vel_b - vel_a;
+        let relative_velocity =
+            rb_b_data.velocity_at(contact.position) - rb_a_data.velocity_at(contact.position);
         let contact_velocity = relative_velocity.dot(normal);
 
         // Normal impulse
-        let r_a_cross_n = r_a.cross(normal);
-        let r_b_cross_n = r_b.cross(normal);
-
-        let inv_mass_sum = rb_a_data.inv_mass
-            + rb_b_data.inv_mass
-            + (rb_a_data.inv_inertia * r_a_cross_n).dot(r_a_cross_n)
-            + (rb_b_data.inv_inertia * r_b_cross_n).dot(r_b_cross_n);
-
+        let inv_mass_sum = effective_inv_mass(&rb_a_data, &rb_b_data, r_a, r_b, normal);
         if inv_mass_sum <= 0.0 {
             continue;
         }
@@ -92,89 +232,145 @@ fn solve_manifold(manifold: &mut ContactManifold, world: &mut hecs::World) {
         let bias =
             BAUMGARTE_BETA / (1.0 / 60.0) * (contact.penetration - PENETRATION_SLOP).max(0.0);
 
-        let j_normal = (-(1.0 + restitution) * contact_velocity + bias) / inv_mass_sum;
+        let j_normal = (-contact_velocity + bias.max(state.bounce[i])) / inv_mass_sum;
 
         // Clamp accumulated normal impulse
         let old_impulse = contact.normal_impulse;
         contact.normal_impulse = (old_impulse + j_normal).max(0.0);
         let j_normal = contact.normal_impulse - old_impulse;
 
-        let impulse = normal * j_normal;
-
-        // Apply normal impulse (need to write back later)
         apply_impulse(
             world,
             manifold.entity_a,
             manifold.entity_b,
-            impulse,
+            normal * j_normal,
             r_a,
             r_b,
         );
 
-        // Friction impulse
-        // Re-read velocities after normal impulse
-        let (rb_a_updated, rb_b_updated) = {
-            let pos_a = world
-                .get::<&GlobalTransform>(manifold.entity_a)
-                .ok()
-                .map(|t| t.0.transform_point3(Vec3::ZERO))
-                .unwrap_or(Vec3::ZERO);
-            let pos_b = world
-                .get::<&GlobalTransform>(manifold.entity_b)
-                .ok()
-                .map(|t| t.0.transform_point3(Vec3::ZERO))
-                .unwrap_or(Vec3::ZERO);
-            let a = world
-                .get::<&RigidBody>(manifold.entity_a)
-                .ok()
-                .map(|rb| RbData::from_rb(&rb, pos_a));
-            let b = world
-                .get::<&RigidBody>(manifold.entity_b)
-                .ok()
-                .map(|rb| RbData::from_rb(&rb, pos_b));
-            (a, b)
+        // Friction impulse along both tangents, using velocities after the
+        // normal impulse
+        let (Some(a), Some(b)) = (
+            read_body(world, manifold.entity_a),
+            read_body(world, manifold.entity_b),
+        ) else {
+            continue;
         };
+        let rel_vel = b.velocity_at(contact.position) - a.velocity_at(contact.position);
+
+        let mut candidate = contact.tangent_impulse;
+        for (k, tangent) in tangents.iter().enumerate() {
+            let inv_mass_t = effective_inv_mass(&a, &b, r_a, r_b, *tangent);
+            if inv_mass_t > 0.0 {
+                candidate[k] -= rel_vel.dot(*tangent) / inv_mass_t;
+            }
+        }
+        let clamped = clamp_friction(
+            candidate,
+            contact.normal_impulse,
+            static_limit,
+            dynamic_limit,
+            state.sliding[i],
+        );
+        let delta = [
+            clamped[0] - contact.tangent_impulse[0],
+            clamped[1] - contact.tangent_impulse[1],
+        ];
+        contact.tangent_impulse = clamped;
+
+        let friction_impulse = tangents[0] * delta[0] + tangents[1] * delta[1];
+        if friction_impulse != Vec3::ZERO {
+            apply_impulse(
+                world,
+                manifold.entity_a,
+                manifold.entity_b,
+                friction_impulse,
+                r_a,
+                r_b,
+            );
+        }
+    }
+
+    solve_rolling_resistance(manifold, world, state);
+}
+
+/// Resist relative rotation with torques bounded by the rolling and spinning
+/// friction coefficients times the total normal impulse.
+fn solve_rolling_resistance(
+    manifold: &ContactManifold,
+    world: &mut hecs::World,
+    state: &mut ManifoldState,
+) {
+    let material = manifold.material;
+    if material.rolling_friction <= 0.0 && material.spinning_friction <= 0.0 {
+        return;
+    }
+    let (Some(a), Some(b)) = (
+        read_body(world, manifold.entity_a),
+        read_body(world, manifold.entity_b),
+    ) else {
+        return;
+    };
+    let normal_impulse: f32 = manifold.contacts.iter().map(|c| c.normal_impulse).sum();
+    let inv_inertia = a.inv_inertia + b.inv_inertia;
+    let normal = manifold.normal;
 
-        if let (Some(a), Some(b)) = (rb_a_updated, rb_b_updated) {
-            let vel_a2 = a.linear_velocity + a.angular_velocity.cross(r_a);
-            let vel_b2 = b.linear_velocity + b.angular_velocity.cross(r_b);
-            let rel_vel2 = vel_b2 - vel_a2;
-
-            // Tangent velocity (remove normal component)
-            let tangent_vel = rel_vel2 - normal * rel_vel2.dot(normal);
-            let tangent_len = tangent_vel.length();
-
-            if tangent_len > 1e-6 {
-                let tangent = tangent_vel / tangent_len;
-
-                let r_a_cross_t = r_a.cross(tangent);
-                let r_b_cross_t = r_b.cross(tangent);
-
-                let inv_mass_t = a.inv_mass
-                    + b.inv_mass
-                    + (a.inv_inertia * r_a_cross_t).dot(r_a_cross_t)
-                    + (b.inv_inertia * r_b_cross_t).dot(r_b_cross_t);
-
-                if inv_mass_t > 0.0 {
-                    let j_tangent = -tangent_len / inv_mass_t;
-
-                    // Coulomb friction: |Jt| <= mu * |Jn|
-                    let max_friction = friction * contact.normal_impulse;
-                    let j_tangent = j_tangent.clamp(-max_friction, max_friction);
-
-                    let friction_impulse = tangent * j_tangent;
-                    apply_impulse(
-                        world,
-                        manifold.entity_a,
-                        manifold.entity_b,
-                        friction_impulse,
-                        r_a,
-                        r_b,
-                    );
-                }
+    let relative_spin = b.angular_velocity - a.angular_velocity;
+    let spin = relative_spin.dot(normal);
+    let roll = relative_spin - normal * spin;
+
+    let mut angular_impulse = Vec3::ZERO;
+
+    // Rolling: oppose rotation about axes in the contact plane
+    if material.rolling_friction > 0.0 {
+        if let Some(axis) = roll.try_normalize() {
+            let k = axis.dot(inv_inertia * axis);
+            if k > 0.0 {
+                let max = material.rolling_friction * normal_impulse;
+                let old = state.rolling_impulse;
+                state.rolling_impulse = (old - axis * (roll.length() / k)).clamp_length_max(max);
+                angular_impulse += state.rolling_impulse - old;
             }
         }
     }
+
+    // Spinning: oppose rotation about the contact normal
+    if material.spinning_friction > 0.0 {
+        let k = normal.dot(inv_inertia * normal);
+        if k > 0.0 {
+            let max = material.spinning_friction * normal_impulse;
+            let old = state.spinning_impulse;
+            state.spinning_impulse = (old - spin / k).clamp(-max, max);
+            angular_impulse += normal * (state.spinning_impulse - old);
+        }
+    }
+
+    if angular_impulse != Vec3::ZERO {
+        apply_angular_impulse(world, manifold.entity_a, manifold.entity_b, angular_impulse);
+    }
+}
+
+/// Inverse effective mass of the contact along `direction`.
+fn effective_inv_mass(a: &RbData, b: &RbData, r_a: Vec3, r_b: Vec3, direction: Vec3) -> f32 {
+    let r_a_cross = r_a.cross(direction);
+    let r_b_cross = r_b.cross(direction);
+    a.inv_mass
+        + b.inv_mass
+        + (a.inv_inertia * r_a_cross).dot(r_a_cross)
+        + (b.inv_inertia * r_b_cross).dot(r_b_cross)
+}
+
+/// Read solver data for an entity's rigid body at its current position.
+fn read_body(world: &hecs::World, entity: hecs::Entity) -> Option<RbData> {
+    let position = world
+        .get::<&GlobalTransform>(entity)
+        .ok()
+        .map(|t| t.0.transform_point3(Vec3::ZERO))
+        .unwrap_or(Vec3::ZERO);
+    world
+        .get::<&RigidBody>(entity)
+        .ok()
+        .map(|rb| RbData::from_rb(&rb, position))
 }
 
 /// Helper struct to cache rigid body data for solver calculations.
@@ -184,11 +380,13 @@ struct RbData {
     linear_velocity: Vec3,
     angular_velocity: Vec3,
     position: Vec3,
-    restitution: f32,
-    friction: f32,
 }
 
 impl RbData {
+    fn velocity_at(&self, point: Vec3) -> Vec3 {
+        self.linear_velocity + self.angular_velocity.cross(point - self.position)
+    }
+
     fn from_rb(rb: &RigidBody, position: Vec3) -> Self {
         let inv_mass = if rb.body_type == RigidBodyType::Dynamic && rb.mass > 0.0 {
             1.0 / rb.mass
@@ -224,8 +422,21 @@ impl RbData {
             linear_velocity: rb.linear_velocity,
             angular_velocity: rb.angular_velocity,
             position,
-            restitution: rb.restitution,
-            friction: rb.friction,
+        }
+    }
+}
+
+/// Apply an angular impulse to B and its opposite to A.
+fn apply_angular_impulse(
+    world: &mut hecs::World,
+    entity_a: hecs::Entity,
+    entity_b: hecs::Entity,
+    impulse: Vec3,
+) {
+    for (entity, sign) in [(entity_a, -1.0), (entity_b, 1.0)] {
+        if let Ok(mut rb) = world.get::<&mut RigidBody>(entity) {
+            let data = RbData::from_rb(&rb, Vec3::ZERO);
+            rb.angular_velocity += data.inv_inertia * impulse * sign;
         }
     }
 }
@@ -0,0 +1,110 @@
+//! Per-step profiling counters and stage timings.
+
+use std::time::{Duration, Instant};
+
+use crate::ecs::components::physics::{RigidBody, RigidBodyType, SleepInfo, SleepState};
+
+/// Which collision pipeline a fixed step ran on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhysicsPath {
+    /// CPU broadphase and narrowphase.
+    #[default]
+    Cpu,
+    /// GPU broadphase with GPU narrowphase for every pair (all spheres).
+    GpuDirect,
+    /// GPU broadphase, narrowphase split between GPU and CPU per pair.
+    GpuMixed,
+    /// `step_gpu` was used but the body count was below the GPU threshold or
+    /// above the GPU capacity (or GPU physics is not initialized), so the CPU
+    /// path ran.
+    GpuFallback,
+}
+
+impl PhysicsPath {
+    /// Whether the GPU broadphase ran.
+    pub fn used_gpu(self) -> bool {
+        matches!(self, PhysicsPath::GpuDirect | PhysicsPath::GpuMixed)
+    }
+}
+
+/// Wall-clock time spent in each stage of a fixed step.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StageTimings {
+    /// Gravity and force fields.
+    pub forces: Duration,
+    pub integrate_velocities: Duration,
+    /// Broadphase, including GPU upload, dispatch and pair readback.
+    pub broadphase: Duration,
+    /// Narrowphase on CPU and/or GPU, including result readback.
+    pub narrowphase: Duration,
+    /// Warm start, contact solve and cache update.
+    pub solver: Duration,
+    pub integrate_positions: Duration,
+    /// Transform sync, force clearing and sleep updates.
+    pub finalize: Duration,
+}
+
+impl StageTimings {
+    /// Sum of all stages.
+    pub fn total(&self) -> Duration {
+        self.forces
+            + self.integrate_velocities
+            + self.broadphase
+            + self.narrowphase
+            + self.solver
+            + self.integrate_positions
+            + self.finalize
+    }
+}
+
+/// Counters and timings for the most recent fixed step.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PhysicsStats {
+    /// Fixed steps run by the last `step`/`step_gpu` call. When zero, the
+    /// remaining fields still describe the previous fixed step.
+    pub substeps: u32,
+    /// Candidate pairs reported by the broadphase.
+    pub broadphase_pairs: usize,
+    /// Pairs sent to the GPU narrowphase.
+    pub gpu_narrowphase_pairs: usize,
+    /// Contact manifolds handed to the solver, one per pair the narrowphase
+    /// found touching.
+    pub manifolds: usize,
+    /// Contact points across all manifolds.
+    pub contacts: usize,
+    /// Dynamic bodies that are awake after the step.
+    pub awake_bodies: usize,
+    /// Dynamic bodies that are sleeping after the step.
+    pub sleeping_bodies: usize,
+    /// Collision pipeline the step ran on.
+    pub path: PhysicsPath,
+    pub timings: StageTimings,
+}
+
+impl PhysicsStats {
+    /// Count awake and sleeping dynamic bodies.
+    pub(crate) fn count_bodies(&mut self, world: &mut hecs::World) {
+        self.awake_bodies = 0;
+        self.sleeping_bodies = 0;
+        for (_, (rb, sleep)) in world.query_mut::<(&RigidBody, Option<&SleepInfo>)>() {
+            if rb.body_type != RigidBodyType::Dynamic {
+                continue;
+            }
+            if sleep.is_some_and(|s| s.state == SleepState::Sleeping) {
+                self.sleeping_bodies += 1;
+            } else {
+                self.awake_bodies += 1;
+            }
+        }
+    }
+}
+
+/// Run one pipeline stage inside a `tracing` span, adding its wall-clock time
+/// to `elapsed`.
+pub(crate) fn timed<R>(stage: &'static str, elapsed: &mut Duration, f: impl FnOnce() -> R) -> R {
+    let _span = tracing::debug_span!("physics_stage", stage).entered();
+    let start = Instant::now();
+    let result = f();
+    *elapsed += start.elapsed();
+    result
+}
@@ -164,4 +164,8 @@ impl Geometry for Axes {
     fn aabb(&self) -> Aabb {
         Aabb::new(Vec3::ZERO, Vec3::splat(self.size))
     }
+
+    fn is_triangle_mesh(&self) -> bool {
+        false
+    }
 }
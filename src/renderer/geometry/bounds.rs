@@ -102,4 +102,8 @@ impl Geometry for BoundingBoxMesh {
     fn aabb(&self) -> Aabb {
         self.aabb
     }
+
+    fn is_triangle_mesh(&self) -> bool {
+        false
+    }
 }
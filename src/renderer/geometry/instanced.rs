@@ -107,6 +107,10 @@ impl Geometry for InstancedMesh {
     fn aabb(&self) -> Aabb {
         self.aabb
     }
+
+    fn instances(&self) -> Option<&InstanceBuffer> {
+        Some(&self.instance_buffer)
+    }
 }
 
 /// Builder for creating instanced meshes from positions.
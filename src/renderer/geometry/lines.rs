@@ -106,6 +106,10 @@ impl Geometry for Lines {
     fn aabb(&self) -> Aabb {
         self.aabb
     }
+
+    fn is_triangle_mesh(&self) -> bool {
+        false
+    }
 }
 
 /// A connected line strip where each vertex connects to the next.
@@ -189,4 +193,8 @@ impl Geometry for LineStrip {
     fn aabb(&self) -> Aabb {
         self.aabb
     }
+
+    fn is_triangle_mesh(&self) -> bool {
+        false
+    }
 }
@@ -9,11 +9,16 @@ use crate::core::pipeline::Vertex;
 use glam::Vec3;
 
 /// A mesh with vertex and index data.
+///
+/// Only the GPU buffers are kept unless the mesh is created with
+/// [`with_cpu_data`](Self::with_cpu_data).
 pub struct Mesh {
     vertex_buffer: VertexBuffer,
     index_buffer: Option<IndexBuffer>,
     draw_count: u32,
     aabb: Aabb,
+    /// Vertex positions and triangle indices, for ray picking.
+    triangles: Option<(Vec<Vec3>, Vec<u32>)>,
 }
 
 impl Mesh {
@@ -41,6 +46,26 @@ impl Mesh {
             index_buffer,
             draw_count,
             aabb,
+            triangles: None,
+        }
+    }
+
+    /// Create a mesh that also keeps a CPU copy of its triangles, so ECS ray
+    /// picking hits its surface rather than its bounding box.
+    pub fn with_cpu_data(
+        ctx: &WgpuContext,
+        vertices: &[Vertex],
+        indices: Option<&[u32]>,
+        label: Option<&str>,
+    ) -> Self {
+        let positions = vertices.iter().map(|v| Vec3::from(v.position)).collect();
+        let triangle_indices = match indices {
+            Some(indices) => indices.to_vec(),
+            None => (0..vertices.len() as u32).collect(),
+        };
+        Self {
+            triangles: Some((positions, triangle_indices)),
+            ..Self::new(ctx, vertices, indices, label)
         }
     }
 
@@ -126,6 +151,12 @@ impl Geometry for Mesh {
     fn aabb(&self) -> Aabb {
         self.aabb
     }
+
+    fn triangles(&self) -> Option<(&[Vec3], &[u32])> {
+        self.triangles
+            .as_ref()
+            .map(|(positions, indices)| (positions.as_slice(), indices.as_slice()))
+    }
 }
 
 // Helper functions for generating primitive geometry
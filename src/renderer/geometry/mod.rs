@@ -27,6 +27,7 @@ pub use sprites::Sprites;
 pub use terrain::{Terrain, TerrainLod};
 
 use crate::core::buffer::{IndexBuffer, VertexBuffer};
+use crate::core::instance::InstanceBuffer;
 use glam::Vec3;
 
 /// Axis-aligned bounding box.
@@ -121,6 +122,27 @@ pub trait Geometry {
     /// Get the axis-aligned bounding box.
     fn aabb(&self) -> Aabb;
 
+    /// CPU copy of the triangle list as vertex positions and indices, three
+    /// per triangle. Used for ray picking; geometry without one is picked by
+    /// its bounding box.
+    fn triangles(&self) -> Option<(&[Vec3], &[u32])> {
+        None
+    }
+
+    /// Whether the vertex buffer holds [`Vertex`](crate::core::pipeline::Vertex)
+    /// data drawn as a triangle list, as the passes that draw meshes with
+    /// their own pipelines, such as the ECS picking ID pass, assume. Line
+    /// geometry returns `false` and is skipped by them.
+    fn is_triangle_mesh(&self) -> bool {
+        true
+    }
+
+    /// Per-instance transforms the geometry is drawn with, if it carries its
+    /// own. Used by the ECS picking ID pass to tell instances apart.
+    fn instances(&self) -> Option<&InstanceBuffer> {
+        None
+    }
+
     /// Draw the geometry using the given render pass.
     fn draw<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
         render_pass.set_vertex_buffer(0, self.vertex_buffer().slice());
@@ -4,6 +4,7 @@
 
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::PipelineBuilder;
 use crate::core::render_states::{BlendState, CullState, DepthState};
 use crate::renderer::viewer::Viewer;
@@ -46,7 +47,7 @@ impl SkyboxVertex {
 /// Inspired by three-d's Skybox, but uses a procedural gradient instead of a cubemap texture.
 /// The skybox is rendered as an inverted cube that always appears behind all other geometry.
 pub struct Skybox {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
     index_count: u32,
@@ -81,7 +82,7 @@ impl Skybox {
         color_horizon: [f32; 4],
         color_bottom: [f32; 4],
     ) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/skybox.wgsl");
+        let shader = ShaderFile::embedded("skybox.wgsl", include_str!("../../shaders/skybox.wgsl"));
 
         // Create cube geometry
         let (vertices, indices) = Self::cube_geometry();
@@ -137,16 +138,21 @@ impl Skybox {
         });
 
         // Pipeline: no depth write, render inside the cube (front face culling)
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("skybox pipeline")
-            .shader(shader)
-            .vertex_layout(SkyboxVertex::layout())
-            .bind_group_layout(&bind_group_layout)
-            .color_format(format)
-            .depth(DepthState::read_only())
-            .blend(BlendState::Opaque)
-            .cull(CullState::Front)
-            .build()?;
+        let (pipeline_ctx, layout) = (ctx.clone(), bind_group_layout.clone());
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("skybox pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(SkyboxVertex::layout())
+                .bind_group_layout(&layout)
+                .color_format(format)
+                .depth(DepthState::read_only())
+                .blend(BlendState::Opaque)
+                .cull(CullState::Front)
+                .build()
+        })?;
 
         Ok(Self {
             pipeline,
@@ -187,13 +193,20 @@ impl Skybox {
         };
         self.uniform_buffer.write(ctx, &uniform);
 
-        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_pipeline(&self.pipeline.get());
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
         render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
         render_pass.draw_indexed(0..self.index_count, 0, 0..1);
     }
 
+    /// Rebuild the pipeline if its shader file changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// it was rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
+
     /// Generate a unit cube (positions only).
     fn cube_geometry() -> (Vec<SkyboxVertex>, Vec<u16>) {
         let vertices = vec![
@@ -6,6 +6,7 @@ use glam::Vec3;
 
 /// Light type enumeration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
 pub enum LightType {
     Ambient,
     Directional,
@@ -15,6 +16,7 @@ pub enum LightType {
 
 /// Attenuation factors for point and spot lights.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attenuation {
     /// Constant attenuation factor (default: 1.0).
     pub constant: f32,
@@ -78,6 +80,52 @@ pub struct LightUniforms {
     pub attenuation: [f32; 4],
 }
 
+/// One light in the scene light storage buffer read by lit materials.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct GpuLight {
+    /// Position (xyz) and range (w, 0 for unlimited) of point and spot lights.
+    pub position_range: [f32; 4],
+    /// Direction the light travels (xyz) and [`GpuLight::kind`] of the light (w).
+    pub direction_kind: [f32; 4],
+    /// Light color and intensity (rgb = color, a = intensity).
+    pub color_intensity: [f32; 4],
+    /// Attenuation factors (constant, linear, quadratic, unused).
+    pub attenuation: [f32; 4],
+    /// Cosines of the inner and outer spot cone angles, and 1 in z when the
+    /// light is shadowed by the scene's shadow map.
+    pub cone_shadow: [f32; 4],
+}
+
+impl GpuLight {
+    /// Shader light kind of a light type.
+    pub fn kind(light_type: LightType) -> f32 {
+        match light_type {
+            LightType::Ambient => 0.0,
+            LightType::Directional => 1.0,
+            LightType::Point => 2.0,
+            LightType::Spot => 3.0,
+        }
+    }
+
+    /// Build from the light uniforms of a light without a spot cone or range.
+    pub fn from_uniforms(light_type: LightType, uniforms: &LightUniforms) -> Self {
+        let [x, y, z, _] = uniforms.direction_or_position;
+        let kind = Self::kind(light_type);
+        let (position_range, direction_kind) = match light_type {
+            LightType::Point | LightType::Spot => ([x, y, z, 0.0], [0.0, -1.0, 0.0, kind]),
+            LightType::Directional | LightType::Ambient => ([0.0; 4], [x, y, z, kind]),
+        };
+        Self {
+            position_range,
+            direction_kind,
+            color_intensity: uniforms.color_intensity,
+            attenuation: uniforms.attenuation,
+            cone_shadow: [-1.0, -1.0, 0.0, 0.0],
+        }
+    }
+}
+
 /// Trait for light sources.
 pub trait Light {
     /// Get the light type.
@@ -85,6 +133,11 @@ pub trait Light {
 
     /// Get the light uniforms for GPU.
     fn uniforms(&self) -> LightUniforms;
+
+    /// Get the light's entry in the scene light storage buffer.
+    fn gpu_light(&self) -> GpuLight {
+        GpuLight::from_uniforms(self.light_type(), &self.uniforms())
+    }
 }
 
 /// Ambient light that illuminates all surfaces equally.
@@ -322,6 +375,14 @@ impl Light for SpotLight {
             ],
         }
     }
+
+    fn gpu_light(&self) -> GpuLight {
+        let mut light = GpuLight::from_uniforms(LightType::Spot, &self.uniforms());
+        let d = self.direction;
+        light.direction_kind = [d.x, d.y, d.z, GpuLight::kind(LightType::Spot)];
+        light.cone_shadow = [self.inner_angle.cos(), self.outer_angle.cos(), 0.0, 0.0];
+        light
+    }
 }
 
 impl Default for SpotLight {
@@ -0,0 +1,206 @@
+//! Scene lighting bindings
+//!
+//! Lights and the shadow map shared by every lit material in a pass.
+
+use crate::context::WgpuContext;
+use crate::core::buffer::RawUniformBuffer;
+use crate::core::texture::{DepthTexture, Texture2DArray};
+use crate::renderer::light::GpuLight;
+use crate::renderer::shadow::ShadowUniform;
+
+/// Light count at the start of the light storage buffer, followed by the
+/// [`GpuLight`] array.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct LightsHeader {
+    count: u32,
+    _padding: [u32; 3],
+}
+
+/// Number of lights a material's own [`SceneLighting`] holds.
+pub const MATERIAL_LIGHT_CAPACITY: usize = 8;
+
+/// Scene lights, shadow uniform, shadow map and comparison sampler, bound as
+/// one group by lit materials.
+///
+/// With no lights the shaders fall back to their default sun. Two bind
+/// groups share the lights: one samples the shadow map and one ignores it,
+/// for draws that do not receive shadows.
+pub struct SceneLighting {
+    lights: wgpu::Buffer,
+    capacity: usize,
+    shadowed: RawUniformBuffer,
+    unshadowed: RawUniformBuffer,
+    shadow_view: Option<wgpu::TextureView>,
+    bind_groups: [wgpu::BindGroup; 2],
+}
+
+impl SceneLighting {
+    /// Bind group layout: shadow uniform (binding 0), shadow map (binding 1),
+    /// comparison sampler (binding 2) and light storage buffer (binding 3),
+    /// all visible to the fragment stage.
+    pub fn bind_group_layout(ctx: &WgpuContext) -> wgpu::BindGroupLayout {
+        ctx.device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("scene lighting bind group layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: wgpu::BufferSize::new(
+                                std::mem::size_of::<ShadowUniform>() as u64,
+                            ),
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Depth,
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Comparison),
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 3,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: true },
+                            has_dynamic_offset: false,
+                            min_binding_size: wgpu::BufferSize::new(
+                                (std::mem::size_of::<LightsHeader>()
+                                    + std::mem::size_of::<GpuLight>())
+                                    as u64,
+                            ),
+                        },
+                        count: None,
+                    },
+                ],
+            })
+    }
+
+    /// Create lighting for up to `capacity` lights, sampling `shadow_map`
+    /// or a placeholder that is never sampled.
+    pub fn new(ctx: &WgpuContext, capacity: usize, shadow_map: Option<&wgpu::TextureView>) -> Self {
+        let capacity = capacity.max(1);
+        let size = std::mem::size_of::<LightsHeader>() + capacity * std::mem::size_of::<GpuLight>();
+        let lights = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("scene lights"),
+            size: size as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let uniform_size = std::mem::size_of::<ShadowUniform>() as u64;
+        let shadowed = RawUniformBuffer::new(ctx, uniform_size, Some("shadowed lighting uniform"));
+        let unshadowed =
+            RawUniformBuffer::new(ctx, uniform_size, Some("unshadowed lighting uniform"));
+
+        let placeholder;
+        let view = match shadow_map {
+            Some(view) => view,
+            None => {
+                placeholder = DepthTexture::new(ctx, 1, 1, Some("placeholder shadow map"));
+                placeholder.view()
+            }
+        };
+        let layout = Self::bind_group_layout(ctx);
+        let sampler = Texture2DArray::create_comparison_sampler(ctx);
+        let bind_group = |uniform: &RawUniformBuffer| {
+            ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("scene lighting bind group"),
+                layout: &layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: uniform.buffer().as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(&sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 3,
+                        resource: lights.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+        let bind_groups = [bind_group(&unshadowed), bind_group(&shadowed)];
+
+        let lighting = Self {
+            lights,
+            capacity,
+            shadowed,
+            unshadowed,
+            shadow_view: shadow_map.cloned(),
+            bind_groups,
+        };
+        lighting.write_lights(ctx, &[]);
+        lighting.write_shadow(ctx, &bytemuck::Zeroable::zeroed());
+        lighting
+    }
+
+    /// Number of lights the storage buffer holds.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The shadow map the bind groups sample, if any.
+    pub fn shadow_map(&self) -> Option<&wgpu::TextureView> {
+        self.shadow_view.as_ref()
+    }
+
+    /// Write the scene lights. Lights beyond [`capacity`](Self::capacity)
+    /// are dropped.
+    pub fn write_lights(&self, ctx: &WgpuContext, lights: &[GpuLight]) {
+        let count = lights.len().min(self.capacity);
+        if count < lights.len() {
+            tracing::warn!(
+                "Scene lighting holds {} lights, dropping {}",
+                self.capacity,
+                lights.len() - count
+            );
+        }
+        let header = LightsHeader {
+            count: count as u32,
+            _padding: [0; 3],
+        };
+        ctx.queue
+            .write_buffer(&self.lights, 0, bytemuck::bytes_of(&header));
+        if count > 0 {
+            ctx.queue.write_buffer(
+                &self.lights,
+                std::mem::size_of::<LightsHeader>() as u64,
+                bytemuck::cast_slice(&lights[..count]),
+            );
+        }
+    }
+
+    /// Write the shadow uniform; sampling is enabled only in the bind group
+    /// for shadow-receiving draws, and only with a shadow map.
+    pub fn write_shadow(&self, ctx: &WgpuContext, uniform: &ShadowUniform) {
+        let enabled = self.shadow_view.is_some();
+        self.shadowed.write(ctx, &uniform.with_enabled(enabled));
+        self.unshadowed.write(ctx, &uniform.with_enabled(false));
+    }
+
+    /// Bind group for draws that do or do not receive shadows.
+    pub fn bind_group(&self, receive_shadow: bool) -> &wgpu::BindGroup {
+        &self.bind_groups[usize::from(receive_shadow)]
+    }
+}
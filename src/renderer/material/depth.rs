@@ -3,6 +3,7 @@
 use super::traits::ModelUniform;
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::PipelineBuilder;
 use crate::core::render_states::{CullState, DepthState};
 use crate::core::vertex::VertexP;
@@ -17,7 +18,7 @@ pub struct LightMatrixUniform {
 
 /// Depth-only material for shadow map generation.
 pub struct DepthMaterial {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     light_buffer: RawUniformBuffer,
     light_bind_group: wgpu::BindGroup,
     model_buffer: RawUniformBuffer,
@@ -27,7 +28,7 @@ pub struct DepthMaterial {
 impl DepthMaterial {
     /// Create a new depth material.
     pub fn new(ctx: &WgpuContext) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/depth.wgsl");
+        let shader = ShaderFile::embedded("depth.wgsl", include_str!("../../shaders/depth.wgsl"));
 
         // Light matrix bind group layout (group 0)
         let light_bind_group_layout =
@@ -63,16 +64,25 @@ impl DepthMaterial {
                     }],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("depth material pipeline")
-            .shader(shader)
-            .vertex_layout(VertexP::layout())
-            .bind_group_layout(&light_bind_group_layout)
-            .bind_group_layout(&model_bind_group_layout)
-            .color_format(wgpu::TextureFormat::R8Unorm) // Dummy format, no color output
-            .depth(DepthState::read_write())
-            .cull(CullState::Back)
-            .build()?;
+        let (pipeline_ctx, light_layout, model_layout) = (
+            ctx.clone(),
+            light_bind_group_layout.clone(),
+            model_bind_group_layout.clone(),
+        );
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("depth material pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(VertexP::layout())
+                .bind_group_layout(&light_layout)
+                .bind_group_layout(&model_layout)
+                .color_format(wgpu::TextureFormat::R8Unorm) // Dummy format, no color output
+                .depth(DepthState::read_write())
+                .cull(CullState::Back)
+                .build()
+        })?;
 
         // Create light matrix buffer
         let light_buffer = RawUniformBuffer::new(
@@ -126,9 +136,16 @@ impl DepthMaterial {
         self.model_buffer.write(ctx, &model_uniform);
     }
 
-    /// Get the render pipeline.
-    pub fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
+    /// Get the render pipeline. Reference counted, so the clone is cheap.
+    pub fn pipeline(&self) -> wgpu::RenderPipeline {
+        self.pipeline.get().clone()
+    }
+
+    /// Rebuild the pipeline if its shader file changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// it was rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
     }
 
     /// Get the light matrix bind group.
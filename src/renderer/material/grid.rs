@@ -2,13 +2,14 @@
 
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::{PipelineBuilder, Vertex};
 use crate::core::render_states::{BlendState, CullState, DepthState};
 use crate::renderer::viewer::{CameraUniform, Viewer};
 
 /// Grid material for rendering a ground grid.
 pub struct GridMaterial {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     camera_buffer: RawUniformBuffer,
     camera_bind_group: wgpu::BindGroup,
 }
@@ -16,7 +17,7 @@ pub struct GridMaterial {
 impl GridMaterial {
     /// Create a new grid material.
     pub fn new(ctx: &WgpuContext, format: wgpu::TextureFormat) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/grid.wgsl");
+        let shader = ShaderFile::embedded("grid.wgsl", include_str!("../../shaders/grid.wgsl"));
 
         // Camera bind group layout (group 0)
         let camera_bind_group_layout =
@@ -35,18 +36,23 @@ impl GridMaterial {
                     }],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("grid material pipeline")
-            .shader(shader)
-            .vertex_entry("vs_grid")
-            .fragment_entry("fs_grid")
-            .vertex_layout(Vertex::layout())
-            .bind_group_layout(&camera_bind_group_layout)
-            .color_format(format)
-            .depth(DepthState::read_write())
-            .blend(BlendState::Alpha)
-            .cull(CullState::None)
-            .build()?;
+        let (pipeline_ctx, camera_layout) = (ctx.clone(), camera_bind_group_layout.clone());
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("grid material pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_entry("vs_grid")
+                .fragment_entry("fs_grid")
+                .vertex_layout(Vertex::layout())
+                .bind_group_layout(&camera_layout)
+                .color_format(format)
+                .depth(DepthState::read_write())
+                .blend(BlendState::Alpha)
+                .cull(CullState::None)
+                .build()
+        })?;
 
         // Create camera uniform buffer
         let camera_buffer = RawUniformBuffer::new(
@@ -77,9 +83,16 @@ impl GridMaterial {
         self.camera_buffer.write(ctx, &camera_uniform);
     }
 
-    /// Get the pipeline.
-    pub fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
+    /// Get the render pipeline. Reference counted, so the clone is cheap.
+    pub fn pipeline(&self) -> wgpu::RenderPipeline {
+        self.pipeline.get().clone()
+    }
+
+    /// Rebuild the pipeline if its shader file changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// it was rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
     }
 
     /// Get the camera bind group.
@@ -3,6 +3,7 @@
 use super::traits::ModelUniform;
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::PipelineBuilder;
 use crate::core::render_states::{BlendState, CullState, DepthState};
 use crate::core::vertex::VertexPC;
@@ -11,7 +12,7 @@ use glam::Mat4;
 
 /// Material for rendering lines with per-vertex colors.
 pub struct LineMaterial {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     camera_buffer: RawUniformBuffer,
     camera_bind_group: wgpu::BindGroup,
     model_buffer: RawUniformBuffer,
@@ -36,7 +37,7 @@ impl LineMaterial {
         blend: BlendState,
         depth: DepthState,
     ) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/line.wgsl");
+        let shader = ShaderFile::embedded("line.wgsl", include_str!("../../shaders/line.wgsl"));
 
         // Camera bind group layout (group 0)
         let camera_bind_group_layout =
@@ -72,18 +73,27 @@ impl LineMaterial {
                     }],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("line material pipeline")
-            .shader(shader)
-            .vertex_layout(VertexPC::layout())
-            .bind_group_layout(&camera_bind_group_layout)
-            .bind_group_layout(&model_bind_group_layout)
-            .color_format(format)
-            .depth(depth)
-            .blend(blend)
-            .cull(CullState::None)
-            .topology(wgpu::PrimitiveTopology::LineList)
-            .build()?;
+        let (pipeline_ctx, camera_layout, model_layout) = (
+            ctx.clone(),
+            camera_bind_group_layout.clone(),
+            model_bind_group_layout.clone(),
+        );
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("line material pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(VertexPC::layout())
+                .bind_group_layout(&camera_layout)
+                .bind_group_layout(&model_layout)
+                .color_format(format)
+                .depth(depth)
+                .blend(blend)
+                .cull(CullState::None)
+                .topology(wgpu::PrimitiveTopology::LineList)
+                .build()
+        })?;
 
         // Create camera uniform buffer
         let camera_buffer = RawUniformBuffer::new(
@@ -135,9 +145,16 @@ impl LineMaterial {
         self.model_buffer.write(ctx, &model_uniform);
     }
 
-    /// Get the render pipeline.
-    pub fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
+    /// Get the render pipeline. Reference counted, so the clone is cheap.
+    pub fn pipeline(&self) -> wgpu::RenderPipeline {
+        self.pipeline.get().clone()
+    }
+
+    /// Rebuild the pipeline if its shader file changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// it was rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
     }
 
     /// Get the camera bind group.
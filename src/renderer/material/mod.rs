@@ -13,6 +13,7 @@ mod position;
 mod sprite;
 mod terrain;
 mod traits;
+mod uniform_ring;
 mod unlit;
 mod uv;
 
@@ -27,5 +28,6 @@ pub use position::PositionMaterial;
 pub use sprite::SpriteMaterial;
 pub use terrain::{TerrainMaterial, TerrainUniform};
 pub use traits::{Material, ModelUniform};
+pub use uniform_ring::{CameraUniformRing, ModelUniformRing, UniformRing};
 pub use unlit::UnlitMaterial;
 pub use uv::UVMaterial;
@@ -3,6 +3,7 @@
 use super::traits::{Material, ModelUniform};
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::{PipelineBuilder, Vertex};
 use crate::core::render_states::{BlendState, CullState, DepthState};
 use crate::renderer::light::Light;
@@ -12,7 +13,7 @@ use glam::Mat4;
 /// Material that visualizes surface normals as colors.
 /// X = Red, Y = Green, Z = Blue (remapped from [-1,1] to [0,1])
 pub struct NormalMaterial {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     camera_buffer: RawUniformBuffer,
     camera_bind_group: wgpu::BindGroup,
     model_buffer: RawUniformBuffer,
@@ -22,53 +23,34 @@ pub struct NormalMaterial {
 impl NormalMaterial {
     /// Create a new normal visualization material.
     pub fn new(ctx: &WgpuContext, format: wgpu::TextureFormat) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/normal.wgsl");
+        let shader = ShaderFile::embedded("normal.wgsl", include_str!("../../shaders/normal.wgsl"));
 
         // Camera bind group layout (group 0)
-        let camera_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("normal camera bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
+        let camera_bind_group_layout = CameraUniform::bind_group_layout(ctx);
 
         // Model bind group layout (group 1)
-        let model_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("normal model bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
-
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("normal material pipeline")
-            .shader(shader)
-            .vertex_layout(Vertex::layout())
-            .bind_group_layout(&camera_bind_group_layout)
-            .bind_group_layout(&model_bind_group_layout)
-            .color_format(format)
-            .depth(DepthState::read_write())
-            .blend(BlendState::Opaque)
-            .cull(CullState::Back)
-            .build()?;
+        let model_bind_group_layout = ModelUniform::bind_group_layout(ctx);
+
+        let (pipeline_ctx, camera_layout, model_layout) = (
+            ctx.clone(),
+            camera_bind_group_layout.clone(),
+            model_bind_group_layout.clone(),
+        );
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("normal material pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(Vertex::layout())
+                .bind_group_layout(&camera_layout)
+                .bind_group_layout(&model_layout)
+                .color_format(format)
+                .depth(DepthState::read_write())
+                .blend(BlendState::Opaque)
+                .cull(CullState::Back)
+                .build()
+        })?;
 
         // Create camera uniform buffer
         let camera_buffer = RawUniformBuffer::new(
@@ -113,8 +95,8 @@ impl NormalMaterial {
 }
 
 impl Material for NormalMaterial {
-    fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
+    fn pipeline(&self) -> wgpu::RenderPipeline {
+        self.pipeline.get().clone()
     }
 
     fn camera_bind_group(&self) -> &wgpu::BindGroup {
@@ -125,16 +107,21 @@ impl Material for NormalMaterial {
         &self.model_bind_group
     }
 
-    fn update_uniforms(
+    fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
+
+    fn update_shared_uniforms(
         &self,
         ctx: &WgpuContext,
         viewer: &dyn Viewer,
-        model_matrix: Mat4,
         _lights: &[&dyn Light],
     ) {
         let camera_uniform = CameraUniform::from_viewer(viewer);
         self.camera_buffer.write(ctx, &camera_uniform);
+    }
 
+    fn update_model_uniform(&self, ctx: &WgpuContext, model_matrix: Mat4) {
         let model_uniform = ModelUniform::from_matrix(model_matrix);
         self.model_buffer.write(ctx, &model_uniform);
     }
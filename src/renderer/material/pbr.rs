@@ -3,9 +3,12 @@
 use super::traits::{Material, ModelUniform};
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
+use crate::core::instance::InstanceData;
 use crate::core::pipeline::{PipelineBuilder, Vertex};
 use crate::core::render_states::{BlendState, CullState, DepthState};
-use crate::renderer::light::Light;
+use crate::renderer::light::{GpuLight, Light};
+use crate::renderer::lighting::{SceneLighting, MATERIAL_LIGHT_CAPACITY};
 use crate::renderer::viewer::{CameraUniform, Viewer};
 use glam::Mat4;
 
@@ -40,15 +43,22 @@ impl Default for PbrUniform {
     }
 }
 
+/// Regular and instanced pipelines, rebuilt together on shader reload.
+struct PbrPipelines {
+    pipeline: wgpu::RenderPipeline,
+    instanced: wgpu::RenderPipeline,
+}
+
 /// PBR material with metallic-roughness workflow.
 pub struct PbrMaterial {
-    pipeline: wgpu::RenderPipeline,
+    pipelines: HotShader<PbrPipelines>,
     camera_buffer: RawUniformBuffer,
     camera_bind_group: wgpu::BindGroup,
     model_buffer: RawUniformBuffer,
     model_bind_group: wgpu::BindGroup,
     pbr_buffer: RawUniformBuffer,
     pbr_bind_group: wgpu::BindGroup,
+    lighting: SceneLighting,
 
     /// Base color (albedo).
     pub base_color: [f32; 4],
@@ -86,41 +96,11 @@ impl PbrMaterial {
         emissive: [f32; 3],
         ao: f32,
     ) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/pbr.wgsl");
-
         // Camera bind group layout (group 0)
-        let camera_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("pbr camera bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
+        let camera_bind_group_layout = CameraUniform::bind_group_layout(ctx);
 
         // Model bind group layout (group 1)
-        let model_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("pbr model bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
+        let model_bind_group_layout = ModelUniform::bind_group_layout(ctx);
 
         // PBR parameters bind group layout (group 2)
         let pbr_bind_group_layout =
@@ -139,18 +119,48 @@ impl PbrMaterial {
                     }],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("pbr material pipeline")
-            .shader(shader)
-            .vertex_layout(Vertex::layout())
-            .bind_group_layout(&camera_bind_group_layout)
-            .bind_group_layout(&model_bind_group_layout)
-            .bind_group_layout(&pbr_bind_group_layout)
-            .color_format(format)
-            .depth(DepthState::read_write())
-            .blend(BlendState::Opaque)
-            .cull(CullState::Back)
-            .build()?;
+        // Scene lighting bind group layout (group 3)
+        let lighting_bind_group_layout = SceneLighting::bind_group_layout(ctx);
+
+        let shader = ShaderFile::embedded("pbr.wgsl", include_str!("../../shaders/pbr.wgsl"));
+        let pipeline_ctx = ctx.clone();
+        let layouts = [
+            camera_bind_group_layout.clone(),
+            model_bind_group_layout.clone(),
+            pbr_bind_group_layout.clone(),
+            lighting_bind_group_layout.clone(),
+        ];
+        let watched = shader.is_watched();
+        let pipelines = HotShader::new(shader, move |shader| {
+            let builder = |label| {
+                layouts.iter().fold(
+                    PipelineBuilder::new(&pipeline_ctx)
+                        .label(label)
+                        .shader(shader)
+                        .catch_errors(watched)
+                        .vertex_layout(Vertex::layout()),
+                    |builder, layout| builder.bind_group_layout(layout),
+                )
+            };
+            let pipeline = builder("pbr material pipeline")
+                .color_format(format)
+                .depth(DepthState::read_write())
+                .blend(BlendState::Opaque)
+                .cull(CullState::Back)
+                .build()?;
+            let instanced = builder("pbr material instanced pipeline")
+                .vertex_entry("vs_instanced")
+                .vertex_layout(InstanceData::layout())
+                .color_format(format)
+                .depth(DepthState::read_write())
+                .blend(BlendState::Opaque)
+                .cull(CullState::Back)
+                .build()?;
+            Ok(PbrPipelines {
+                pipeline,
+                instanced,
+            })
+        })?;
 
         // Create camera uniform buffer
         let camera_buffer = RawUniformBuffer::new(
@@ -201,13 +211,14 @@ impl PbrMaterial {
         });
 
         Ok(Self {
-            pipeline,
+            pipelines,
             camera_buffer,
             camera_bind_group,
             model_buffer,
             model_bind_group,
             pbr_buffer,
             pbr_bind_group,
+            lighting: SceneLighting::new(ctx, MATERIAL_LIGHT_CAPACITY, None),
             base_color,
             metallic,
             roughness,
@@ -223,34 +234,48 @@ impl PbrMaterial {
 }
 
 impl Material for PbrMaterial {
-    fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
+    fn pipeline(&self) -> wgpu::RenderPipeline {
+        self.pipelines.get().pipeline.clone()
     }
 
     fn camera_bind_group(&self) -> &wgpu::BindGroup {
         &self.camera_bind_group
     }
 
+    fn instanced_pipeline(&self) -> Option<wgpu::RenderPipeline> {
+        Some(self.pipelines.get().instanced.clone())
+    }
+
     fn model_bind_group(&self) -> &wgpu::BindGroup {
         &self.model_bind_group
     }
 
     fn extra_bind_groups(&self) -> Vec<(u32, &wgpu::BindGroup)> {
-        vec![(2, &self.pbr_bind_group)]
+        vec![
+            (2, &self.pbr_bind_group),
+            (3, self.lighting.bind_group(false)),
+        ]
     }
 
-    fn update_uniforms(
+    fn reload_shaders(&self) -> bool {
+        self.pipelines.reload_if_changed()
+    }
+
+    fn lighting_group(&self) -> Option<u32> {
+        Some(3)
+    }
+
+    fn update_shared_uniforms(
         &self,
         ctx: &WgpuContext,
         viewer: &dyn Viewer,
-        model_matrix: Mat4,
-        _lights: &[&dyn Light],
+        lights: &[&dyn Light],
     ) {
         let camera_uniform = CameraUniform::from_viewer(viewer);
         self.camera_buffer.write(ctx, &camera_uniform);
 
-        let model_uniform = ModelUniform::from_matrix(model_matrix);
-        self.model_buffer.write(ctx, &model_uniform);
+        let gpu_lights: Vec<GpuLight> = lights.iter().map(|light| light.gpu_light()).collect();
+        self.lighting.write_lights(ctx, &gpu_lights);
 
         let pbr_uniform = PbrUniform {
             base_color: self.base_color,
@@ -262,4 +287,9 @@ impl Material for PbrMaterial {
         };
         self.pbr_buffer.write(ctx, &pbr_uniform);
     }
+
+    fn update_model_uniform(&self, ctx: &WgpuContext, model_matrix: Mat4) {
+        let model_uniform = ModelUniform::from_matrix(model_matrix);
+        self.model_buffer.write(ctx, &model_uniform);
+    }
 }
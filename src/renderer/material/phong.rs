@@ -42,7 +42,7 @@ impl PhongMaterial {
 }
 
 impl Material for PhongMaterial {
-    fn pipeline(&self) -> &wgpu::RenderPipeline {
+    fn pipeline(&self) -> wgpu::RenderPipeline {
         self.inner.pipeline()
     }
 
@@ -54,14 +54,28 @@ impl Material for PhongMaterial {
         self.inner.model_bind_group()
     }
 
-    fn update_uniforms(
+    fn instanced_pipeline(&self) -> Option<wgpu::RenderPipeline> {
+        self.inner.instanced_pipeline()
+    }
+
+    fn extra_bind_groups(&self) -> Vec<(u32, &wgpu::BindGroup)> {
+        self.inner.extra_bind_groups()
+    }
+
+    fn lighting_group(&self) -> Option<u32> {
+        self.inner.lighting_group()
+    }
+
+    fn update_shared_uniforms(
         &self,
         ctx: &WgpuContext,
         viewer: &dyn Viewer,
-        model_matrix: Mat4,
         lights: &[&dyn Light],
     ) {
-        self.inner
-            .update_uniforms(ctx, viewer, model_matrix, lights);
+        self.inner.update_shared_uniforms(ctx, viewer, lights);
+    }
+
+    fn update_model_uniform(&self, ctx: &WgpuContext, model_matrix: Mat4) {
+        self.inner.update_model_uniform(ctx, model_matrix);
     }
 }
@@ -3,6 +3,7 @@
 use super::traits::{Material, ModelUniform};
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::{PipelineBuilder, Vertex};
 use crate::core::render_states::{BlendState, CullState, DepthState};
 use crate::renderer::light::Light;
@@ -11,7 +12,7 @@ use glam::Mat4;
 
 /// Material for rendering billboard sprites that always face the camera.
 pub struct SpriteMaterial {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     camera_buffer: RawUniformBuffer,
     camera_bind_group: wgpu::BindGroup,
     model_buffer: RawUniformBuffer,
@@ -21,51 +22,34 @@ pub struct SpriteMaterial {
 impl SpriteMaterial {
     /// Create a new sprite material.
     pub fn new(ctx: &WgpuContext, format: wgpu::TextureFormat) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/sprite.wgsl");
-
-        let camera_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("sprite camera bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
-
-        let model_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("sprite model bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
-
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("sprite material pipeline")
-            .shader(shader)
-            .vertex_layout(Vertex::layout())
-            .bind_group_layout(&camera_bind_group_layout)
-            .bind_group_layout(&model_bind_group_layout)
-            .color_format(format)
-            .depth(DepthState::read_write())
-            .blend(BlendState::Alpha)
-            .cull(CullState::None)
-            .build()?;
+        let shader = ShaderFile::embedded("sprite.wgsl", include_str!("../../shaders/sprite.wgsl"));
+
+        // Camera bind group layout (group 0)
+        let camera_bind_group_layout = CameraUniform::bind_group_layout(ctx);
+
+        // Model bind group layout (group 1)
+        let model_bind_group_layout = ModelUniform::bind_group_layout(ctx);
+
+        let (pipeline_ctx, camera_layout, model_layout) = (
+            ctx.clone(),
+            camera_bind_group_layout.clone(),
+            model_bind_group_layout.clone(),
+        );
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("sprite material pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(Vertex::layout())
+                .bind_group_layout(&camera_layout)
+                .bind_group_layout(&model_layout)
+                .color_format(format)
+                .depth(DepthState::read_write())
+                .blend(BlendState::Alpha)
+                .cull(CullState::None)
+                .build()
+        })?;
 
         let camera_buffer = RawUniformBuffer::new(
             ctx,
@@ -108,8 +92,8 @@ impl SpriteMaterial {
 }
 
 impl Material for SpriteMaterial {
-    fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
+    fn pipeline(&self) -> wgpu::RenderPipeline {
+        self.pipeline.get().clone()
     }
 
     fn camera_bind_group(&self) -> &wgpu::BindGroup {
@@ -120,16 +104,21 @@ impl Material for SpriteMaterial {
         &self.model_bind_group
     }
 
-    fn update_uniforms(
+    fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
+
+    fn update_shared_uniforms(
         &self,
         ctx: &WgpuContext,
         viewer: &dyn Viewer,
-        model_matrix: Mat4,
         _lights: &[&dyn Light],
     ) {
         let camera_uniform = CameraUniform::from_viewer(viewer);
         self.camera_buffer.write(ctx, &camera_uniform);
+    }
 
+    fn update_model_uniform(&self, ctx: &WgpuContext, model_matrix: Mat4) {
         let model_uniform = ModelUniform::from_matrix(model_matrix);
         self.model_buffer.write(ctx, &model_uniform);
     }
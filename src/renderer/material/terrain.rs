@@ -6,6 +6,7 @@
 use super::traits::{Material, ModelUniform};
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::{PipelineBuilder, Vertex};
 use crate::core::render_states::{BlendState, CullState, DepthState};
 use crate::renderer::light::Light;
@@ -29,7 +30,7 @@ pub struct TerrainUniform {
 /// Uses a custom shader that blends between a low-altitude color and a high-altitude
 /// color based on vertex height.
 pub struct TerrainMaterial {
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     camera_buffer: RawUniformBuffer,
     camera_bind_group: wgpu::BindGroup,
     model_buffer: RawUniformBuffer,
@@ -69,41 +70,14 @@ impl TerrainMaterial {
         min_height: f32,
         max_height: f32,
     ) -> anyhow::Result<Self> {
-        let shader = include_str!("../../shaders/terrain.wgsl");
+        let shader =
+            ShaderFile::embedded("terrain.wgsl", include_str!("../../shaders/terrain.wgsl"));
 
         // Camera bind group layout (group 0)
-        let camera_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("terrain camera bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
+        let camera_bind_group_layout = CameraUniform::bind_group_layout(ctx);
 
         // Model bind group layout (group 1)
-        let model_bind_group_layout =
-            ctx.device
-                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: Some("terrain model bind group layout"),
-                    entries: &[wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::VERTEX,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    }],
-                });
+        let model_bind_group_layout = ModelUniform::bind_group_layout(ctx);
 
         // Terrain bind group layout (group 2)
         let terrain_bind_group_layout =
@@ -122,18 +96,28 @@ impl TerrainMaterial {
                     }],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("terrain material pipeline")
-            .shader(shader)
-            .vertex_layout(Vertex::layout())
-            .bind_group_layout(&camera_bind_group_layout)
-            .bind_group_layout(&model_bind_group_layout)
-            .bind_group_layout(&terrain_bind_group_layout)
-            .color_format(format)
-            .depth(DepthState::read_write())
-            .blend(BlendState::Opaque)
-            .cull(CullState::Back)
-            .build()?;
+        let (pipeline_ctx, camera_layout, model_layout, terrain_layout) = (
+            ctx.clone(),
+            camera_bind_group_layout.clone(),
+            model_bind_group_layout.clone(),
+            terrain_bind_group_layout.clone(),
+        );
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("terrain material pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(Vertex::layout())
+                .bind_group_layout(&camera_layout)
+                .bind_group_layout(&model_layout)
+                .bind_group_layout(&terrain_layout)
+                .color_format(format)
+                .depth(DepthState::read_write())
+                .blend(BlendState::Opaque)
+                .cull(CullState::Back)
+                .build()
+        })?;
 
         // Create camera uniform buffer
         let camera_buffer = RawUniformBuffer::new(
@@ -200,8 +184,8 @@ impl TerrainMaterial {
 }
 
 impl Material for TerrainMaterial {
-    fn pipeline(&self) -> &wgpu::RenderPipeline {
-        &self.pipeline
+    fn pipeline(&self) -> wgpu::RenderPipeline {
+        self.pipeline.get().clone()
     }
 
     fn camera_bind_group(&self) -> &wgpu::BindGroup {
@@ -212,23 +196,23 @@ impl Material for TerrainMaterial {
         &self.model_bind_group
     }
 
+    fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
+
     fn extra_bind_groups(&self) -> Vec<(u32, &wgpu::BindGroup)> {
         vec![(2, &self.terrain_bind_group)]
     }
 
-    fn update_uniforms(
+    fn update_shared_uniforms(
         &self,
         ctx: &WgpuContext,
         viewer: &dyn Viewer,
-        model_matrix: Mat4,
         _lights: &[&dyn Light],
     ) {
         let camera_uniform = CameraUniform::from_viewer(viewer);
         self.camera_buffer.write(ctx, &camera_uniform);
 
-        let model_uniform = ModelUniform::from_matrix(model_matrix);
-        self.model_buffer.write(ctx, &model_uniform);
-
         let terrain_uniform = TerrainUniform {
             min_height: self.min_height,
             max_height: self.max_height,
@@ -239,4 +223,9 @@ impl Material for TerrainMaterial {
         };
         self.terrain_buffer.write(ctx, &terrain_uniform);
     }
+
+    fn update_model_uniform(&self, ctx: &WgpuContext, model_matrix: Mat4) {
+        let model_uniform = ModelUniform::from_matrix(model_matrix);
+        self.model_buffer.write(ctx, &model_uniform);
+    }
 }
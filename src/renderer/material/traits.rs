@@ -8,7 +8,11 @@ use glam::Mat4;
 /// Trait for materials that control surface appearance.
 pub trait Material {
     /// Get the render pipeline.
-    fn pipeline(&self) -> &wgpu::RenderPipeline;
+    ///
+    /// Pipelines are reference counted, so this is a cheap clone; it lets
+    /// materials rebuild theirs on [`reload_shaders`](Self::reload_shaders)
+    /// without `&mut self`.
+    fn pipeline(&self) -> wgpu::RenderPipeline;
 
     /// Get the camera bind group.
     fn camera_bind_group(&self) -> &wgpu::BindGroup;
@@ -27,6 +31,41 @@ pub trait Material {
         Vec::new()
     }
 
+    /// Pipeline variant that reads the model transform and color from an
+    /// [`InstanceData`](crate::core::instance::InstanceData) vertex buffer at
+    /// slot 1 instead of the model uniform.
+    ///
+    /// It shares the bind group layouts of [`pipeline`](Self::pipeline), so group
+    /// 1 must still be bound even though the shader ignores it. Materials
+    /// without an instanced variant return `None` and are drawn one entity at
+    /// a time.
+    fn instanced_pipeline(&self) -> Option<wgpu::RenderPipeline> {
+        None
+    }
+
+    /// Group index at which the material binds a
+    /// [`SceneLighting`](crate::renderer::SceneLighting).
+    ///
+    /// Its own lighting is returned from
+    /// [`extra_bind_groups`](Self::extra_bind_groups) and filled with the
+    /// `lights` passed to [`update_shared_uniforms`](Self::update_shared_uniforms);
+    /// renderers with scene-wide lights and shadows bind theirs at this index
+    /// instead. Unlit materials return `None`.
+    fn lighting_group(&self) -> Option<u32> {
+        None
+    }
+
+    /// Update state shared by every draw using this material: camera, lights
+    /// and material parameters.
+    fn update_shared_uniforms(&self, ctx: &WgpuContext, viewer: &dyn Viewer, lights: &[&dyn Light]);
+
+    /// Write `model_matrix` into the material's own model buffer, bound by
+    /// [`model_bind_group`](Self::model_bind_group) at dynamic offset 0.
+    ///
+    /// Only valid for one draw per submit; renderers drawing many instances
+    /// with one material use a [`ModelUniformRing`](super::ModelUniformRing).
+    fn update_model_uniform(&self, ctx: &WgpuContext, model_matrix: Mat4);
+
     /// Update uniforms before rendering.
     fn update_uniforms(
         &self,
@@ -34,7 +73,17 @@ pub trait Material {
         viewer: &dyn Viewer,
         model_matrix: Mat4,
         lights: &[&dyn Light],
-    );
+    ) {
+        self.update_shared_uniforms(ctx, viewer, lights);
+        self.update_model_uniform(ctx, model_matrix);
+    }
+
+    /// Rebuild pipelines whose shader files changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// anything was rebuilt.
+    fn reload_shaders(&self) -> bool {
+        false
+    }
 }
 
 /// Model uniform data for GPU.
@@ -46,6 +95,29 @@ pub struct ModelUniform {
 }
 
 impl ModelUniform {
+    /// Bind group layout for the model uniform (group 1) shared by all materials.
+    ///
+    /// The binding uses a dynamic offset so a single buffer can hold the model
+    /// uniforms of many draws.
+    pub fn bind_group_layout(ctx: &WgpuContext) -> wgpu::BindGroupLayout {
+        ctx.device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("model bind group layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: wgpu::BufferSize::new(
+                            std::mem::size_of::<ModelUniform>() as u64,
+                        ),
+                    },
+                    count: None,
+                }],
+            })
+    }
+
     pub fn from_matrix(model: Mat4) -> Self {
         let normal_matrix = model.inverse().transpose();
         Self {
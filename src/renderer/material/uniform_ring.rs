@@ -0,0 +1,214 @@
+//! Per-frame rings of uniforms addressed by dynamic offset.
+
+use std::marker::PhantomData;
+
+use bytemuck::Pod;
+use glam::Mat4;
+
+use super::traits::ModelUniform;
+use crate::context::WgpuContext;
+use crate::renderer::viewer::{CameraUniform, Viewer};
+
+/// Number of uniforms a new ring has room for.
+const INITIAL_CAPACITY: u64 = 256;
+
+/// A uniform buffer holding many values of `T`, each bound by dynamic offset.
+///
+/// Materials share pipeline and parameter state between every entity and
+/// camera using them, so per-draw and per-camera data cannot live in the
+/// material. Instead, renderers [`push`](Self::push) each value,
+/// [`upload`](Self::upload) once before drawing and bind
+/// [`bind_group`](Self::bind_group) with the returned dynamic offset.
+pub struct UniformRing<T> {
+    layout: wgpu::BindGroupLayout,
+    label: &'static str,
+    buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    /// Distance between consecutive uniforms, aligned to the device's
+    /// `min_uniform_buffer_offset_alignment`.
+    stride: u64,
+    /// Number of uniforms the buffer can hold.
+    capacity: u64,
+    staging: Vec<u8>,
+    len: u64,
+    _marker: PhantomData<T>,
+}
+
+/// Ring of model uniforms bound at group 1.
+pub type ModelUniformRing = UniformRing<ModelUniform>;
+
+/// Ring of camera uniforms bound at group 0.
+pub type CameraUniformRing = UniformRing<CameraUniform>;
+
+impl<T: Pod> UniformRing<T> {
+    /// Create an empty ring whose bind group uses `layout`, a single dynamic
+    /// uniform binding of `T`.
+    pub fn with_layout(
+        ctx: &WgpuContext,
+        layout: wgpu::BindGroupLayout,
+        label: &'static str,
+    ) -> Self {
+        let alignment = ctx.device.limits().min_uniform_buffer_offset_alignment as u64;
+        let stride = (std::mem::size_of::<T>() as u64).next_multiple_of(alignment);
+        let (buffer, bind_group) = Self::allocate(ctx, &layout, label, stride * INITIAL_CAPACITY);
+        Self {
+            layout,
+            label,
+            buffer,
+            bind_group,
+            stride,
+            capacity: INITIAL_CAPACITY,
+            staging: Vec::new(),
+            len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    fn allocate(
+        ctx: &WgpuContext,
+        layout: &wgpu::BindGroupLayout,
+        label: &str,
+        size: u64,
+    ) -> (wgpu::Buffer, wgpu::BindGroup) {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
+            layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &buffer,
+                    offset: 0,
+                    size: wgpu::BufferSize::new(std::mem::size_of::<T>() as u64),
+                }),
+            }],
+        });
+        (buffer, bind_group)
+    }
+
+    /// Remove all pushed uniforms. Call once per frame before pushing.
+    pub fn clear(&mut self) {
+        self.staging.clear();
+        self.len = 0;
+    }
+
+    /// Append a uniform, returning its dynamic offset.
+    pub fn push(&mut self, value: &T) -> u32 {
+        let offset = self.len * self.stride;
+        self.staging.extend_from_slice(bytemuck::bytes_of(value));
+        self.staging.resize((offset + self.stride) as usize, 0);
+        self.len += 1;
+        offset as u32
+    }
+
+    /// Write all pushed uniforms to the GPU, growing the buffer if needed.
+    ///
+    /// Growing replaces the bind group, so call this before binding it.
+    pub fn upload(&mut self, ctx: &WgpuContext) {
+        if self.len > self.capacity {
+            let capacity = self.len.next_power_of_two();
+            let (buffer, bind_group) =
+                Self::allocate(ctx, &self.layout, self.label, self.stride * capacity);
+            self.buffer = buffer;
+            self.bind_group = bind_group;
+            self.capacity = capacity;
+        }
+        if !self.staging.is_empty() {
+            ctx.queue.write_buffer(&self.buffer, 0, &self.staging);
+        }
+    }
+
+    /// Bind group used with the offsets returned by [`push`](Self::push).
+    pub fn bind_group(&self) -> &wgpu::BindGroup {
+        &self.bind_group
+    }
+
+    /// Number of uniforms pushed since the last [`clear`](Self::clear).
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Whether no uniforms have been pushed since the last [`clear`](Self::clear).
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Distance in bytes between consecutive dynamic offsets.
+    pub fn stride(&self) -> u64 {
+        self.stride
+    }
+}
+
+impl UniformRing<ModelUniform> {
+    /// Create an empty ring of model uniforms.
+    pub fn new(ctx: &WgpuContext) -> Self {
+        Self::with_layout(
+            ctx,
+            ModelUniform::bind_group_layout(ctx),
+            "model uniform ring",
+        )
+    }
+
+    /// Append the model uniform for `model_matrix`, returning its dynamic offset.
+    pub fn push_matrix(&mut self, model_matrix: Mat4) -> u32 {
+        self.push(&ModelUniform::from_matrix(model_matrix))
+    }
+}
+
+impl UniformRing<CameraUniform> {
+    /// Create an empty ring of camera uniforms.
+    pub fn new(ctx: &WgpuContext) -> Self {
+        Self::with_layout(
+            ctx,
+            CameraUniform::bind_group_layout(ctx),
+            "camera uniform ring",
+        )
+    }
+
+    /// Append the camera uniform for `viewer`, returning its dynamic offset.
+    pub fn push_viewer(&mut self, viewer: &dyn Viewer) -> u32 {
+        self.push(&CameraUniform::from_viewer(viewer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn try_create_ctx() -> Option<WgpuContext> {
+        if std::env::var("REIN_SKIP_GPU_TESTS").is_ok() {
+            return None;
+        }
+        WgpuContext::new_blocking(None).ok()
+    }
+
+    #[test]
+    fn test_ring_offsets_are_aligned_and_grow() {
+        let Some(ctx) = try_create_ctx() else {
+            eprintln!("Skipping: no GPU device available");
+            return;
+        };
+        let mut ring = ModelUniformRing::new(&ctx);
+        let alignment = ctx.device.limits().min_uniform_buffer_offset_alignment as u64;
+        assert_eq!(ring.stride() % alignment, 0);
+        assert!(ring.stride() >= std::mem::size_of::<ModelUniform>() as u64);
+
+        let count = INITIAL_CAPACITY as usize + 10;
+        let offsets: Vec<u32> = (0..count)
+            .map(|i| ring.push_matrix(Mat4::from_translation(glam::Vec3::splat(i as f32))))
+            .collect();
+        assert_eq!(offsets[1] as u64, ring.stride());
+        assert_eq!(ring.len(), count);
+        ring.upload(&ctx);
+        assert!(ring.capacity >= count as u64);
+
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(ring.push_matrix(Mat4::IDENTITY), 0);
+    }
+}
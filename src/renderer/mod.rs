@@ -7,8 +7,10 @@ pub mod control;
 pub mod culling;
 pub mod geometry;
 pub mod light;
+pub mod lighting;
 pub mod material;
 pub mod object;
+pub mod ray;
 pub mod shadow;
 pub mod viewer;
 
@@ -19,12 +21,16 @@ pub use geometry::{
     Aabb, Axes, BoundingBoxMesh, Circle, Geometry, InstancedMesh, LineStrip, Lines, Mesh,
     ParticleData, ParticleSystem, Rectangle, Skybox, Sprites, Terrain, TerrainLod,
 };
-pub use light::{AmbientLight, Attenuation, DirectionalLight, Light, PointLight, SpotLight};
+pub use light::{
+    AmbientLight, Attenuation, DirectionalLight, GpuLight, Light, PointLight, SpotLight,
+};
+pub use lighting::SceneLighting;
 pub use material::{
-    ColorMaterial, DepthMaterial, GridMaterial, LineMaterial, Material, ModelUniform,
-    NormalMaterial, PbrMaterial, PhongMaterial, PositionMaterial, SpriteMaterial, TerrainMaterial,
-    TerrainUniform, UVMaterial, UnlitMaterial,
+    CameraUniformRing, ColorMaterial, DepthMaterial, GridMaterial, LineMaterial, Material,
+    ModelUniform, ModelUniformRing, NormalMaterial, PbrMaterial, PhongMaterial, PositionMaterial,
+    SpriteMaterial, TerrainMaterial, TerrainUniform, UVMaterial, UniformRing, UnlitMaterial,
 };
 pub use object::{Gm, Object};
+pub use ray::Ray;
 pub use shadow::{DirectionalShadow, ShadowConfig, ShadowMap, ShadowUniform};
 pub use viewer::{Camera, Projection, Viewer};
@@ -82,9 +82,9 @@ impl<G: Geometry, M: Material> Object for Gm<G, M> {
             .update_uniforms(ctx, viewer, self.transform, lights);
 
         // Set pipeline and bind groups
-        render_pass.set_pipeline(self.material.pipeline());
-        render_pass.set_bind_group(0, self.material.camera_bind_group(), &[]);
-        render_pass.set_bind_group(1, self.material.model_bind_group(), &[]);
+        render_pass.set_pipeline(&self.material.pipeline());
+        render_pass.set_bind_group(0, self.material.camera_bind_group(), &[0]);
+        render_pass.set_bind_group(1, self.material.model_bind_group(), &[0]);
 
         // Bind any additional material-specific bind groups (e.g. group 2+)
         for (group, bind_group) in self.material.extra_bind_groups() {
@@ -0,0 +1,144 @@
+//! Rays for picking
+//!
+//! Builds world-space rays from a viewer and cursor position and intersects
+//! them with bounding boxes and triangles.
+
+use super::geometry::Aabb;
+use super::viewer::Viewer;
+use glam::{Mat4, Vec2, Vec3};
+
+/// A ray with an origin and a direction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    /// Direction; normalized for rays built by this module, so `t` values
+    /// are world distances.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Create a ray, normalizing `direction`.
+    pub fn new(origin: Vec3, direction: Vec3) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Ray through `ndc` (x and y in -1..1, y up) of the view-projection
+    /// `view_projection`, from the near plane towards the far plane.
+    ///
+    /// Works for both perspective and orthographic projections.
+    pub fn from_ndc(view_projection: Mat4, ndc: Vec2) -> Self {
+        let inverse = view_projection.inverse();
+        let near = inverse.project_point3(ndc.extend(0.0));
+        let far = inverse.project_point3(ndc.extend(1.0));
+        Self::new(near, far - near)
+    }
+
+    /// Ray under the cursor at `cursor` pixels from the top-left corner of a
+    /// viewport of `viewport_size` pixels.
+    pub fn from_screen(viewer: &dyn Viewer, cursor: Vec2, viewport_size: Vec2) -> Self {
+        let uv = cursor / viewport_size.max(Vec2::ONE);
+        let ndc = Vec2::new(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0);
+        Self::from_ndc(viewer.view_projection_matrix(), ndc)
+    }
+
+    /// Point at parameter `t` along the ray.
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// The ray in the space of `matrix`, keeping `t` values unchanged: the
+    /// direction is transformed but not normalized.
+    pub fn transform(&self, matrix: Mat4) -> Self {
+        Self {
+            origin: matrix.transform_point3(self.origin),
+            direction: matrix.transform_vector3(self.direction),
+        }
+    }
+
+    /// Entry parameter of the ray into `aabb`, or 0 if the origin is inside.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<f32> {
+        let inv = self.direction.recip();
+        let t0 = (aabb.min - self.origin) * inv;
+        let t1 = (aabb.max - self.origin) * inv;
+        // NaN from 0 * inf (origin on a slab plane) is dropped by min/max
+        let t_near = t0.min(t1).max_element().max(0.0);
+        let t_far = t0.max(t1).min_element();
+        (t_near <= t_far).then_some(t_near)
+    }
+
+    /// Parameter of the hit with triangle `a`, `b`, `c`, from either side.
+    pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+        // Möller-Trumbore
+        let edge1 = b - a;
+        let edge2 = c - a;
+        let p = self.direction.cross(edge2);
+        let det = edge1.dot(p);
+        if det.abs() < f32::EPSILON * edge1.length_squared().max(edge2.length_squared()) {
+            return None;
+        }
+        let inv_det = det.recip();
+        let s = self.origin - a;
+        let u = s.dot(p) * inv_det;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = s.cross(edge1);
+        let v = self.direction.dot(q) * inv_det;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = edge2.dot(q) * inv_det;
+        (t >= 0.0).then_some(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::viewer::Camera;
+
+    #[test]
+    fn test_screen_center_ray_points_at_target() {
+        let camera = Camera::new_perspective(
+            Vec3::new(0.0, 0.0, 10.0),
+            Vec3::ZERO,
+            Vec3::Y,
+            60.0,
+            2.0,
+            0.1,
+            100.0,
+        );
+        let ray = Ray::from_screen(&camera, Vec2::new(400.0, 200.0), Vec2::new(800.0, 400.0));
+        assert!(ray.direction.abs_diff_eq(-Vec3::Z, 1e-5));
+        assert!((ray.origin.z - 9.9).abs() < 1e-3);
+
+        // Upper-left cursor goes up and left
+        let corner = Ray::from_screen(&camera, Vec2::ZERO, Vec2::new(800.0, 400.0));
+        assert!(corner.direction.x < 0.0 && corner.direction.y > 0.0);
+    }
+
+    #[test]
+    fn test_aabb_and_triangle_hits() {
+        let ray = Ray::new(Vec3::new(0.25, 0.25, 5.0), -Vec3::Z);
+        let aabb = Aabb::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+        assert_eq!(ray.intersect_aabb(&aabb), Some(4.0));
+        assert_eq!(
+            Ray::new(Vec3::ZERO, Vec3::X).intersect_aabb(&aabb),
+            Some(0.0)
+        );
+        assert_eq!(
+            Ray::new(Vec3::new(0.0, 3.0, 5.0), -Vec3::Z).intersect_aabb(&aabb),
+            None
+        );
+
+        let (a, b, c) = (Vec3::ZERO, Vec3::X, Vec3::Y);
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(5.0));
+        // Back faces are hit too
+        assert_eq!(ray.intersect_triangle(a, c, b), Some(5.0));
+        let miss = Ray::new(Vec3::new(0.75, 0.75, 5.0), -Vec3::Z);
+        assert_eq!(miss.intersect_triangle(a, b, c), None);
+    }
+}
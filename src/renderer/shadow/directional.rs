@@ -3,6 +3,7 @@
 use super::{ShadowConfig, ShadowMap, ShadowUniform};
 use crate::context::WgpuContext;
 use crate::core::buffer::RawUniformBuffer;
+use crate::core::hot_reload::{HotShader, ShaderFile};
 use crate::core::pipeline::PipelineBuilder;
 use crate::core::render_states::{CullState, DepthState};
 use crate::core::vertex::VertexP;
@@ -22,7 +23,7 @@ struct DepthPassUniform {
 /// Directional light shadow mapper.
 pub struct DirectionalShadow {
     shadow_map: ShadowMap,
-    pipeline: wgpu::RenderPipeline,
+    pipeline: HotShader<wgpu::RenderPipeline>,
     bind_group_layout: wgpu::BindGroupLayout,
     uniform_buffer: RawUniformBuffer,
     shadow_sampler: wgpu::Sampler,
@@ -33,7 +34,7 @@ impl DirectionalShadow {
     pub fn new(ctx: &WgpuContext, config: ShadowConfig) -> anyhow::Result<Self> {
         let shadow_map = ShadowMap::new(ctx, config);
 
-        let shader = include_str!("../../shaders/shadow.wgsl");
+        let shader = ShaderFile::embedded("shadow.wgsl", include_str!("../../shaders/shadow.wgsl"));
 
         let bind_group_layout =
             ctx.device
@@ -51,14 +52,19 @@ impl DirectionalShadow {
                     }],
                 });
 
-        let pipeline = PipelineBuilder::new(ctx)
-            .label("shadow depth pipeline")
-            .shader(shader)
-            .vertex_layout(VertexP::layout())
-            .bind_group_layout(&bind_group_layout)
-            .depth(DepthState::default())
-            .cull(CullState::Back)
-            .build_depth_only()?;
+        let (pipeline_ctx, layout) = (ctx.clone(), bind_group_layout.clone());
+        let watched = shader.is_watched();
+        let pipeline = HotShader::new(shader, move |shader| {
+            PipelineBuilder::new(&pipeline_ctx)
+                .label("shadow depth pipeline")
+                .shader(shader)
+                .catch_errors(watched)
+                .vertex_layout(VertexP::layout())
+                .bind_group_layout(&layout)
+                .depth(DepthState::default())
+                .cull(CullState::Back)
+                .build_depth_only()
+        })?;
 
         let uniform_buffer = RawUniformBuffer::new(
             ctx,
@@ -137,7 +143,7 @@ impl DirectionalShadow {
             multiview_mask: None,
         });
 
-        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_pipeline(&self.pipeline.get());
 
         for (model_matrix, geometry) in objects {
             let uniform = DepthPassUniform {
@@ -160,6 +166,13 @@ impl DirectionalShadow {
         }
     }
 
+    /// Rebuild the pipeline if its shader file changed, when hot reload is
+    /// enabled (see [`hot_reload`](crate::core::hot_reload)). Returns whether
+    /// it was rebuilt.
+    pub fn reload_shaders(&self) -> bool {
+        self.pipeline.reload_if_changed()
+    }
+
     /// Get the shadow map for sampling.
     pub fn shadow_map(&self) -> &ShadowMap {
         &self.shadow_map
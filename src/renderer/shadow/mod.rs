@@ -69,14 +69,21 @@ impl ShadowMap {
         self.depth_texture.view()
     }
 
-    /// Get the shadow uniform data for shaders.
+    /// Get the shadow uniform data for shaders, with sampling enabled.
     pub fn uniform(&self) -> ShadowUniform {
+        let pcf_radius = if self.config.pcf_enabled {
+            self.config.pcf_radius
+        } else {
+            0
+        };
         ShadowUniform {
             light_matrix: self.light_matrix.to_cols_array_2d(),
             bias: self.config.bias,
             normal_bias: self.config.normal_bias,
-            pcf_radius: self.config.pcf_radius as f32,
+            pcf_radius: pcf_radius as f32,
             shadow_map_size: self.config.resolution as f32,
+            enabled: 1.0,
+            _padding: [0.0; 3],
         }
     }
 }
@@ -95,4 +102,16 @@ pub struct ShadowUniform {
     pub pcf_radius: f32,
     /// Shadow map size (for texel size calculation).
     pub shadow_map_size: f32,
+    /// 1 when receivers sample the shadow map, 0 to leave them unshadowed.
+    pub enabled: f32,
+    /// Padding.
+    pub _padding: [f32; 3],
+}
+
+impl ShadowUniform {
+    /// Copy of this uniform with shadow sampling switched on or off.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = if enabled { 1.0 } else { 0.0 };
+        self
+    }
 }
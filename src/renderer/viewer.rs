@@ -2,12 +2,14 @@
 //!
 //! Provides camera types for 3D rendering.
 
+use crate::context::WgpuContext;
 #[cfg(feature = "window")]
 use crate::window::frame_io::Viewport;
 use glam::{Mat4, Vec3};
 
 /// Projection mode for a camera.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "scene", derive(serde::Serialize, serde::Deserialize))]
 pub enum Projection {
     /// Perspective projection.
     Perspective {
@@ -223,6 +225,29 @@ pub struct CameraUniform {
 }
 
 impl CameraUniform {
+    /// Bind group layout for the camera uniform (group 0) shared by all materials.
+    ///
+    /// The binding uses a dynamic offset so a single buffer can hold the
+    /// uniforms of several cameras rendered in one pass.
+    pub fn bind_group_layout(ctx: &WgpuContext) -> wgpu::BindGroupLayout {
+        ctx.device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("camera bind group layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: wgpu::BufferSize::new(
+                            std::mem::size_of::<CameraUniform>() as u64,
+                        ),
+                    },
+                    count: None,
+                }],
+            })
+    }
+
     /// Create a new camera uniform from a viewer.
     pub fn from_viewer(viewer: &dyn Viewer) -> Self {
         let vp = viewer.view_projection_matrix();
@@ -16,6 +16,48 @@ var<uniform> camera: CameraUniform;
 @group(1) @binding(0)
 var<uniform> model: ModelUniform;
 
+struct ShadowUniform {
+    light_matrix: mat4x4<f32>,
+    bias: f32,
+    normal_bias: f32,
+    pcf_radius: f32,
+    shadow_map_size: f32,
+    enabled: f32,
+    _padding0: f32,
+    _padding1: f32,
+    _padding2: f32,
+};
+
+// kind in direction_kind.w: 0 ambient, 1 directional, 2 point, 3 spot
+struct Light {
+    position_range: vec4<f32>,
+    direction_kind: vec4<f32>,
+    color_intensity: vec4<f32>,
+    attenuation: vec4<f32>,
+    // x, y: cosines of the inner and outer spot cone, z: 1 when shadowed
+    cone_shadow: vec4<f32>,
+};
+
+struct SceneLights {
+    count: u32,
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+    lights: array<Light>,
+};
+
+@group(2) @binding(0)
+var<uniform> shadow: ShadowUniform;
+
+@group(2) @binding(1)
+var shadow_map: texture_depth_2d;
+
+@group(2) @binding(2)
+var shadow_sampler: sampler_comparison;
+
+@group(2) @binding(3)
+var<storage, read> scene_lights: SceneLights;
+
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) normal: vec3<f32>,
@@ -23,7 +65,7 @@ struct VertexInput {
 };
 
 struct VertexOutput {
-    @builtin(position) clip_position: vec4<f32>,
+    @builtin(position) @invariant clip_position: vec4<f32>,
     @location(0) world_position: vec3<f32>,
     @location(1) world_normal: vec3<f32>,
     @location(2) color: vec3<f32>,
@@ -42,22 +84,137 @@ fn vs_main(input: VertexInput) -> VertexOutput {
     return output;
 }
 
+// Per-instance transform and color, used by the instanced pipeline variant.
+struct InstanceInput {
+    @location(4) col0: vec4<f32>,
+    @location(5) col1: vec4<f32>,
+    @location(6) col2: vec4<f32>,
+    @location(7) col3: vec4<f32>,
+    @location(8) color: vec4<f32>,
+};
+
+@vertex
+fn vs_instanced(input: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var output: VertexOutput;
+
+    let instance_model = mat4x4<f32>(instance.col0, instance.col1, instance.col2, instance.col3);
+    let world_pos = instance_model * vec4<f32>(input.position, 1.0);
+    output.clip_position = camera.view_proj * world_pos;
+    output.world_position = world_pos.xyz;
+
+    // Inverse transpose of a rotation-scale matrix: divide each column by its squared length
+    let c0 = instance.col0.xyz;
+    let c1 = instance.col1.xyz;
+    let c2 = instance.col2.xyz;
+    let normal_matrix = mat3x3<f32>(c0 / dot(c0, c0), c1 / dot(c1, c1), c2 / dot(c2, c2));
+    output.world_normal = normalize(normal_matrix * input.normal);
+    output.color = input.color * instance.color.rgb;
+
+    return output;
+}
+
+// Fraction of light reaching a surface point, filtered with a (2r+1)^2 PCF kernel
+fn shadow_factor(world_position: vec3<f32>, normal: vec3<f32>) -> f32 {
+    if (shadow.enabled < 0.5) {
+        return 1.0;
+    }
+
+    let offset_position = world_position + normal * shadow.normal_bias;
+    let light_clip = shadow.light_matrix * vec4<f32>(offset_position, 1.0);
+    let ndc = light_clip.xyz / light_clip.w;
+    let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+    if (any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0)) || ndc.z > 1.0) {
+        return 1.0;
+    }
+
+    let depth = ndc.z - shadow.bias;
+    let texel = 1.0 / shadow.shadow_map_size;
+    let radius = i32(shadow.pcf_radius);
+    var lit = 0.0;
+    var samples = 0.0;
+    for (var y = -radius; y <= radius; y++) {
+        for (var x = -radius; x <= radius; x++) {
+            let offset = vec2<f32>(f32(x), f32(y)) * texel;
+            lit += textureSampleCompareLevel(shadow_map, shadow_sampler, uv + offset, depth);
+            samples += 1.0;
+        }
+    }
+    return lit / samples;
+}
+
+// Direction towards a light and its color scaled by intensity, distance and
+// cone attenuation, and shadowing
+struct LightSample {
+    to_light: vec3<f32>,
+    radiance: vec3<f32>,
+};
+
+fn sample_light(light: Light, world_position: vec3<f32>, normal: vec3<f32>) -> LightSample {
+    var sample: LightSample;
+    var strength = light.color_intensity.a;
+    let kind = u32(light.direction_kind.w + 0.5);
+    if (kind == 1u) {
+        sample.to_light = normalize(-light.direction_kind.xyz);
+    } else {
+        let offset = light.position_range.xyz - world_position;
+        let distance = length(offset);
+        sample.to_light = offset / max(distance, 1e-4);
+
+        let a = light.attenuation;
+        strength /= max(a.x + a.y * distance + a.z * distance * distance, 1e-4);
+        let range = light.position_range.w;
+        if (range > 0.0) {
+            let falloff = clamp(1.0 - pow(distance / range, 4.0), 0.0, 1.0);
+            strength *= falloff * falloff;
+        }
+        if (kind == 3u) {
+            let cos_angle = dot(-sample.to_light, normalize(light.direction_kind.xyz));
+            strength *= smoothstep(light.cone_shadow.y, light.cone_shadow.x, cos_angle);
+        }
+    }
+    if (light.cone_shadow.z > 0.5) {
+        strength *= shadow_factor(world_position, normal);
+    }
+    sample.radiance = light.color_intensity.rgb * strength;
+    return sample;
+}
+
+// Blinn-Phong diffuse and specular response to a light from `to_light`
+fn blinn_phong(n: vec3<f32>, view_dir: vec3<f32>, to_light: vec3<f32>) -> f32 {
+    let diffuse = max(dot(n, to_light), 0.0);
+    let half_dir = normalize(to_light + view_dir);
+    let specular = pow(max(dot(n, half_dir), 0.0), 32.0) * 0.3;
+    return diffuse * 0.7 + specular;
+}
+
 @fragment
 fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
-    // Simple directional light
-    let light_dir = normalize(vec3<f32>(0.3, 1.0, 0.5));
-    let ambient = 0.3;
-
     let n = normalize(input.world_normal);
-    let diffuse = max(dot(n, light_dir), 0.0);
-
-    // Specular (Blinn-Phong)
     let view_dir = normalize(camera.eye.xyz - input.world_position);
-    let half_dir = normalize(light_dir + view_dir);
-    let specular = pow(max(dot(n, half_dir), 0.0), 32.0) * 0.3;
 
-    let lighting = ambient + diffuse * 0.7 + specular;
-    let final_color = input.color * lighting;
+    var ambient = vec3<f32>(0.0);
+    var has_ambient = false;
+    var direct = vec3<f32>(0.0);
+    let count = scene_lights.count;
+    if (count == 0u) {
+        // Default sun when the scene has no lights
+        direct = vec3<f32>(blinn_phong(n, view_dir, normalize(vec3<f32>(0.3, 1.0, 0.5))));
+    }
+    for (var i = 0u; i < count; i++) {
+        let light = scene_lights.lights[i];
+        if (light.direction_kind.w < 0.5) {
+            ambient += light.color_intensity.rgb * light.color_intensity.a;
+            has_ambient = true;
+            continue;
+        }
+        let sample = sample_light(light, input.world_position, n);
+        direct += sample.radiance * blinn_phong(n, view_dir, sample.to_light);
+    }
+    if (!has_ambient) {
+        ambient = vec3<f32>(0.3);
+    }
+
+    let final_color = input.color * (ambient + direct);
 
     return vec4<f32>(final_color, 1.0);
 }
@@ -0,0 +1,284 @@
+// GPU SPH (smoothed-particle hydrodynamics) fluid solver.
+// Five passes per substep:
+//   Pass 1 (cs_clear_grid):  Reset spatial hash cell heads
+//   Pass 2 (cs_build_grid):  Insert particles into per-cell linked lists
+//   Pass 3 (cs_density):     Density and pressure from neighbors (poly6 kernel)
+//   Pass 4 (cs_forces):      Pressure (spiky) + viscosity + gravity accelerations
+//   Pass 5 (cs_integrate):   Symplectic Euler + boundary handling
+
+struct FluidParticle {
+    position: vec3<f32>,
+    density: f32,
+    velocity: vec3<f32>,
+    pressure: f32,
+};
+
+struct SphParams {
+    num_particles: u32,
+    table_size: u32,
+    num_colliders: u32,
+    _pad0: u32,
+    gravity: vec3<f32>,
+    dt: f32,
+    bounds_min: vec3<f32>,
+    smoothing_radius: f32,
+    bounds_max: vec3<f32>,
+    particle_mass: f32,
+    rest_density: f32,
+    stiffness: f32,
+    viscosity: f32,
+    boundary_restitution: f32,
+};
+
+// Same layout as the narrowphase ShapeData.
+// type 0 = sphere: data.x = radius
+// type 1 = box:    data.xyz = half_extents
+struct ShapeData {
+    position: vec3<f32>,
+    shape_type: u32,
+    data: vec4<f32>,
+    axis_x: vec3<f32>,
+    scale_x: f32,
+    axis_y: vec3<f32>,
+    scale_y: f32,
+    axis_z: vec3<f32>,
+    scale_z: f32,
+};
+
+@group(0) @binding(0) var<storage, read_write> particles: array<FluidParticle>;
+@group(0) @binding(1) var<storage, read_write> accelerations: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> cell_head: array<atomic<i32>>;
+@group(0) @binding(3) var<storage, read_write> next_particle: array<i32>;
+@group(0) @binding(4) var<storage, read> colliders: array<ShapeData>;
+
+@group(1) @binding(0) var<uniform> params: SphParams;
+
+const PI: f32 = 3.14159265359;
+
+fn cell_coord(p: vec3<f32>) -> vec3<i32> {
+    return vec3<i32>(floor(p / params.smoothing_radius));
+}
+
+// FNV-like hash for grid cells, folded into the table size
+fn hash_cell(c: vec3<i32>) -> u32 {
+    var h: u32 = 2166136261u;
+    h = h ^ u32(c.x + 32768);
+    h = h * 16777619u;
+    h = h ^ u32(c.y + 32768);
+    h = h * 16777619u;
+    h = h ^ u32(c.z + 32768);
+    h = h * 16777619u;
+    return h % params.table_size;
+}
+
+// Hash buckets of the 27 cells around `base`. Cells that collide in the table
+// share a chain, so repeats are set to table_size to walk each chain once
+fn neighbor_buckets(base: vec3<i32>) -> array<u32, 27> {
+    var buckets: array<u32, 27>;
+    var n = 0u;
+    for (var dx = -1; dx <= 1; dx++) {
+        for (var dy = -1; dy <= 1; dy++) {
+            for (var dz = -1; dz <= 1; dz++) {
+                let bucket = hash_cell(base + vec3<i32>(dx, dy, dz));
+                var seen = false;
+                for (var k = 0u; k < n; k++) {
+                    seen = seen || buckets[k] == bucket;
+                }
+                buckets[n] = select(bucket, params.table_size, seen);
+                n++;
+            }
+        }
+    }
+    return buckets;
+}
+
+@compute @workgroup_size(64)
+fn cs_clear_grid(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.table_size) {
+        return;
+    }
+    atomicStore(&cell_head[i], -1);
+}
+
+@compute @workgroup_size(64)
+fn cs_build_grid(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.num_particles) {
+        return;
+    }
+    let h = hash_cell(cell_coord(particles[i].position));
+    next_particle[i] = atomicExchange(&cell_head[h], i32(i));
+}
+
+@compute @workgroup_size(64)
+fn cs_density(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.num_particles) {
+        return;
+    }
+
+    let h = params.smoothing_radius;
+    let h2 = h * h;
+    let poly6 = 315.0 / (64.0 * PI * pow(h, 9.0));
+    let pi = particles[i].position;
+    let base = cell_coord(pi);
+
+    var density = 0.0;
+    var buckets = neighbor_buckets(base);
+    for (var k = 0; k < 27; k++) {
+        if (buckets[k] >= params.table_size) {
+            continue;
+        }
+        var j = atomicLoad(&cell_head[buckets[k]]);
+        while (j >= 0) {
+            let r = pi - particles[j].position;
+            let r2 = dot(r, r);
+            if (r2 < h2) {
+                let diff = h2 - r2;
+                density += params.particle_mass * poly6 * diff * diff * diff;
+            }
+            j = next_particle[j];
+        }
+    }
+
+    particles[i].density = max(density, params.rest_density * 0.01);
+    // Clamped equation of state: no attraction under rest density
+    particles[i].pressure = max(params.stiffness * (density - params.rest_density), 0.0);
+}
+
+@compute @workgroup_size(64)
+fn cs_forces(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.num_particles) {
+        return;
+    }
+
+    let h = params.smoothing_radius;
+    let spiky_grad = -45.0 / (PI * pow(h, 6.0));
+    let visc_lap = 45.0 / (PI * pow(h, 6.0));
+    let me = particles[i];
+    let base = cell_coord(me.position);
+
+    var pressure_force = vec3<f32>(0.0);
+    var viscosity_force = vec3<f32>(0.0);
+    var buckets = neighbor_buckets(base);
+    for (var k = 0; k < 27; k++) {
+        if (buckets[k] >= params.table_size) {
+            continue;
+        }
+        var j = atomicLoad(&cell_head[buckets[k]]);
+        while (j >= 0) {
+            if (u32(j) != i) {
+                let other = particles[j];
+                let r = me.position - other.position;
+                let dist = length(r);
+                if (dist < h && dist > 1e-6) {
+                    let dir = r / dist;
+                    let w = h - dist;
+                    pressure_force -= dir * params.particle_mass
+                        * (me.pressure + other.pressure) / (2.0 * other.density)
+                        * spiky_grad * w * w;
+                    viscosity_force += params.viscosity * params.particle_mass
+                        * (other.velocity - me.velocity) / other.density
+                        * visc_lap * w;
+                }
+            }
+            j = next_particle[j];
+        }
+    }
+
+    let accel = (pressure_force + viscosity_force) / me.density + params.gravity;
+    accelerations[i] = vec4<f32>(accel, 0.0);
+}
+
+// Signed distance and outward normal of a collider at point p.
+// Returns vec4(normal, distance). Unsupported shapes return a large distance.
+fn collider_sdf(s: ShapeData, p: vec3<f32>) -> vec4<f32> {
+    let d = p - s.position;
+    if (s.shape_type == 0u) {
+        let radius = s.data.x * max(s.scale_x, max(s.scale_y, s.scale_z));
+        let len = length(d);
+        var n = vec3<f32>(0.0, 1.0, 0.0);
+        if (len > 1e-6) {
+            n = d / len;
+        }
+        return vec4<f32>(n, len - radius);
+    }
+    if (s.shape_type == 1u) {
+        let local = vec3<f32>(dot(d, s.axis_x), dot(d, s.axis_y), dot(d, s.axis_z));
+        let half = s.data.xyz * vec3<f32>(s.scale_x, s.scale_y, s.scale_z);
+        let q = abs(local) - half;
+        let outside = max(q, vec3<f32>(0.0));
+        let outside_len = length(outside);
+        var local_n: vec3<f32>;
+        var dist: f32;
+        if (outside_len > 0.0) {
+            local_n = outside / outside_len * sign(local);
+            dist = outside_len;
+        } else {
+            // Inside: push out through the nearest face
+            if (q.x > q.y && q.x > q.z) {
+                local_n = vec3<f32>(sign(local.x), 0.0, 0.0);
+                dist = q.x;
+            } else if (q.y > q.z) {
+                local_n = vec3<f32>(0.0, sign(local.y), 0.0);
+                dist = q.y;
+            } else {
+                local_n = vec3<f32>(0.0, 0.0, sign(local.z));
+                dist = q.z;
+            }
+        }
+        let n = s.axis_x * local_n.x + s.axis_y * local_n.y + s.axis_z * local_n.z;
+        return vec4<f32>(normalize(n), dist);
+    }
+    return vec4<f32>(0.0, 1.0, 0.0, 1e30);
+}
+
+fn reflect_velocity(v: vec3<f32>, n: vec3<f32>) -> vec3<f32> {
+    let vn = dot(v, n);
+    if (vn < 0.0) {
+        return v - (1.0 + params.boundary_restitution) * vn * n;
+    }
+    return v;
+}
+
+@compute @workgroup_size(64)
+fn cs_integrate(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.num_particles) {
+        return;
+    }
+
+    var p = particles[i];
+    p.velocity += accelerations[i].xyz * params.dt;
+    p.position += p.velocity * params.dt;
+
+    // Static colliders: keep particles half a smoothing radius off the surface
+    let radius = params.smoothing_radius * 0.5;
+    for (var c = 0u; c < params.num_colliders; c++) {
+        let sdf = collider_sdf(colliders[c], p.position);
+        if (sdf.w < radius) {
+            p.position += sdf.xyz * (radius - sdf.w);
+            p.velocity = reflect_velocity(p.velocity, sdf.xyz);
+        }
+    }
+
+    // Domain bounds
+    for (var axis = 0; axis < 3; axis++) {
+        if (p.position[axis] < params.bounds_min[axis]) {
+            p.position[axis] = params.bounds_min[axis];
+            var n = vec3<f32>(0.0);
+            n[axis] = 1.0;
+            p.velocity = reflect_velocity(p.velocity, n);
+        }
+        if (p.position[axis] > params.bounds_max[axis]) {
+            p.position[axis] = params.bounds_max[axis];
+            var n = vec3<f32>(0.0);
+            n[axis] = -1.0;
+            p.velocity = reflect_velocity(p.velocity, n);
+        }
+    }
+
+    particles[i] = p;
+}
@@ -0,0 +1,79 @@
+// Selection outline post-processing shader
+// Draws an outline around the selection mask: red = selected coverage,
+// green = selected coverage not hidden behind other geometry
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) uv: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+struct OutlineUniform {
+    color: vec4<f32>,
+    hidden_color: vec4<f32>,
+    // x: width in pixels, y: x-ray (0 or 1)
+    params: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var input_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var input_sampler: sampler;
+@group(0) @binding(2)
+var mask_texture: texture_2d<f32>;
+@group(0) @binding(3)
+var<uniform> outline: OutlineUniform;
+
+// Keeps the neighborhood search bounded
+const MAX_WIDTH: i32 = 16;
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var output: VertexOutput;
+    output.position = vec4<f32>(input.position.xy, 0.0, 1.0);
+    output.uv = input.uv.xy;
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, input.uv);
+    let size = vec2<i32>(textureDimensions(mask_texture));
+    let coord = vec2<i32>(input.position.xy);
+    let center = textureLoad(mask_texture, clamp(coord, vec2<i32>(0), size - 1), 0);
+    let x_ray = outline.params.y > 0.5;
+
+    // Outline pixels lie just outside the visible (or, with x-ray, whole) silhouette
+    let in_visible = center.g > 0.5;
+    let in_selected = center.r > 0.5;
+    if in_visible || (x_ray && in_selected) {
+        return color;
+    }
+
+    let width = clamp(i32(outline.params.x + 0.5), 0, MAX_WIDTH);
+    var near_visible = false;
+    var near_selected = false;
+    for (var dy = -width; dy <= width; dy++) {
+        for (var dx = -width; dx <= width; dx++) {
+            if dx * dx + dy * dy > width * width {
+                continue;
+            }
+            let sample_coord = clamp(coord + vec2<i32>(dx, dy), vec2<i32>(0), size - 1);
+            let mask = textureLoad(mask_texture, sample_coord, 0);
+            near_visible = near_visible || mask.g > 0.5;
+            near_selected = near_selected || mask.r > 0.5;
+        }
+    }
+
+    if near_visible {
+        return vec4<f32>(mix(color.rgb, outline.color.rgb, outline.color.a), color.a);
+    }
+    if x_ray && near_selected {
+        return vec4<f32>(mix(color.rgb, outline.hidden_color.rgb, outline.hidden_color.a), color.a);
+    }
+    return color;
+}
@@ -0,0 +1,89 @@
+// SPH fluid point sprite shader
+//
+// Draws one camera-facing quad per particle, reading particle positions
+// straight from the solver's storage buffer via the instance index.
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    eye: vec4<f32>,
+};
+
+struct FluidParticle {
+    position: vec3<f32>,
+    density: f32,
+    velocity: vec3<f32>,
+    pressure: f32,
+};
+
+struct FluidRenderParams {
+    color: vec4<f32>,
+    fast_color: vec4<f32>,
+    radius: f32,
+    max_speed: f32,
+    _pad0: f32,
+    _pad1: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var<storage, read> particles: array<FluidParticle>;
+
+@group(1) @binding(1)
+var<uniform> render_params: FluidRenderParams;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+    @location(1) corner: vec2<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let particle = particles[instance_index];
+
+    let forward = normalize(camera.eye.xyz - particle.position);
+    var world_up = vec3<f32>(0.0, 1.0, 0.0);
+    if abs(dot(forward, world_up)) > 0.999 {
+        world_up = vec3<f32>(0.0, 0.0, 1.0);
+    }
+    let right = normalize(cross(world_up, forward));
+    let up = cross(forward, right);
+
+    let world_pos = particle.position
+        + (right * corner.x + up * corner.y) * render_params.radius;
+
+    let speed = length(particle.velocity);
+    let t = clamp(speed / max(render_params.max_speed, 1e-6), 0.0, 1.0);
+
+    var output: VertexOutput;
+    output.clip_position = camera.view_proj * vec4<f32>(world_pos, 1.0);
+    output.color = mix(render_params.color, render_params.fast_color, t);
+    output.corner = corner;
+    return output;
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let r2 = dot(input.corner, input.corner);
+    if r2 > 1.0 {
+        discard;
+    }
+    // Fake sphere shading from the disc normal
+    let n = vec3<f32>(input.corner, sqrt(1.0 - r2));
+    let light = 0.35 + 0.65 * max(dot(n, normalize(vec3<f32>(0.3, 0.6, 0.8))), 0.0);
+    return vec4<f32>(input.color.rgb * light, input.color.a);
+}
@@ -0,0 +1,65 @@
+// Picking ID pass shader
+// Writes a pick ID per pixel into an R32Uint target. Plain meshes take the ID
+// from their per-draw record; meshes with their own instance buffer add the
+// instance index to it
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    eye: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(4) col0: vec4<f32>,
+    @location(5) col1: vec4<f32>,
+    @location(6) col2: vec4<f32>,
+    @location(7) col3: vec4<f32>,
+};
+
+struct IdInput {
+    @location(9) col0: vec4<f32>,
+    @location(10) col1: vec4<f32>,
+    @location(11) col2: vec4<f32>,
+    @location(12) col3: vec4<f32>,
+    @location(13) id: u32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) @interpolate(flat) id: u32,
+};
+
+@vertex
+fn vs_main(input: VertexInput, record: IdInput) -> VertexOutput {
+    let model = mat4x4<f32>(record.col0, record.col1, record.col2, record.col3);
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * model * vec4<f32>(input.position, 1.0);
+    out.id = record.id;
+    return out;
+}
+
+@vertex
+fn vs_instanced(
+    input: VertexInput,
+    instance: InstanceInput,
+    record: IdInput,
+    @builtin(instance_index) index: u32,
+) -> VertexOutput {
+    let model = mat4x4<f32>(record.col0, record.col1, record.col2, record.col3);
+    let instance_model = mat4x4<f32>(instance.col0, instance.col1, instance.col2, instance.col3);
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * model * instance_model * vec4<f32>(input.position, 1.0);
+    out.id = record.id + index;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) u32 {
+    return in.id;
+}
@@ -0,0 +1,66 @@
+// Selection mask shader
+// Renders selected ECS entities into the outline mask: red marks coverage,
+// green the fragments not behind the scene depth buffer
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    eye: vec4<f32>,
+};
+
+struct ModelUniform {
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+@group(1) @binding(0)
+var<uniform> model: ModelUniform;
+
+@group(2) @binding(0)
+var scene_depth: texture_depth_2d;
+@group(2) @binding(1)
+var depth_sampler: sampler_comparison;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(4) col0: vec4<f32>,
+    @location(5) col1: vec4<f32>,
+    @location(6) col2: vec4<f32>,
+    @location(7) col3: vec4<f32>,
+};
+
+// Tolerance for the mask fragment matching the depth the scene pass wrote:
+// a constant plus half the depth change across the pixel
+const DEPTH_BIAS: f32 = 1e-6;
+const DEPTH_SLOPE_BIAS: f32 = 0.5;
+
+// Positions are computed exactly as in the material shaders and marked
+// invariant, so both passes rasterize the same depths
+@vertex
+fn vs_main(input: VertexInput) -> @builtin(position) @invariant vec4<f32> {
+    let world_pos = model.model * vec4<f32>(input.position, 1.0);
+    return camera.view_proj * world_pos;
+}
+
+// A mesh's own instances are placed relative to its entity
+@vertex
+fn vs_instanced(input: VertexInput, instance: InstanceInput) -> @builtin(position) @invariant vec4<f32> {
+    let instance_model = mat4x4<f32>(instance.col0, instance.col1, instance.col2, instance.col3);
+    let world_pos = model.model * instance_model * vec4<f32>(input.position, 1.0);
+    return camera.view_proj * world_pos;
+}
+
+// Blended additively, so overlapping fragments combine like a logical OR
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    // A LessEqual comparison against the depth under this pixel
+    let uv = position.xy / vec2<f32>(textureDimensions(scene_depth));
+    let bias = DEPTH_BIAS + DEPTH_SLOPE_BIAS * fwidth(position.z);
+    let visible = textureSampleCompareLevel(scene_depth, depth_sampler, uv, position.z - bias);
+    return vec4<f32>(1.0, visible, 0.0, 1.0);
+}
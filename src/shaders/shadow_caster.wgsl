@@ -0,0 +1,46 @@
+// Shadow caster depth pass shader
+// Renders ECS shadow casters from the light, sharing the camera and model
+// uniform layouts of the material shaders
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    eye: vec4<f32>,
+};
+
+struct ModelUniform {
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> light: CameraUniform;
+
+@group(1) @binding(0)
+var<uniform> model: ModelUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct InstanceInput {
+    @location(4) col0: vec4<f32>,
+    @location(5) col1: vec4<f32>,
+    @location(6) col2: vec4<f32>,
+    @location(7) col3: vec4<f32>,
+};
+
+@vertex
+fn vs_main(input: VertexInput) -> @builtin(position) vec4<f32> {
+    return light.view_proj * model.model * vec4<f32>(input.position, 1.0);
+}
+
+@vertex
+fn vs_instanced(input: VertexInput, instance: InstanceInput) -> @builtin(position) vec4<f32> {
+    let instance_model = mat4x4<f32>(instance.col0, instance.col1, instance.col2, instance.col3);
+    return light.view_proj * instance_model * vec4<f32>(input.position, 1.0);
+}
+
+// Depth is written automatically by the depth attachment
+@fragment
+fn fs_main() {
+}
@@ -0,0 +1,157 @@
+//! Game clock with pause, time scale and single-stepping.
+
+/// Game clock advanced once per frame.
+///
+/// Separates game time, which is clamped, scaled and stopped while paused,
+/// from unscaled (real) time. The engine stores one as a resource and feeds
+/// its game time to the fixed-step loop, including engine-managed physics;
+/// a standalone [`PhysicsWorld`] owns one for [`PhysicsWorld::step`].
+///
+/// [`PhysicsWorld`]: crate::physics::PhysicsWorld
+/// [`PhysicsWorld::step`]: crate::physics::PhysicsWorld::step
+#[derive(Debug, Clone, Copy)]
+pub struct Time {
+    paused: bool,
+    scale: f64,
+    max_delta: f64,
+    step_requested: bool,
+    delta: f64,
+    elapsed: f64,
+    unscaled_delta: f64,
+    unscaled_elapsed: f64,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self {
+            paused: false,
+            scale: 1.0,
+            max_delta: 0.25,
+            step_requested: false,
+            delta: 0.0,
+            elapsed: 0.0,
+            unscaled_delta: 0.0,
+            unscaled_elapsed: 0.0,
+        }
+    }
+}
+
+impl Time {
+    /// Create a running clock with a time scale of 1 and a max delta of 0.25s.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop game time.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Restart game time.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Pause if running, resume if paused.
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Whether game time is stopped.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Advance game time by exactly one step on the next frame, even while
+    /// paused. Time scale and max delta do not apply to the step.
+    pub fn step(&mut self) {
+        self.step_requested = true;
+    }
+
+    /// Game seconds per real second.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Set the time scale, e.g. 0.25 for slow motion. Negative values are
+    /// treated as 0.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(0.0);
+    }
+
+    /// Longest real frame time counted as game time (seconds).
+    pub fn max_delta(&self) -> f64 {
+        self.max_delta
+    }
+
+    /// Set the longest real frame time counted as game time, so a hitch
+    /// (breakpoint, window drag) does not advance the game by seconds.
+    pub fn set_max_delta(&mut self, max_delta: f64) {
+        self.max_delta = max_delta.max(0.0);
+    }
+
+    /// Game time of the last frame (seconds).
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+
+    /// Game time since the clock started (seconds).
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Real time of the last frame (seconds).
+    pub fn unscaled_delta(&self) -> f64 {
+        self.unscaled_delta
+    }
+
+    /// Real time since the clock started (seconds).
+    pub fn unscaled_elapsed(&self) -> f64 {
+        self.unscaled_elapsed
+    }
+
+    /// Advance by a frame of `real_delta` seconds and return the game delta.
+    ///
+    /// A pending [`step`](Self::step) advances by exactly `step_size`.
+    pub fn advance(&mut self, real_delta: f64, step_size: f64) -> f64 {
+        self.unscaled_delta = real_delta;
+        self.unscaled_elapsed += real_delta;
+        self.delta = if self.step_requested {
+            self.step_requested = false;
+            step_size
+        } else if self.paused {
+            0.0
+        } else {
+            real_delta.min(self.max_delta) * self.scale
+        };
+        self.elapsed += self.delta;
+        self.delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_scale_step_and_clamp() {
+        let mut time = Time::new();
+        time.set_scale(0.5);
+        assert_eq!(time.advance(0.1, 0.01), 0.05);
+        // Clamped to max_delta before scaling
+        assert_eq!(time.advance(2.0, 0.01), 0.125);
+
+        time.pause();
+        assert_eq!(time.advance(0.1, 0.01), 0.0);
+        time.step();
+        assert_eq!(time.advance(0.1, 0.01), 0.01);
+        assert_eq!(time.advance(0.1, 0.01), 0.0);
+
+        time.resume();
+        time.set_scale(1.0);
+        assert_eq!(time.advance(0.1, 0.01), 0.1);
+        assert!((time.elapsed() - 0.285).abs() < 1e-12);
+        assert!((time.unscaled_elapsed() - 2.5).abs() < 1e-12);
+        assert_eq!(time.unscaled_delta(), 0.1);
+    }
+}
@@ -2,33 +2,61 @@
 //!
 //! Provides a renderable robot model from URDF.
 
+use crate::assets::{Assets, Handle};
 use crate::context::WgpuContext;
 use crate::core::pipeline::Vertex;
 use crate::renderer::geometry::{Aabb, Geometry, Mesh};
 use crate::renderer::light::Light;
-use crate::renderer::material::{ColorMaterial, GridMaterial, Material};
+use crate::renderer::material::{ColorMaterial, GridMaterial, Material, ModelUniformRing};
 use crate::renderer::viewer::Viewer;
 use crate::urdf::loader::{GeometryType, JointInfo, UrdfLoader};
 use glam::{Mat4, Quat, Vec3};
 use std::collections::HashMap;
 use std::path::Path;
-#[cfg(feature = "ecs")]
 use std::sync::Arc;
 
 /// A link in the robot model with its mesh and material.
 struct RobotLink {
     name: String,
-    mesh: Mesh,
+    /// Shared between links with the same geometry and color.
+    mesh: Arc<Mesh>,
     local_transform: Mat4,
     world_transform: Mat4,
 }
 
+/// Identity of a link visual's mesh: geometry kind, its dimensions and the
+/// color, compared bitwise.
+#[derive(PartialEq, Eq, Hash)]
+struct MeshKey {
+    kind: u8,
+    dimensions: [u32; 3],
+    color: [u32; 3],
+}
+
+impl MeshKey {
+    fn new(geometry: &GeometryType, color: [f32; 3]) -> Self {
+        let (kind, dimensions) = match *geometry {
+            GeometryType::Box { size } => (0, size),
+            GeometryType::Cylinder { radius, height } => (1, [radius, height, 0.0]),
+            GeometryType::Sphere { radius } => (2, [radius, 0.0, 0.0]),
+            GeometryType::Capsule { radius, length } => (3, [radius, length, 0.0]),
+        };
+        Self {
+            kind,
+            dimensions: dimensions.map(f32::to_bits),
+            color: color.map(f32::to_bits),
+        }
+    }
+}
+
 /// A renderable robot model loaded from URDF.
 pub struct RobotModel {
     links: Vec<RobotLink>,
     joints: Vec<JointInfo>,
     link_transforms: HashMap<String, Mat4>,
     material: ColorMaterial,
+    /// Per-link model uniforms, since all links share `material`.
+    model_ring: ModelUniformRing,
     grid_mesh: Mesh,
     grid_material: GridMaterial,
 }
@@ -46,9 +74,17 @@ impl RobotModel {
         let grid_material = GridMaterial::new(ctx, format)?;
 
         let mut links = Vec::with_capacity(urdf_model.link_visuals.len());
+        let mut meshes = Assets::<Mesh>::new();
+        let mut handles: HashMap<MeshKey, Handle<Mesh>> = HashMap::new();
 
         for visual in &urdf_model.link_visuals {
-            let mesh = Self::create_mesh(ctx, &visual.geometry, visual.color);
+            // Repeated shapes (fingers, wheels) share one mesh
+            let handle = handles
+                .entry(MeshKey::new(&visual.geometry, visual.color))
+                .or_insert_with(|| {
+                    meshes.add(Self::create_mesh(ctx, &visual.geometry, visual.color))
+                });
+            let mesh = meshes.get(handle).expect("mesh was just added");
 
             links.push(RobotLink {
                 name: visual.link_name.clone(),
@@ -65,6 +101,7 @@ impl RobotModel {
             joints: urdf_model.joints,
             link_transforms: HashMap::new(),
             material,
+            model_ring: ModelUniformRing::new(ctx),
             grid_mesh,
             grid_material,
         };
@@ -317,7 +354,7 @@ impl RobotModel {
 
     /// Render the robot model.
     pub fn render(
-        &self,
+        &mut self,
         ctx: &WgpuContext,
         viewer: &dyn Viewer,
         lights: &[&dyn Light],
@@ -325,7 +362,7 @@ impl RobotModel {
     ) {
         // Render grid first
         self.grid_material.update_camera(ctx, viewer);
-        render_pass.set_pipeline(self.grid_material.pipeline());
+        render_pass.set_pipeline(&self.grid_material.pipeline());
         render_pass.set_bind_group(0, self.grid_material.camera_bind_group(), &[]);
         render_pass.set_vertex_buffer(0, self.grid_mesh.vertex_buffer().slice());
         if let Some(index_buffer) = self.grid_mesh.index_buffer() {
@@ -334,13 +371,22 @@ impl RobotModel {
         }
 
         // Render robot links
-        for link in &self.links {
-            self.material
-                .update_uniforms(ctx, viewer, link.world_transform, lights);
-
-            render_pass.set_pipeline(self.material.pipeline());
-            render_pass.set_bind_group(0, self.material.camera_bind_group(), &[]);
-            render_pass.set_bind_group(1, self.material.model_bind_group(), &[]);
+        self.material.update_shared_uniforms(ctx, viewer, lights);
+        self.model_ring.clear();
+        let offsets: Vec<u32> = self
+            .links
+            .iter()
+            .map(|link| self.model_ring.push_matrix(link.world_transform))
+            .collect();
+        self.model_ring.upload(ctx);
+
+        render_pass.set_pipeline(&self.material.pipeline());
+        render_pass.set_bind_group(0, self.material.camera_bind_group(), &[0]);
+        for (group, bind_group) in self.material.extra_bind_groups() {
+            render_pass.set_bind_group(group, bind_group, &[]);
+        }
+        for (link, offset) in self.links.iter().zip(offsets) {
+            render_pass.set_bind_group(1, self.model_ring.bind_group(), &[offset]);
             render_pass.set_vertex_buffer(0, link.mesh.vertex_buffer().slice());
 
             if let Some(index_buffer) = link.mesh.index_buffer() {
@@ -354,7 +400,8 @@ impl RobotModel {
 
     /// Spawn the robot as ECS entities with parent-child hierarchy.
     ///
-    /// Consumes the `RobotModel` and moves each link's mesh into the ECS world.
+    /// Consumes the `RobotModel` and moves each link's mesh into the ECS world;
+    /// links with the same geometry share one mesh.
     /// Each link becomes an entity with Transform, GlobalTransform, and MeshRenderer.
     /// The hierarchy follows the URDF joint tree.
     /// Returns the root entity.
@@ -367,7 +414,7 @@ impl RobotModel {
         use crate::ecs::components::rendering::{
             FrustumCullable, MaterialHandle, MeshHandle, MeshRenderer, Visible,
         };
-        use crate::ecs::components::transform::{Children, GlobalTransform, Parent, Transform};
+        use crate::ecs::components::transform::{GlobalTransform, Parent, Transform};
 
         let material_arc: Arc<dyn crate::ecs::bridge::MaterialResource> = Arc::new(self.material);
 
@@ -378,7 +425,7 @@ impl RobotModel {
             let global = GlobalTransform(link.world_transform);
 
             let renderer = MeshRenderer {
-                mesh: MeshHandle(Arc::new(link.mesh)),
+                mesh: MeshHandle(link.mesh),
                 material: MaterialHandle(material_arc.clone()),
                 visible: true,
                 cast_shadow: true,
@@ -390,25 +437,15 @@ impl RobotModel {
         }
 
         // Build parent-child relationships from joints
-        let mut children_map: HashMap<hecs::Entity, Vec<hecs::Entity>> = HashMap::new();
-
         for joint in &self.joints {
             if let (Some(&parent_entity), Some(&child_entity)) = (
                 link_entities.get(&joint.parent_link),
                 link_entities.get(&joint.child_link),
             ) {
-                world.insert_one(child_entity, Parent(parent_entity)).ok();
-                children_map
-                    .entry(parent_entity)
-                    .or_default()
-                    .push(child_entity);
+                crate::ecs::hierarchy::set_parent(world, child_entity, parent_entity)?;
             }
         }
 
-        for (parent_entity, children) in children_map {
-            world.insert_one(parent_entity, Children(children)).ok();
-        }
-
         // Find root: base_link or first link without a Parent
         let root = link_entities
             .get("base_link")